@@ -0,0 +1,19 @@
+use super::{ApiKeyLocation, Secret};
+
+/// `#[http_client(auth = ...)]` 解析得到的认证配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthConfig {
+    /// `bearer(env = "API_TOKEN")`
+    Bearer { token: Secret },
+    /// `basic(username = "...", password_env = "...")`
+    Basic { username: Secret, password: Secret },
+    /// `api_key(header = "X-Api-Key", env = "API_KEY")` 或 `api_key(query = "api_key", ...)`
+    ApiKey { location: ApiKeyLocation, key: Secret },
+    /// `oauth2_client_credentials(token_url = "...", client_id = "...", client_secret_env = "...", scopes = [...])`
+    OAuth2ClientCredentials {
+        token_url: String,
+        client_id: Secret,
+        client_secret: Secret,
+        scopes: Vec<String>,
+    },
+}
@@ -0,0 +1,9 @@
+pub mod config;
+pub mod secret;
+pub mod provider;
+pub mod oauth2;
+
+pub use config::AuthConfig;
+pub use secret::Secret;
+pub use provider::{ApiKeyAuth, ApiKeyLocation, AuthClient, BasicAuth, BearerAuth, SwanAuth};
+pub use oauth2::OAuth2ClientCredentials;
@@ -0,0 +1,212 @@
+use super::provider::sensitive_header;
+use super::{AuthClient, Secret, SwanAuth};
+use crate::error::SwanError;
+use async_trait::async_trait;
+use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderValue};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 令牌到期前提前刷新的时间，短期令牌最多提前其有效期的一半
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// OAuth2 客户端凭据模式（client credentials grant）
+///
+/// 首次请求时向 `token_url` 获取访问令牌并缓存，到期前提前刷新；
+/// 请求被 401 拒绝时刷新一次令牌并重发。并发请求共享同一次刷新。
+/// 客户端 ID 与密钥以 HTTP Basic 认证发送给令牌端点。
+#[derive(Debug)]
+pub struct OAuth2ClientCredentials {
+    token_url: String,
+    client_id: Secret,
+    client_secret: Secret,
+    scopes: Vec<String>,
+    token: Mutex<Option<AccessToken>>,
+}
+
+#[derive(Debug)]
+struct AccessToken {
+    header: HeaderValue,
+    /// 需要刷新的时间，令牌端点未返回 `expires_in` 时为 `None`（直到被 401 拒绝）
+    refresh_at: Option<Instant>,
+}
+
+impl AccessToken {
+    fn is_fresh(&self) -> bool {
+        self.refresh_at.is_none_or(|refresh_at| Instant::now() < refresh_at)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl OAuth2ClientCredentials {
+    pub fn new(token_url: impl Into<String>, client_id: impl Into<Secret>, client_secret: impl Into<Secret>) -> Self {
+        Self {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            scopes: Vec::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// 设置申请的权限范围
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 向令牌端点申请新的访问令牌
+    async fn fetch(&self, client: &AuthClient<'_>) -> Result<AccessToken, SwanError> {
+        let mut form = vec![("grant_type", "client_credentials".to_string())];
+        if !self.scopes.is_empty() {
+            form.push(("scope", self.scopes.join(" ")));
+        }
+
+        let request = client.client()
+            .post(&self.token_url)
+            .basic_auth(self.client_id.resolve()?, Some(self.client_secret.resolve()?))
+            .header(ACCEPT, "application/json")
+            .form(&form)
+            .build()
+            .map_err(SwanError::from)?;
+
+        let requested_at = Instant::now();
+        let response = client.execute(request).await?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(SwanError::from)?;
+        if !status.is_success() {
+            return Err(SwanError::auth(format!(
+                "Token endpoint returned {}: {}",
+                status,
+                String::from_utf8_lossy(&body),
+            )));
+        }
+
+        let token: TokenResponse = serde_json::from_slice(&body)
+            .map_err(|e| SwanError::auth(format!("Invalid token response: {}", e)))?;
+        log::debug!("Fetched OAuth2 access token from {}", self.token_url);
+
+        Ok(AccessToken {
+            header: sensitive_header(&format!("Bearer {}", token.access_token))?,
+            refresh_at: token.expires_in.map(|expires_in| {
+                let lifetime = Duration::from_secs(expires_in);
+                requested_at + lifetime - REFRESH_MARGIN.min(lifetime / 2)
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl SwanAuth for OAuth2ClientCredentials {
+    async fn authorize(&self, client: &AuthClient<'_>, request: &mut reqwest::Request) -> Result<(), SwanError> {
+        let mut token = self.token.lock().await;
+        let header = match token.as_ref().filter(|token| token.is_fresh()) {
+            Some(token) => token.header.clone(),
+            None => {
+                let fresh = self.fetch(client).await?;
+                let header = fresh.header.clone();
+                *token = Some(fresh);
+                header
+            }
+        };
+        drop(token);
+
+        request.headers_mut().insert(AUTHORIZATION, header);
+        Ok(())
+    }
+
+    async fn refresh(&self, client: &AuthClient<'_>, rejected: &reqwest::Request) -> Result<bool, SwanError> {
+        let mut token = self.token.lock().await;
+        // 等待锁期间其他请求已经换了新令牌，直接用新令牌重发
+        if let Some(current) = token.as_ref()
+            && rejected.headers().get(AUTHORIZATION) != Some(&current.header)
+        {
+            return Ok(true);
+        }
+
+        *token = None;
+        *token = Some(self.fetch(client).await?);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockResponse, MockTransport};
+
+    fn request() -> reqwest::Request {
+        reqwest::Request::new(reqwest::Method::GET, "https://api.example.com/items".parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_token_is_cached_and_refreshed_on_401() {
+        let transport = MockTransport::new();
+        let token_endpoint = transport.mock("POST", "/oauth/token")
+            .header("Authorization", "Basic aWQ6c2VjcmV0")
+            .body("grant_type=client_credentials&scope=read+write")
+            .respond_sequence([
+                MockResponse::json(r#"{"access_token":"t1","token_type":"bearer","expires_in":3600}"#),
+                MockResponse::json(r#"{"access_token":"t2","token_type":"bearer","expires_in":3600}"#),
+            ]);
+
+        let client = reqwest::Client::new();
+        let client = AuthClient::new(&client, Some(&transport));
+        let auth = OAuth2ClientCredentials::new("https://auth.example.com/oauth/token", "id", "secret")
+            .scopes(["read", "write"]);
+
+        let mut first = request();
+        auth.authorize(&client, &mut first).await.unwrap();
+        let mut second = request();
+        auth.authorize(&client, &mut second).await.unwrap();
+        assert_eq!(second.headers().get(AUTHORIZATION).unwrap(), "Bearer t1");
+        token_endpoint.assert_calls(1);
+
+        // 两个请求都被拒绝时只刷新一次
+        assert!(auth.refresh(&client, &first).await.unwrap());
+        assert!(auth.refresh(&client, &second).await.unwrap());
+        token_endpoint.assert_calls(2);
+
+        let mut retried = request();
+        auth.authorize(&client, &mut retried).await.unwrap();
+        assert_eq!(retried.headers().get(AUTHORIZATION).unwrap(), "Bearer t2");
+    }
+
+    #[tokio::test]
+    async fn test_expiring_token_is_refreshed_before_use() {
+        let transport = MockTransport::new();
+        let token_endpoint = transport.mock("POST", "/oauth/token")
+            .respond(MockResponse::json(r#"{"access_token":"short","expires_in":0}"#));
+
+        let client = reqwest::Client::new();
+        let client = AuthClient::new(&client, Some(&transport));
+        let auth = OAuth2ClientCredentials::new("https://auth.example.com/oauth/token", "id", "secret");
+
+        auth.authorize(&client, &mut request()).await.unwrap();
+        auth.authorize(&client, &mut request()).await.unwrap();
+        token_endpoint.assert_calls(2);
+    }
+
+    #[tokio::test]
+    async fn test_token_endpoint_error() {
+        let transport = MockTransport::new();
+        transport.mock("POST", "/oauth/token").respond(MockResponse::new(401).body("invalid_client"));
+
+        let client = reqwest::Client::new();
+        let client = AuthClient::new(&client, Some(&transport));
+        let auth = OAuth2ClientCredentials::new("https://auth.example.com/oauth/token", "id", "secret");
+
+        let error = auth.authorize(&client, &mut request()).await.unwrap_err();
+        assert_eq!(error.kind(), "auth");
+        assert!(error.to_string().contains("invalid_client"));
+    }
+}
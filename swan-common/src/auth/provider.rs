@@ -0,0 +1,215 @@
+use super::Secret;
+use crate::error::SwanError;
+use crate::testing::MockTransport;
+use crate::url::set_query_param;
+use async_trait::async_trait;
+use base64::Engine;
+use reqwest::header::{AUTHORIZATION, HeaderName, HeaderValue};
+use std::fmt;
+
+/// 客户端级认证
+///
+/// 通过 `#[http_client(auth = ...)]` 或客户端构建器的 `auth()` 设置。每次尝试（包括重试）发送前调用
+/// [`authorize`](SwanAuth::authorize)；响应为 401 时调用 [`refresh`](SwanAuth::refresh)，
+/// 返回 `true` 时重新授权并重发一次。
+#[async_trait]
+pub trait SwanAuth: Send + Sync {
+    /// 为请求添加凭据
+    async fn authorize(&self, client: &AuthClient<'_>, request: &mut reqwest::Request) -> Result<(), SwanError>;
+
+    /// 请求被 401 拒绝后更新凭据，`rejected` 为被拒绝的请求
+    ///
+    /// 默认不更新，401 响应原样返回。
+    async fn refresh(&self, _client: &AuthClient<'_>, _rejected: &reqwest::Request) -> Result<bool, SwanError> {
+        Ok(false)
+    }
+
+    /// 凭据写入的查询参数名称，追踪 span 记录 `url.full` 时隐去它们的值
+    ///
+    /// 默认没有。
+    fn query_params(&self) -> &[String] {
+        &[]
+    }
+}
+
+impl fmt::Debug for dyn SwanAuth + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SwanAuth")
+    }
+}
+
+/// 认证过程中发送请求（如获取令牌）使用的客户端
+///
+/// 与业务请求共享连接池、代理与超时配置；客户端使用模拟传输层时，请求同样交给模拟传输层。
+#[derive(Debug, Clone, Copy)]
+pub struct AuthClient<'a> {
+    client: &'a reqwest::Client,
+    transport: Option<&'a MockTransport>,
+}
+
+impl<'a> AuthClient<'a> {
+    pub fn new(client: &'a reqwest::Client, transport: Option<&'a MockTransport>) -> Self {
+        Self { client, transport }
+    }
+
+    /// 用于构建请求的 `reqwest::Client`
+    pub fn client(&self) -> &'a reqwest::Client {
+        self.client
+    }
+
+    /// 发送请求，不经过拦截器、重试与认证
+    pub async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response, SwanError> {
+        match self.transport {
+            Some(transport) => transport.execute(request).await,
+            None => self.client.execute(request).await.map_err(SwanError::from),
+        }
+    }
+}
+
+/// 生成不会出现在日志中的请求头取值
+pub(crate) fn sensitive_header(value: &str) -> Result<HeaderValue, SwanError> {
+    let mut value = HeaderValue::from_str(value)
+        .map_err(|_| SwanError::auth("Credential contains characters not allowed in a header"))?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// `Authorization: Bearer <token>`
+#[derive(Debug, Clone)]
+pub struct BearerAuth {
+    token: Secret,
+}
+
+impl BearerAuth {
+    pub fn new(token: impl Into<Secret>) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+#[async_trait]
+impl SwanAuth for BearerAuth {
+    async fn authorize(&self, _client: &AuthClient<'_>, request: &mut reqwest::Request) -> Result<(), SwanError> {
+        let header = sensitive_header(&format!("Bearer {}", self.token.resolve()?))?;
+        request.headers_mut().insert(AUTHORIZATION, header);
+        Ok(())
+    }
+}
+
+/// `Authorization: Basic <base64(username:password)>`
+#[derive(Debug, Clone)]
+pub struct BasicAuth {
+    username: Secret,
+    password: Secret,
+}
+
+impl BasicAuth {
+    pub fn new(username: impl Into<Secret>, password: impl Into<Secret>) -> Self {
+        Self { username: username.into(), password: password.into() }
+    }
+}
+
+#[async_trait]
+impl SwanAuth for BasicAuth {
+    async fn authorize(&self, _client: &AuthClient<'_>, request: &mut reqwest::Request) -> Result<(), SwanError> {
+        let credentials = format!("{}:{}", self.username.resolve()?, self.password.resolve()?);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        request.headers_mut().insert(AUTHORIZATION, sensitive_header(&format!("Basic {}", encoded))?);
+        Ok(())
+    }
+}
+
+/// API Key 的位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    /// 请求头名称
+    Header(String),
+    /// 查询参数名称
+    Query(String),
+}
+
+/// 以请求头或查询参数携带的 API Key
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuth {
+    location: ApiKeyLocation,
+    key: Secret,
+}
+
+impl ApiKeyAuth {
+    /// 放在请求头 `name` 中
+    pub fn header(name: impl Into<String>, key: impl Into<Secret>) -> Self {
+        Self { location: ApiKeyLocation::Header(name.into()), key: key.into() }
+    }
+
+    /// 放在查询参数 `name` 中
+    pub fn query(name: impl Into<String>, key: impl Into<Secret>) -> Self {
+        Self { location: ApiKeyLocation::Query(name.into()), key: key.into() }
+    }
+}
+
+#[async_trait]
+impl SwanAuth for ApiKeyAuth {
+    async fn authorize(&self, _client: &AuthClient<'_>, request: &mut reqwest::Request) -> Result<(), SwanError> {
+        let key = self.key.resolve()?;
+        match &self.location {
+            ApiKeyLocation::Header(name) => {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| SwanError::auth(format!("Invalid API key header name: {}", name)))?;
+                request.headers_mut().insert(name, sensitive_header(&key)?);
+            }
+            ApiKeyLocation::Query(name) => set_query_param(request.url_mut(), name, &key),
+        }
+        Ok(())
+    }
+
+    fn query_params(&self) -> &[String] {
+        match &self.location {
+            ApiKeyLocation::Header(_) => &[],
+            ApiKeyLocation::Query(name) => std::slice::from_ref(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn authorize(auth: &dyn SwanAuth, url: &str) -> reqwest::Request {
+        let client = reqwest::Client::new();
+        let mut request = reqwest::Request::new(reqwest::Method::GET, url.parse().unwrap());
+        auth.authorize(&AuthClient::new(&client, None), &mut request).await.unwrap();
+        request
+    }
+
+    #[tokio::test]
+    async fn test_bearer_and_basic() {
+        let request = authorize(&BearerAuth::new("abc"), "https://api.example.com/").await;
+        let header = request.headers().get(AUTHORIZATION).unwrap();
+        assert_eq!(header, "Bearer abc");
+        assert!(header.is_sensitive());
+
+        let request = authorize(&BasicAuth::new("aladdin", "opensesame"), "https://api.example.com/").await;
+        assert_eq!(request.headers().get(AUTHORIZATION).unwrap(), "Basic YWxhZGRpbjpvcGVuc2VzYW1l");
+    }
+
+    #[tokio::test]
+    async fn test_api_key() {
+        let request = authorize(&ApiKeyAuth::header("X-Api-Key", "k1"), "https://api.example.com/").await;
+        assert_eq!(request.headers().get("x-api-key").unwrap(), "k1");
+
+        let request = authorize(&ApiKeyAuth::query("api_key", "k2"), "https://api.example.com/items?api_key=old&page=2").await;
+        assert_eq!(request.url().as_str(), "https://api.example.com/items?page=2&api_key=k2");
+    }
+
+    #[tokio::test]
+    async fn test_missing_env_fails_before_sending() {
+        let client = reqwest::Client::new();
+        let mut request = reqwest::Request::new(reqwest::Method::GET, "https://api.example.com/".parse().unwrap());
+
+        let error = BearerAuth::new(Secret::env("SWAN_TEST_TOKEN_NOT_SET"))
+            .authorize(&AuthClient::new(&client, None), &mut request)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, SwanError::Auth(_)));
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::error::SwanError;
+use std::fmt;
+
+/// 凭据取值：直接给出，或在每次使用时从环境变量读取
+///
+/// 从环境变量读取时，轮换凭据只需更新环境变量；变量缺失时请求返回 [`SwanError::Auth`]。
+#[derive(Clone, PartialEq, Eq)]
+pub enum Secret {
+    /// 直接给出的取值
+    Value(String),
+    /// 环境变量名
+    Env(String),
+}
+
+impl Secret {
+    /// 从环境变量读取的凭据
+    pub fn env(name: impl Into<String>) -> Self {
+        Secret::Env(name.into())
+    }
+
+    /// 读取凭据的当前取值
+    pub fn resolve(&self) -> Result<String, SwanError> {
+        match self {
+            Secret::Value(value) => Ok(value.clone()),
+            Secret::Env(name) => std::env::var(name)
+                .map_err(|_| SwanError::auth(format!("Environment variable {} is not set", name))),
+        }
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret::Value(value.to_string())
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret::Value(value)
+    }
+}
+
+/// 不输出凭据本身
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Secret::Value(_) => f.write_str("Secret(***)"),
+            Secret::Env(name) => write!(f, "Secret(env {})", name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(Secret::from("token").resolve().unwrap(), "token");
+
+        let error = Secret::env("SWAN_TEST_SECRET_NOT_SET").resolve().unwrap_err();
+        assert_eq!(error.kind(), "auth");
+        assert!(error.to_string().contains("SWAN_TEST_SECRET_NOT_SET"));
+    }
+
+    #[test]
+    fn test_debug_hides_value() {
+        assert_eq!(format!("{:?}", Secret::from("token")), "Secret(***)");
+        assert_eq!(format!("{:?}", Secret::env("API_TOKEN")), "Secret(env API_TOKEN)");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::types::RetryConfig;
+use std::time::Duration;
+
+/// 方法级 HTTP 缓存配置
+///
+/// 响应的 `Cache-Control: max-age` 优先；响应未声明新鲜期时使用 `max_age`，
+/// 两者都没有时缓存的响应每次都要用 `If-None-Match` / `If-Modified-Since` 重新验证。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheConfig {
+    /// 响应未声明 `max-age` 时的新鲜期
+    pub max_age: Option<Duration>,
+}
+
+impl CacheConfig {
+    /// 使用响应头决定新鲜期的配置
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置响应未声明 `max-age` 时的新鲜期
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// 解析 `"max_age=60s"` 形式的配置
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut result = Self::new();
+
+        for param in config.split(',').map(str::trim).filter(|param| !param.is_empty()) {
+            let (key, value) = param.split_once('=')
+                .ok_or_else(|| format!("Expected key=value in cache config: {}", param))?;
+
+            match key.trim() {
+                "max_age" => match RetryConfig::parse_duration(value.trim())? {
+                    0 => return Err("max_age must be greater than zero".to_string()),
+                    millis => result.max_age = Some(Duration::from_millis(millis)),
+                },
+                key => return Err(format!("Unknown cache parameter: {} (expected max_age)", key)),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_age() {
+        let config = CacheConfig::parse("max_age=60s").unwrap();
+        assert_eq!(config, CacheConfig::new().max_age(Duration::from_secs(60)));
+        assert_eq!(CacheConfig::parse(" max_age = 500ms ").unwrap().max_age, Some(Duration::from_millis(500)));
+        assert_eq!(CacheConfig::parse("").unwrap(), CacheConfig::new());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(CacheConfig::parse("max_age").is_err());
+        assert!(CacheConfig::parse("max_age=0s").is_err());
+        assert!(CacheConfig::parse("ttl=60s").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use super::{CachedResponse, SwanCacheStore};
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 磁盘缓存存储
+///
+/// 每条响应保存为目录下的一个文件：第一行是 JSON 元信息（键、状态码、响应头、时间），
+/// 其后是原始响应体。文件名取键的哈希，读取时校验文件中的键，避免哈希冲突返回错误的响应。
+/// 进程重启后缓存仍然有效；读写失败只记录日志并当作未命中。
+#[derive(Debug, Clone)]
+pub struct DiskCacheStore {
+    dir: PathBuf,
+}
+
+impl DiskCacheStore {
+    /// 使用 `dir` 保存缓存文件，目录不存在时在首次写入时创建
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 缓存目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.cache", fnv1a(key.as_bytes())))
+    }
+
+    async fn write(&self, key: &str, response: &CachedResponse) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let mut contents = encode_metadata(key, response).to_string().into_bytes();
+        contents.push(b'\n');
+        contents.extend_from_slice(&response.body);
+
+        // 先写临时文件再重命名，并发读取不会看到写了一半的文件
+        let path = self.path(key);
+        let temp = path.with_extension(format!("{}.tmp", fastrand::u64(..)));
+        tokio::fs::write(&temp, contents).await?;
+        if let Err(error) = tokio::fs::rename(&temp, &path).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(error);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SwanCacheStore for DiskCacheStore {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let contents = match tokio::fs::read(self.path(key)).await {
+            Ok(contents) => Bytes::from(contents),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                log::warn!("Failed to read cache entry for {}: {}", key, error);
+                return None;
+            }
+        };
+
+        let response = decode(key, contents);
+        if response.is_none() {
+            log::warn!("Ignoring invalid cache entry for {}", key);
+        }
+        response
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) {
+        if let Err(error) = self.write(key, &response).await {
+            log::warn!("Failed to write cache entry for {}: {}", key, error);
+        }
+    }
+
+    async fn remove(&self, key: &str) {
+        match tokio::fs::remove_file(self.path(key)).await {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                log::warn!("Failed to remove cache entry for {}: {}", key, error);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// FNV-1a 64 位哈希，结果不随 Rust 版本变化，可用作文件名
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn millis_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_millis() as u64)
+}
+
+/// 取值不是合法 UTF-8 的响应头不会写入磁盘
+fn encode_metadata(key: &str, response: &CachedResponse) -> Value {
+    let headers: Vec<Value> = response.headers
+        .iter()
+        .filter_map(|(name, value)| Some(json!([name.as_str(), value.to_str().ok()?])))
+        .collect();
+    let vary: Vec<Value> = response.vary
+        .iter()
+        .map(|(name, value)| json!([name.as_str(), value.as_ref().and_then(|value| value.to_str().ok())]))
+        .collect();
+
+    json!({
+        "key": key,
+        "status": response.status.as_u16(),
+        "headers": headers,
+        "stored_at": millis_since_epoch(response.stored_at),
+        "fresh_until": millis_since_epoch(response.fresh_until),
+        "vary": vary,
+    })
+}
+
+fn decode(key: &str, contents: Bytes) -> Option<CachedResponse> {
+    let newline = contents.iter().position(|byte| *byte == b'\n')?;
+    let metadata: Value = serde_json::from_slice(&contents[..newline]).ok()?;
+    if metadata["key"].as_str()? != key {
+        return None;
+    }
+
+    let mut headers = HeaderMap::new();
+    for header in metadata["headers"].as_array()? {
+        let name = HeaderName::from_bytes(header[0].as_str()?.as_bytes()).ok()?;
+        let value = HeaderValue::from_str(header[1].as_str()?).ok()?;
+        headers.append(name, value);
+    }
+
+    let mut vary = Vec::new();
+    for entry in metadata["vary"].as_array()? {
+        let name = HeaderName::from_bytes(entry[0].as_str()?.as_bytes()).ok()?;
+        let value = match entry[1].as_str() {
+            Some(value) => Some(HeaderValue::from_str(value).ok()?),
+            None => None,
+        };
+        vary.push((name, value));
+    }
+
+    let time = |field: &str| Some(UNIX_EPOCH + Duration::from_millis(metadata[field].as_u64()?));
+
+    Some(CachedResponse {
+        status: StatusCode::from_u16(u16::try_from(metadata["status"].as_u64()?).ok()?).ok()?,
+        headers,
+        body: contents.slice(newline + 1..),
+        stored_at: time("stored_at")?,
+        fresh_until: time("fresh_until")?,
+        vary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("swan-cache-{}", fastrand::u64(..)))
+    }
+
+    fn response() -> CachedResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert("etag", HeaderValue::from_static("\"v1\""));
+        headers.append("set-cookie", HeaderValue::from_static("a=1"));
+        headers.append("set-cookie", HeaderValue::from_static("b=2"));
+
+        CachedResponse {
+            status: StatusCode::OK,
+            headers,
+            body: Bytes::from_static(b"line one\nline two"),
+            stored_at: UNIX_EPOCH + Duration::from_millis(1_700_000_000_000),
+            fresh_until: UNIX_EPOCH + Duration::from_millis(1_700_000_060_000),
+            vary: vec![
+                (HeaderName::from_static("accept"), Some(HeaderValue::from_static("application/json"))),
+                (HeaderName::from_static("x-tenant"), None),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let dir = temp_dir();
+        let store = DiskCacheStore::new(&dir);
+        let key = "GET https://api.example.com/countries";
+
+        assert!(store.get(key).await.is_none());
+        store.put(key, response()).await;
+        assert_eq!(store.get(key).await.unwrap(), response());
+
+        // 新实例读取同一目录，模拟进程重启
+        assert_eq!(DiskCacheStore::new(&dir).get(key).await.unwrap(), response());
+
+        store.remove(key).await;
+        assert!(store.get(key).await.is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_key_mismatch_is_a_miss() {
+        let dir = temp_dir();
+        let store = DiskCacheStore::new(&dir);
+        store.put("GET https://a", response()).await;
+
+        // 把文件挪到另一个键的位置，模拟哈希冲突
+        std::fs::rename(store.path("GET https://a"), store.path("GET https://b")).unwrap();
+        assert!(store.get("GET https://b").await.is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -0,0 +1,378 @@
+use super::{CacheConfig, CachedResponse, MemoryCacheStore, SwanCacheStore};
+use crate::error::SwanError;
+use reqwest::header::{self, HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// 区分调用身份的请求头，取值不同的调用不共享缓存
+pub const IDENTITY_HEADERS: [&str; 3] = ["authorization", "proxy-authorization", "cookie"];
+
+/// 生成的客户端持有的 HTTP 缓存
+///
+/// 标注了 `cache` 的 GET 方法在发送前调用 [`lookup`](Self::lookup)，
+/// 收到响应后调用 [`update`](Self::update)。
+///
+/// 缓存键包含最终 URL 与 [`IDENTITY_HEADERS`] 取值的摘要，因此不同凭据或 Cookie 的调用
+/// （包括共享同一存储的不同客户端）不会读到彼此的响应；存储中只保存摘要，不保存凭据本身。
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    store: Arc<dyn SwanCacheStore>,
+}
+
+/// 一次缓存查询的结果，发送请求后交回 [`HttpCache::update`]
+#[derive(Debug)]
+pub struct CacheLookup {
+    key: String,
+    config: CacheConfig,
+    /// 请求声明了 `Cache-Control: no-store`，本次调用不读也不写缓存
+    bypass: bool,
+    /// 已过期、等待重新验证的响应
+    stale: Option<CachedResponse>,
+    fresh: Option<reqwest::Response>,
+    request_headers: HeaderMap,
+}
+
+impl HttpCache {
+    /// 使用指定的存储
+    pub fn new(store: Arc<dyn SwanCacheStore>) -> Self {
+        Self { store }
+    }
+
+    /// 查询缓存
+    ///
+    /// `identity` 为实际发送的请求（已加上客户端默认请求头、认证凭据与 Cookie），用于计算缓存键。
+    /// 命中新鲜的响应时可以通过 [`CacheLookup::take_fresh`] 直接取出；
+    /// 命中已过期的响应时为请求加上 `If-None-Match` / `If-Modified-Since`（请求中已有时保留原值）。
+    pub async fn lookup(
+        &self,
+        request: &mut reqwest::Request,
+        identity: &reqwest::Request,
+        config: &CacheConfig,
+    ) -> CacheLookup {
+        let directives = CacheControl::parse(request.headers());
+        let mut lookup = CacheLookup {
+            key: cache_key(request, identity),
+            config: *config,
+            bypass: directives.no_store,
+            stale: None,
+            fresh: None,
+            request_headers: HeaderMap::new(),
+        };
+        if lookup.bypass {
+            return lookup;
+        }
+
+        lookup.request_headers = request.headers().clone();
+        let Some(entry) = self.store.get(&lookup.key).await else { return lookup };
+        if !entry.matches_vary(request.headers()) {
+            return lookup;
+        }
+
+        if entry.is_fresh(SystemTime::now()) && !directives.no_cache {
+            log::debug!("Cache hit: {}", lookup.key);
+            lookup.fresh = Some(entry.to_response(request.url()));
+            return lookup;
+        }
+
+        let headers = request.headers_mut();
+        if let Some(etag) = entry.headers.get(header::ETAG)
+            && !headers.contains_key(header::IF_NONE_MATCH)
+        {
+            headers.insert(header::IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = entry.headers.get(header::LAST_MODIFIED)
+            && !headers.contains_key(header::IF_MODIFIED_SINCE)
+        {
+            headers.insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+        }
+        lookup.stale = Some(entry);
+        lookup
+    }
+
+    /// 根据响应更新缓存，返回交给调用方的响应
+    ///
+    /// 重新验证得到的 304 视为命中，返回缓存的响应；可缓存的 200 响应会先读取响应体再存入。
+    pub async fn update(&self, lookup: CacheLookup, response: reqwest::Response) -> Result<reqwest::Response, SwanError> {
+        if lookup.bypass {
+            return Ok(response);
+        }
+
+        let now = SystemTime::now();
+        match response.status() {
+            StatusCode::NOT_MODIFIED => {
+                // 调用方自己带了条件请求头而缓存中没有对应响应时，原样返回 304
+                let Some(mut entry) = lookup.stale else { return Ok(response) };
+
+                for (name, value) in response.headers() {
+                    if name != header::CONTENT_LENGTH {
+                        entry.headers.insert(name.clone(), value.clone());
+                    }
+                }
+                entry.stored_at = now;
+                entry.fresh_until = now + freshness(&entry.headers, &lookup.config);
+
+                log::debug!("Cache revalidated: {}", lookup.key);
+                let cached = entry.to_response(response.url());
+                self.store.put(&lookup.key, entry).await;
+                Ok(cached)
+            }
+            StatusCode::OK => {
+                let headers = response.headers();
+                let vary = vary_headers(headers, &lookup.request_headers);
+                let lifetime = freshness(headers, &lookup.config);
+                let has_validator = headers.contains_key(header::ETAG) || headers.contains_key(header::LAST_MODIFIED);
+
+                let (Some(vary), false) = (vary, CacheControl::parse(headers).no_store) else {
+                    self.store.remove(&lookup.key).await;
+                    return Ok(response);
+                };
+                if lifetime.is_zero() && !has_validator {
+                    // 既不新鲜也无法重新验证，存下来也用不上
+                    self.store.remove(&lookup.key).await;
+                    return Ok(response);
+                }
+
+                let url = response.url().clone();
+                let status = response.status();
+                let headers = response.headers().clone();
+                let body = response.bytes().await?;
+
+                let entry = CachedResponse {
+                    status,
+                    headers,
+                    body,
+                    stored_at: now,
+                    fresh_until: now + lifetime,
+                    vary,
+                };
+                let cached = entry.to_response(&url);
+                self.store.put(&lookup.key, entry).await;
+                Ok(cached)
+            }
+            _ => Ok(response),
+        }
+    }
+}
+
+impl Default for HttpCache {
+    fn default() -> Self {
+        Self::new(Arc::new(MemoryCacheStore::default()))
+    }
+}
+
+impl CacheLookup {
+    /// 取出命中的新鲜响应，取出后不需要再发送请求
+    pub fn take_fresh(&mut self) -> Option<reqwest::Response> {
+        self.fresh.take()
+    }
+}
+
+/// `GET {url}`；携带身份信息时追加其摘要
+fn cache_key(request: &reqwest::Request, identity: &reqwest::Request) -> String {
+    let mut key = format!("GET {}", request.url());
+
+    let mut hasher = Sha256::new();
+    let mut identified = false;
+    if identity.url() != request.url() {
+        // 如查询参数中的 API Key
+        hasher.update(identity.url().as_str());
+        identified = true;
+    }
+    for name in IDENTITY_HEADERS {
+        for value in identity.headers().get_all(name) {
+            hasher.update(name);
+            hasher.update(b"\n");
+            hasher.update(value.as_bytes());
+            hasher.update(b"\n");
+            identified = true;
+        }
+    }
+    if identified {
+        key.push_str(" identity=");
+        key.push_str(&hex::encode(hasher.finalize()));
+    }
+    key
+}
+
+/// 响应的新鲜期：`max-age` 减去 `Age`，其次是配置的 `max_age`
+fn freshness(headers: &HeaderMap, config: &CacheConfig) -> Duration {
+    let directives = CacheControl::parse(headers);
+    if directives.no_cache {
+        return Duration::ZERO;
+    }
+
+    match directives.max_age {
+        Some(max_age) => {
+            let age = headers.get(header::AGE)
+                .and_then(|value| value.to_str().ok()?.trim().parse().ok())
+                .unwrap_or(0);
+            Duration::from_secs(max_age.saturating_sub(age))
+        }
+        None => config.max_age.unwrap_or_default(),
+    }
+}
+
+/// 记录 `Vary` 列出的请求头取值；`Vary: *` 表示响应不可缓存，返回 `None`
+fn vary_headers(headers: &HeaderMap, request_headers: &HeaderMap) -> Option<Vec<(HeaderName, Option<HeaderValue>)>> {
+    let mut vary = Vec::new();
+    for value in headers.get_all(header::VARY) {
+        for name in value.to_str().ok()?.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            if name == "*" {
+                return None;
+            }
+            let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+            let value = request_headers.get(&name).cloned();
+            vary.push((name, value));
+        }
+    }
+    Some(vary)
+}
+
+/// 缓存用到的 `Cache-Control` 指令
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut result = Self::default();
+        let directives = headers.get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+
+            if name.eq_ignore_ascii_case("no-store") {
+                result.no_store = true;
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                result.no_cache = true;
+            } else if name.eq_ignore_ascii_case("max-age") {
+                // 无法解析的 max-age 按已过期处理
+                result.max_age = Some(value.and_then(|value| value.parse().ok()).unwrap_or(0));
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs.iter()
+            .map(|(name, value)| (HeaderName::from_static(name), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    fn request() -> reqwest::Request {
+        reqwest::Request::new(Method::GET, reqwest::Url::parse("https://api.example.com/countries").unwrap())
+    }
+
+    fn response(status: u16, pairs: &[(&'static str, &'static str)], body: &'static str) -> reqwest::Response {
+        let mut response = http::Response::builder().status(status).body(body).unwrap();
+        *response.headers_mut() = headers(pairs);
+        response.into()
+    }
+
+    #[test]
+    fn test_parse_cache_control() {
+        let parsed = CacheControl::parse(&headers(&[("cache-control", "public, Max-Age=\"60\", no-cache")]));
+        assert_eq!(parsed, CacheControl { no_store: false, no_cache: true, max_age: Some(60) });
+        assert!(CacheControl::parse(&headers(&[("cache-control", "no-store")])).no_store);
+        assert_eq!(CacheControl::parse(&headers(&[("cache-control", "max-age=soon")])).max_age, Some(0));
+    }
+
+    #[test]
+    fn test_freshness() {
+        let config = CacheConfig::new().max_age(Duration::from_secs(30));
+        assert_eq!(freshness(&headers(&[("cache-control", "max-age=60"), ("age", "15")]), &config), Duration::from_secs(45));
+        assert_eq!(freshness(&headers(&[("cache-control", "max-age=10"), ("age", "15")]), &config), Duration::ZERO);
+        assert_eq!(freshness(&headers(&[("cache-control", "max-age=60, no-cache")]), &config), Duration::ZERO);
+        assert_eq!(freshness(&HeaderMap::new(), &config), Duration::from_secs(30));
+        assert_eq!(freshness(&HeaderMap::new(), &CacheConfig::new()), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_hit_and_revalidation() {
+        let cache = HttpCache::default();
+        let config = CacheConfig::new();
+
+        let mut request = request();
+        let mut lookup = cache.lookup(&mut request, &self::request(), &config).await;
+        assert!(lookup.take_fresh().is_none());
+        let stored = cache.update(lookup, response(200, &[("etag", "\"v1\"")], "[]")).await.unwrap();
+        assert_eq!(stored.text().await.unwrap(), "[]");
+
+        // 没有新鲜期，下一次带上 If-None-Match 重新验证
+        let mut request = self::request();
+        let mut lookup = cache.lookup(&mut request, &self::request(), &config).await;
+        assert!(lookup.take_fresh().is_none());
+        assert_eq!(request.headers()[header::IF_NONE_MATCH], "\"v1\"");
+
+        let revalidated = cache.update(lookup, response(304, &[("cache-control", "max-age=60")], "")).await.unwrap();
+        assert_eq!(revalidated.status(), StatusCode::OK);
+        assert_eq!(revalidated.text().await.unwrap(), "[]");
+
+        // 304 带来了新鲜期，之后直接命中
+        let mut request = self::request();
+        let mut lookup = cache.lookup(&mut request, &self::request(), &config).await;
+        assert_eq!(lookup.take_fresh().unwrap().text().await.unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_cache_key_separates_identities() {
+        let anonymous = cache_key(&request(), &request());
+        assert_eq!(anonymous, "GET https://api.example.com/countries");
+
+        let with_token = |token: &'static str| {
+            let mut identity = request();
+            identity.headers_mut().insert(header::AUTHORIZATION, HeaderValue::from_static(token));
+            cache_key(&request(), &identity)
+        };
+        let alice = with_token("Bearer alice");
+        assert!(alice.starts_with("GET https://api.example.com/countries identity="));
+        assert!(!alice.contains("alice"));
+        assert_ne!(alice, with_token("Bearer bob"));
+        assert_eq!(alice, with_token("Bearer alice"));
+
+        let mut api_key = request();
+        api_key.url_mut().set_query(Some("api_key=secret"));
+        assert_ne!(cache_key(&request(), &api_key), anonymous);
+    }
+
+    #[tokio::test]
+    async fn test_no_store_and_vary() {
+        let cache = HttpCache::default();
+        let config = CacheConfig::new().max_age(Duration::from_secs(60));
+
+        let mut request = request();
+        let lookup = cache.lookup(&mut request, &self::request(), &config).await;
+        cache.update(lookup, response(200, &[("cache-control", "no-store")], "a")).await.unwrap();
+        assert!(cache.lookup(&mut self::request(), &self::request(), &config).await.take_fresh().is_none());
+
+        let mut request = self::request();
+        request.headers_mut().insert("accept-language", HeaderValue::from_static("en"));
+        let lookup = cache.lookup(&mut request, &self::request(), &config).await;
+        cache.update(lookup, response(200, &[("vary", "Accept-Language")], "hello")).await.unwrap();
+
+        let mut other = self::request();
+        other.headers_mut().insert("accept-language", HeaderValue::from_static("fr"));
+        assert!(cache.lookup(&mut other, &self::request(), &config).await.take_fresh().is_none());
+
+        let mut same = self::request();
+        same.headers_mut().insert("accept-language", HeaderValue::from_static("en"));
+        assert!(cache.lookup(&mut same, &self::request(), &config).await.take_fresh().is_some());
+    }
+}
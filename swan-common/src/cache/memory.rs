@@ -0,0 +1,143 @@
+use super::{CachedResponse, SwanCacheStore};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// 默认最多缓存的响应条数
+const DEFAULT_CAPACITY: usize = 256;
+
+/// 进程内的 LRU 缓存存储
+///
+/// 超出容量时淘汰最久未被读取或写入的响应。
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    /// 单调递增的访问计数，作为最近使用顺序
+    tick: u64,
+    entries: HashMap<String, (u64, CachedResponse)>,
+    /// 访问计数 -> 键，最小的为最久未使用
+    recency: BTreeMap<u64, String>,
+}
+
+impl MemoryCacheStore {
+    /// 创建最多保存 `capacity` 条响应的存储
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    /// 当前缓存的响应条数
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// 是否没有缓存任何响应
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for MemoryCacheStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) -> Option<&CachedResponse> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (last_used, _) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        *last_used = tick;
+        self.recency.insert(tick, key.to_string());
+        self.entries.get(key).map(|(_, response)| response)
+    }
+}
+
+#[async_trait]
+impl SwanCacheStore for MemoryCacheStore {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.lock().touch(key).cloned()
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) {
+        let mut state = self.lock();
+        state.tick += 1;
+        let tick = state.tick;
+
+        if let Some((last_used, _)) = state.entries.insert(key.to_string(), (tick, response)) {
+            state.recency.remove(&last_used);
+        }
+        state.recency.insert(tick, key.to_string());
+
+        while state.entries.len() > self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else { break };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    async fn remove(&self, key: &str) {
+        let mut state = self.lock();
+        if let Some((last_used, _)) = state.entries.remove(key) {
+            state.recency.remove(&last_used);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: reqwest::StatusCode::OK,
+            headers: Default::default(),
+            body: body.into(),
+            stored_at: SystemTime::now(),
+            fresh_until: SystemTime::now(),
+            vary: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used() {
+        let store = MemoryCacheStore::new(2);
+        store.put("a", response("a")).await;
+        store.put("b", response("b")).await;
+
+        // 读取 a 后 b 成为最久未使用
+        assert!(store.get("a").await.is_some());
+        store.put("c", response("c")).await;
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("b").await.is_none());
+        assert_eq!(store.get("a").await.unwrap().body, "a");
+        assert_eq!(store.get("c").await.unwrap().body, "c");
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_remove() {
+        let store = MemoryCacheStore::new(2);
+        store.put("a", response("old")).await;
+        store.put("a", response("new")).await;
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get("a").await.unwrap().body, "new");
+
+        store.remove("a").await;
+        assert!(store.is_empty());
+        assert!(store.get("a").await.is_none());
+    }
+}
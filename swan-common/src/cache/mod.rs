@@ -0,0 +1,11 @@
+pub mod config;
+pub mod store;
+pub mod memory;
+pub mod disk;
+pub mod http;
+
+pub use config::CacheConfig;
+pub use store::{CachedResponse, SwanCacheStore};
+pub use memory::MemoryCacheStore;
+pub use disk::DiskCacheStore;
+pub use http::{CacheLookup, HttpCache};
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{ResponseBuilderExt, StatusCode, Url};
+use std::fmt;
+use std::time::SystemTime;
+
+/// 缓存中的一条响应
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    /// 响应状态码
+    pub status: StatusCode,
+    /// 响应头（重新验证时按 304 响应更新）
+    pub headers: HeaderMap,
+    /// 响应体
+    pub body: Bytes,
+    /// 存入或最近一次重新验证的时间
+    pub stored_at: SystemTime,
+    /// 新鲜期截止时间，之后需要重新验证
+    pub fresh_until: SystemTime,
+    /// `Vary` 列出的请求头及存入时的取值，取值不同的请求不会命中
+    pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+}
+
+impl CachedResponse {
+    /// 在 `now` 时是否仍然新鲜
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        now < self.fresh_until
+    }
+
+    /// 请求头是否与存入时的 `Vary` 取值一致
+    pub fn matches_vary(&self, request_headers: &HeaderMap) -> bool {
+        self.vary.iter().all(|(name, value)| request_headers.get(name) == value.as_ref())
+    }
+
+    /// 构建返回给调用方的响应
+    pub fn to_response(&self, url: &Url) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(self.status).url(url.clone());
+        if let Some(headers) = builder.headers_mut() {
+            *headers = self.headers.clone();
+        }
+        let response = builder.body(self.body.clone()).expect("cached response parts are valid");
+        reqwest::Response::from(response)
+    }
+}
+
+/// HTTP 缓存存储
+///
+/// 通过客户端构建器的 `cache_store()` 替换，默认使用 [`MemoryCacheStore`](super::MemoryCacheStore)。
+/// 缓存是尽力而为的：实现内部的读写失败应当记录日志后当作未命中处理，而不是让请求失败。
+///
+/// 键由请求方法与最终 URL 组成，如 `GET https://api.example.com/countries`。
+#[async_trait]
+pub trait SwanCacheStore: Send + Sync {
+    /// 读取缓存的响应
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    /// 存入或替换缓存的响应
+    async fn put(&self, key: &str, response: CachedResponse);
+
+    /// 删除缓存的响应
+    async fn remove(&self, key: &str);
+}
+
+impl fmt::Debug for dyn SwanCacheStore + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SwanCacheStore")
+    }
+}
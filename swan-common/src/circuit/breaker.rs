@@ -0,0 +1,293 @@
+use super::CircuitBreakerConfig;
+use crate::error::SwanError;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+/// 熔断器状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// 正常放行请求，统计失败次数
+    Closed,
+    /// 熔断中，请求直接失败
+    Open,
+    /// 冷却结束，放行一个探测请求决定恢复还是重新打开
+    HalfOpen,
+}
+
+/// 单个主机的熔断状态
+#[derive(Debug)]
+enum HostState {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { probing: bool },
+}
+
+#[derive(Debug)]
+struct HostCircuit {
+    state: HostState,
+    /// 关闭状态下窗口内的失败时间点
+    failures: VecDeque<Instant>,
+}
+
+impl HostCircuit {
+    fn new() -> Self {
+        Self {
+            state: HostState::Closed,
+            failures: VecDeque::new(),
+        }
+    }
+}
+
+/// 按主机隔离的熔断器
+///
+/// 主机以 `host` 或 `host:port`（URL 中显式写出端口时）区分。
+/// 传输层错误与 5xx 响应计为失败；打开期间的请求返回 [`SwanError::CircuitOpen`]，
+/// 不会发送到网络。
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    hosts: Mutex<HashMap<String, HostCircuit>>,
+}
+
+impl CircuitBreaker {
+    /// 以指定配置创建熔断器
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 熔断器配置
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// 查询主机当前的熔断状态，未出现过的主机为 [`CircuitState::Closed`]
+    pub fn state(&self, host: &str) -> CircuitState {
+        match self.lock().get(host).map(|circuit| &circuit.state) {
+            None | Some(HostState::Closed) => CircuitState::Closed,
+            Some(HostState::Open { until }) if Instant::now() < *until => CircuitState::Open,
+            Some(HostState::Open { .. }) | Some(HostState::HalfOpen { .. }) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// 请求发送前获取许可，熔断打开时返回 [`SwanError::CircuitOpen`]
+    pub fn acquire(&self, url: &reqwest::Url) -> Result<CircuitPermit<'_>, SwanError> {
+        self.acquire_at(Self::host_key(url), Instant::now())
+    }
+
+    /// 熔断器使用的主机标识
+    pub fn host_key(url: &reqwest::Url) -> String {
+        let host = url.host_str().unwrap_or_default();
+        match url.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        }
+    }
+
+    fn acquire_at(&self, host: String, now: Instant) -> Result<CircuitPermit<'_>, SwanError> {
+        let mut hosts = self.lock();
+        let circuit = hosts.entry(host.clone()).or_insert_with(HostCircuit::new);
+
+        let probe = match circuit.state {
+            HostState::Closed => false,
+            HostState::Open { until } if now < until => {
+                return Err(SwanError::CircuitOpen { host });
+            }
+            HostState::Open { .. } | HostState::HalfOpen { probing: false } => {
+                circuit.state = HostState::HalfOpen { probing: true };
+                true
+            }
+            HostState::HalfOpen { probing: true } => {
+                return Err(SwanError::CircuitOpen { host });
+            }
+        };
+        drop(hosts);
+
+        Ok(CircuitPermit {
+            breaker: self,
+            host,
+            probe,
+            completed: false,
+        })
+    }
+
+    fn complete(&self, host: &str, probe: bool, success: bool, now: Instant) {
+        let mut hosts = self.lock();
+        let Some(circuit) = hosts.get_mut(host) else { return };
+
+        if probe {
+            circuit.failures.clear();
+            circuit.state = if success {
+                HostState::Closed
+            } else {
+                HostState::Open { until: now + self.config.cooldown }
+            };
+            return;
+        }
+
+        // 打开期间完成的普通请求不再影响状态
+        if success || !matches!(circuit.state, HostState::Closed) {
+            return;
+        }
+
+        circuit.failures.push_back(now);
+        while circuit.failures.front().is_some_and(|at| now.duration_since(*at) > self.config.window) {
+            circuit.failures.pop_front();
+        }
+
+        if circuit.failures.len() >= self.config.failures as usize {
+            log::warn!("Circuit breaker opened for {} after {} failures", host, circuit.failures.len());
+            circuit.failures.clear();
+            circuit.state = HostState::Open { until: now + self.config.cooldown };
+        }
+    }
+
+    /// 被取消的探测请求释放探测名额，让下一个请求继续探测
+    fn release_probe(&self, host: &str) {
+        if let Some(circuit) = self.lock().get_mut(host)
+            && matches!(circuit.state, HostState::HalfOpen { probing: true })
+        {
+            circuit.state = HostState::HalfOpen { probing: false };
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, HostCircuit>> {
+        self.hosts.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 一次请求的熔断许可，请求结束后通过 [`CircuitPermit::record`] 报告结果
+#[derive(Debug)]
+#[must_use = "the request outcome must be recorded"]
+pub struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    host: String,
+    probe: bool,
+    completed: bool,
+}
+
+impl CircuitPermit<'_> {
+    /// 记录请求结果：传输层错误与 5xx 响应计为失败
+    pub fn record(self, result: &Result<reqwest::Response, SwanError>) {
+        let success = match result {
+            Ok(response) => !response.status().is_server_error(),
+            Err(_) => false,
+        };
+        self.complete(success, Instant::now());
+    }
+
+    fn complete(mut self, success: bool, now: Instant) {
+        self.completed = true;
+        self.breaker.complete(&self.host, self.probe, success, now);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if self.probe && !self.completed {
+            self.breaker.release_probe(&self.host);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig::new(3, Duration::from_secs(10), Duration::from_secs(5)))
+    }
+
+    fn fail(breaker: &CircuitBreaker, now: Instant) {
+        breaker.acquire_at("api".into(), now).unwrap().complete(false, now);
+    }
+
+    #[test]
+    fn test_opens_after_failures_within_window() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        fail(&breaker, start);
+        fail(&breaker, start + Duration::from_secs(1));
+        assert_eq!(breaker.state("api"), CircuitState::Closed);
+
+        fail(&breaker, start + Duration::from_secs(2));
+        assert_eq!(breaker.state("api"), CircuitState::Open);
+
+        let error = breaker.acquire_at("api".into(), start + Duration::from_secs(3)).unwrap_err();
+        assert!(error.is_circuit_open());
+        assert_eq!(breaker.state("other"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_forgotten() {
+        let breaker = breaker();
+        let start = Instant::now();
+
+        fail(&breaker, start);
+        fail(&breaker, start + Duration::from_secs(1));
+        fail(&breaker, start + Duration::from_secs(12));
+
+        assert_eq!(breaker.state("api"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_probe_closes_on_success() {
+        let breaker = breaker();
+        let start = Instant::now();
+        for offset in 0..3 {
+            fail(&breaker, start + Duration::from_millis(offset));
+        }
+
+        let after_cooldown = start + Duration::from_secs(6);
+        let probe = breaker.acquire_at("api".into(), after_cooldown).unwrap();
+        // 探测期间其他请求仍然快速失败
+        assert!(breaker.acquire_at("api".into(), after_cooldown).is_err());
+
+        probe.complete(true, after_cooldown);
+        assert_eq!(breaker.state("api"), CircuitState::Closed);
+        assert!(breaker.acquire_at("api".into(), after_cooldown).is_ok());
+    }
+
+    #[test]
+    fn test_half_open_probe_reopens_on_failure() {
+        let breaker = breaker();
+        let start = Instant::now();
+        for offset in 0..3 {
+            fail(&breaker, start + Duration::from_millis(offset));
+        }
+
+        let after_cooldown = start + Duration::from_secs(6);
+        fail(&breaker, after_cooldown);
+
+        assert!(breaker.acquire_at("api".into(), after_cooldown + Duration::from_secs(1)).is_err());
+        assert!(breaker.acquire_at("api".into(), after_cooldown + Duration::from_secs(6)).is_ok());
+    }
+
+    #[test]
+    fn test_dropped_probe_releases_half_open() {
+        let breaker = breaker();
+        let start = Instant::now();
+        for offset in 0..3 {
+            fail(&breaker, start + Duration::from_millis(offset));
+        }
+
+        let after_cooldown = start + Duration::from_secs(6);
+        drop(breaker.acquire_at("api".into(), after_cooldown).unwrap());
+
+        assert!(breaker.acquire_at("api".into(), after_cooldown).is_ok());
+    }
+
+    #[test]
+    fn test_host_key() {
+        let url = reqwest::Url::parse("https://api.example.com/users").unwrap();
+        assert_eq!(CircuitBreaker::host_key(&url), "api.example.com");
+
+        let url = reqwest::Url::parse("http://127.0.0.1:8080/users").unwrap();
+        assert_eq!(CircuitBreaker::host_key(&url), "127.0.0.1:8080");
+    }
+}
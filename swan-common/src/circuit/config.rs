@@ -0,0 +1,107 @@
+use crate::types::RetryConfig;
+use std::time::Duration;
+
+/// 熔断器配置
+///
+/// `window` 时间窗口内失败达到 `failures` 次时打开熔断，
+/// 经过 `cooldown` 后进入半开状态，放行一个探测请求。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// 打开熔断所需的失败次数
+    pub failures: u32,
+    /// 统计失败次数的滑动时间窗口
+    pub window: Duration,
+    /// 打开后到允许探测请求的冷却时间
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failures: 5,
+            window: Duration::from_secs(30),
+            cooldown: Duration::from_secs(10),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// 创建熔断器配置
+    pub fn new(failures: u32, window: Duration, cooldown: Duration) -> Self {
+        Self { failures, window, cooldown }
+    }
+
+    /// 解析 `"failures=5, window=30s, cooldown=10s"` 形式的配置，未给出的参数使用默认值
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        for param in config.split(',') {
+            let param = param.trim();
+            if param.is_empty() { continue; }
+
+            let (key, value) = param.split_once('=')
+                .ok_or_else(|| format!("Expected key=value in circuit_breaker config: {}", param))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "failures" => {
+                    result.failures = value.parse()
+                        .ok()
+                        .filter(|failures| *failures > 0)
+                        .ok_or_else(|| format!("Invalid failures: {}", value))?;
+                }
+                "window" => {
+                    result.window = Self::parse_positive_duration(key, value)?;
+                }
+                "cooldown" => {
+                    result.cooldown = Self::parse_positive_duration(key, value)?;
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown circuit_breaker parameter: {} (expected failures, window or cooldown)",
+                        key
+                    ));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_positive_duration(key: &str, value: &str) -> Result<Duration, String> {
+        match RetryConfig::parse_duration(value)? {
+            0 => Err(format!("{} must be greater than zero", key)),
+            millis => Ok(Duration::from_millis(millis)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = CircuitBreakerConfig::parse("failures=3, window=10s, cooldown=500ms").unwrap();
+
+        assert_eq!(config, CircuitBreakerConfig::new(3, Duration::from_secs(10), Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_parse_uses_defaults() {
+        let config = CircuitBreakerConfig::parse("failures=2").unwrap();
+
+        assert_eq!(config.failures, 2);
+        assert_eq!(config.window, Duration::from_secs(30));
+        assert_eq!(config.cooldown, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        assert!(CircuitBreakerConfig::parse("failures=0").is_err());
+        assert!(CircuitBreakerConfig::parse("window=0s").is_err());
+        assert!(CircuitBreakerConfig::parse("cooldown=soon").is_err());
+        assert!(CircuitBreakerConfig::parse("threshold=5").is_err());
+        assert!(CircuitBreakerConfig::parse("5").is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod breaker;
+
+pub use config::CircuitBreakerConfig;
+pub use breaker::{CircuitBreaker, CircuitPermit, CircuitState};
@@ -0,0 +1,70 @@
+use super::ClientOptions;
+use crate::error::SwanError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 客户端持有的方法级 `reqwest::Client`，供带有方法级 `proxy` 的方法使用
+///
+/// 这些客户端以实例的 [`ClientOptions`] 构建，与实例客户端共享超时、默认请求头、
+/// Cookie、TLS 与自动解压配置，只在代理设置上不同。每个方法首次调用时创建并缓存。
+#[derive(Debug)]
+pub struct MethodClients {
+    options: ClientOptions,
+    clients: Mutex<HashMap<&'static str, reqwest::Client>>,
+}
+
+impl MethodClients {
+    /// 以客户端实例的配置创建
+    pub fn new(options: ClientOptions) -> Self {
+        Self {
+            options,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取方法的客户端，首次调用时由 `builder` 提供代理设置，再应用实例配置
+    pub fn get_or_build<F>(&self, method: &'static str, builder: F) -> Result<reqwest::Client, SwanError>
+    where
+        F: FnOnce() -> Result<reqwest::ClientBuilder, SwanError>,
+    {
+        let mut clients = self.clients.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(client) = clients.get(method) {
+            return Ok(client.clone());
+        }
+
+        let client = self.options.apply(builder()?)?.build()?;
+        clients.insert(method, client.clone());
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_built_once_per_method() {
+        let clients = MethodClients::new(ClientOptions::new("https://api.example.com"));
+        let mut builds = 0;
+
+        for _ in 0..2 {
+            clients.get_or_build("search", || {
+                builds += 1;
+                Ok(reqwest::Client::builder().no_proxy())
+            }).unwrap();
+        }
+
+        assert_eq!(builds, 1);
+    }
+
+    #[test]
+    fn test_instance_options_applied() {
+        let mut options = ClientOptions::new("https://api.example.com");
+        options.insert_default_header("Bad Header", "value");
+        let clients = MethodClients::new(options);
+
+        let error = clients.get_or_build("search", || Ok(reqwest::Client::builder().no_proxy())).unwrap_err();
+
+        assert!(error.to_string().contains("Bad Header"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod options;
+pub mod method_clients;
+
+pub use options::ClientOptions;
+pub use method_clients::MethodClients;
@@ -0,0 +1,201 @@
+use crate::auth::SwanAuth;
+use crate::signing::SwanSigner;
+use crate::cache::SwanCacheStore;
+use crate::circuit::CircuitBreakerConfig;
+use crate::compression::ContentEncoding;
+use crate::cookies::CookieJar;
+use crate::error::SwanError;
+use crate::metrics::SwanMetrics;
+use crate::rate_limit::RateLimitConfig;
+use crate::testing::MockTransport;
+use crate::tls::TlsConfig;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 客户端运行时配置
+///
+/// 由 `#[http_client]` 生成的 `XxxBuilder` 持有，宏参数提供默认值，
+/// 运行时可通过构建器覆盖，最终应用到 `reqwest::ClientBuilder` 上。
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// 基础 URL
+    pub base_url: String,
+    /// 整个请求的超时时间
+    pub timeout: Option<Duration>,
+    /// 建立连接的超时时间
+    pub connect_timeout: Option<Duration>,
+    /// 每个请求都会携带的默认请求头
+    pub default_headers: HeaderMap,
+    /// User-Agent 请求头
+    pub user_agent: Option<String>,
+    /// 按主机熔断的配置
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// 客户端级令牌桶限流配置
+    pub rate_limit: Option<RateLimitConfig>,
+    /// 请求指标钩子
+    pub metrics: Option<Arc<dyn SwanMetrics>>,
+    /// `cache` 方法使用的缓存存储，未设置时使用内存 LRU
+    pub cache_store: Option<Arc<dyn SwanCacheStore>>,
+    /// 客户端级认证
+    pub auth: Option<Arc<dyn SwanAuth>>,
+    /// 请求签名
+    pub signer: Option<Arc<dyn SwanSigner>>,
+    /// Cookie 存储，未设置时不保存也不发送 Cookie
+    pub cookies: Option<CookieJar>,
+    /// TLS 配置，未设置时使用 reqwest 默认的系统 TLS
+    pub tls: Option<TlsConfig>,
+    /// 自动解压的响应编码，同时决定 `Accept-Encoding`；为空时不解压
+    pub decompress: Vec<ContentEncoding>,
+    /// 替代网络的模拟传输层（测试用）
+    pub transport: Option<MockTransport>,
+    /// 第一个无效的默认请求头，延迟到构建时报告
+    invalid_header: Option<String>,
+}
+
+impl ClientOptions {
+    /// 以指定基础 URL 创建配置
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 添加默认请求头
+    ///
+    /// 名称或值不合法时不会立即报错，而是在 [`ClientOptions::apply`] 时返回错误，
+    /// 以便构建器保持链式调用。
+    pub fn insert_default_header(&mut self, name: &str, value: &str) {
+        match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            (Ok(name), Ok(value)) => {
+                self.default_headers.insert(name, value);
+            }
+            _ => {
+                self.invalid_header
+                    .get_or_insert_with(|| format!("Invalid default header '{}: {}'", name, value));
+            }
+        }
+    }
+
+    /// 客户端为每个请求添加的请求头：默认请求头与 User-Agent
+    ///
+    /// `reqwest` 在发送时才合并这些请求头，签名前需要先按同样的规则补齐。
+    pub fn request_headers(&self) -> HeaderMap {
+        let mut headers = self.default_headers.clone();
+        if let Some(user_agent) = self.user_agent.as_ref().and_then(|ua| HeaderValue::from_str(ua).ok()) {
+            headers.insert(USER_AGENT, user_agent);
+        }
+        headers
+    }
+
+    /// 模拟传输层，附带默认请求头与 User-Agent
+    pub fn mock_transport(&self) -> Option<MockTransport> {
+        let transport = self.transport.as_ref()?;
+        Some(transport.with_default_headers(self.request_headers()))
+    }
+
+    /// 将配置应用到 `reqwest::ClientBuilder`
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, SwanError> {
+        if let Some(error) = &self.invalid_header {
+            return Err(SwanError::request(error.clone()));
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.validate()?;
+        }
+
+        let mut builder = builder.default_headers(self.default_headers.clone());
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent = HeaderValue::from_str(user_agent)
+                .map_err(|_| SwanError::request(format!("Invalid user agent '{}'", user_agent)))?;
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(cookies) = &self.cookies {
+            builder = builder.cookie_provider(Arc::new(cookies.clone()));
+        }
+        builder = builder
+            .gzip(self.decompress.contains(&ContentEncoding::Gzip))
+            .brotli(self.decompress.contains(&ContentEncoding::Brotli))
+            .zstd(self.decompress.contains(&ContentEncoding::Zstd));
+        if let Some(tls) = &self.tls {
+            builder = builder.use_preconfigured_tls(tls.client_config()?);
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_default_header() {
+        let mut options = ClientOptions::new("https://api.example.com");
+        options.insert_default_header("X-Tenant", "acme");
+
+        assert_eq!(options.default_headers.get("x-tenant").unwrap(), "acme");
+        assert!(options.apply(reqwest::Client::builder()).is_ok());
+    }
+
+    #[test]
+    fn test_request_headers_include_user_agent() {
+        let mut options = ClientOptions::new("https://api.example.com");
+        options.insert_default_header("X-Tenant", "acme");
+        options.user_agent = Some("swan-test/1.0".into());
+
+        let headers = options.request_headers();
+
+        assert_eq!(headers.get("x-tenant").unwrap(), "acme");
+        assert_eq!(headers.get(USER_AGENT).unwrap(), "swan-test/1.0");
+    }
+
+    #[test]
+    fn test_invalid_default_header_reported_on_apply() {
+        let mut options = ClientOptions::new("https://api.example.com");
+        options.insert_default_header("Bad Header", "value");
+        options.insert_default_header("X-Ok", "value");
+
+        let error = options.apply(reqwest::Client::builder()).unwrap_err();
+
+        assert!(matches!(error, SwanError::Request(_)));
+        assert!(error.to_string().contains("Bad Header"));
+    }
+
+    #[test]
+    fn test_invalid_user_agent_reported_on_apply() {
+        let options = ClientOptions {
+            user_agent: Some("bad\nagent".into()),
+            ..ClientOptions::new("https://api.example.com")
+        };
+
+        assert!(options.apply(reqwest::Client::builder()).is_err());
+    }
+
+    #[test]
+    fn test_invalid_rate_limit_reported_on_apply() {
+        let options = ClientOptions {
+            rate_limit: Some(RateLimitConfig::new(0, Duration::from_secs(1))),
+            ..ClientOptions::new("https://api.example.com")
+        };
+
+        assert_eq!(options.apply(reqwest::Client::builder()).unwrap_err().kind(), "request");
+    }
+
+    #[test]
+    fn test_invalid_tls_reported_on_apply() {
+        let options = ClientOptions {
+            tls: Some(TlsConfig::new().ca("missing-ca.pem")),
+            ..ClientOptions::new("https://api.example.com")
+        };
+
+        assert_eq!(options.apply(reqwest::Client::builder()).unwrap_err().kind(), "io");
+    }
+}
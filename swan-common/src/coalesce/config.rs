@@ -0,0 +1,46 @@
+/// 默认参与合并键的请求头，保证不同身份的调用不会共享响应
+pub const DEFAULT_KEY_HEADERS: [&str; 3] = ["authorization", "cookie", "accept"];
+
+/// 方法级请求合并配置
+///
+/// 方法名、请求方法、最终 URL 以及 `key_headers` 列出的请求头取值都相同的并发调用会被合并。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoalesceConfig {
+    /// 参与合并键的请求头（小写）
+    pub key_headers: Vec<String>,
+}
+
+impl CoalesceConfig {
+    /// 使用默认请求头（`Authorization`、`Cookie`、`Accept`）的配置
+    pub fn new() -> Self {
+        Self {
+            key_headers: DEFAULT_KEY_HEADERS.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// 追加参与合并键的请求头
+    pub fn key_header(mut self, name: impl AsRef<str>) -> Self {
+        let name = name.as_ref().to_ascii_lowercase();
+        if !self.key_headers.contains(&name) {
+            self.key_headers.push(name);
+        }
+        self
+    }
+}
+
+impl Default for CoalesceConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_header() {
+        let config = CoalesceConfig::new().key_header("X-Tenant").key_header("Accept");
+        assert_eq!(config.key_headers, ["authorization", "cookie", "accept", "x-tenant"]);
+    }
+}
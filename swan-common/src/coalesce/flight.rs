@@ -0,0 +1,211 @@
+use crate::error::SwanError;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::watch;
+
+/// 一次调用的共享结果，发起方完成前为 `None`
+type Outcome<T> = Option<Result<T, Arc<SwanError>>>;
+
+/// 客户端持有的请求合并器
+///
+/// 键相同的并发调用中，第一个调用（发起方）发送请求，其余调用等待并获得解码结果的克隆；
+/// 发起方的错误经 [`SwanError::share`] 复制后交给每个等待方，发起方自身收到原始错误。发起方被取消时，等待方中的一个重新发起请求。
+/// 请求完成后立即移出合并表，之后的调用会重新发送，合并不会缓存结果。
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    in_flight: Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>,
+}
+
+enum Role<T> {
+    Leader(watch::Sender<Outcome<T>>),
+    Waiter(watch::Receiver<Outcome<T>>),
+    /// 键相同但结果类型不同，不参与合并
+    Alone,
+}
+
+/// 发起方完成或被取消时将键移出合并表
+struct Flight<'a> {
+    coalescer: &'a Coalescer,
+    key: &'a str,
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        self.coalescer.lock().remove(self.key);
+    }
+}
+
+impl Coalescer {
+    /// 合并键：方法名、请求方法、最终 URL 与 `key_headers` 列出的请求头取值
+    pub fn key(method: &str, request: &reqwest::Request, key_headers: &[&str]) -> String {
+        let mut key = format!("{} {} {}", method, request.method(), request.url());
+        for name in key_headers {
+            for value in request.headers().get_all(*name) {
+                key.push('\n');
+                key.push_str(name);
+                key.push_str(": ");
+                key.push_str(&String::from_utf8_lossy(value.as_bytes()));
+            }
+        }
+        key
+    }
+
+    /// 执行 `call`，或等待键相同的进行中调用并共享其结果
+    ///
+    /// 等待方收到的错误经过 [`SwanError::share`] 复制，发起方收到 `call` 返回的原始错误。
+    pub async fn run<T, F>(&self, key: String, call: F) -> Result<T, SwanError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<T, SwanError>>,
+    {
+        let sender = loop {
+            match self.join::<T>(&key) {
+                Role::Leader(sender) => break sender,
+                Role::Alone => return call.await,
+                Role::Waiter(mut receiver) => {
+                    // 发送端被丢弃说明发起方已取消，重新竞争发起方
+                    if let Ok(outcome) = receiver.wait_for(Option::is_some).await
+                        && let Some(result) = outcome.clone()
+                    {
+                        log::debug!("Coalesced request: {}", key);
+                        return result.map_err(|error| error.share());
+                    }
+                }
+            }
+        };
+
+        let flight = Flight { coalescer: self, key: &key };
+        let result = call.await;
+        drop(flight);
+
+        if sender.receiver_count() == 0 {
+            return result;
+        }
+        match result {
+            Ok(value) => {
+                sender.send_replace(Some(Ok(value.clone())));
+                Ok(value)
+            }
+            Err(error) => {
+                sender.send_replace(Some(Err(Arc::new(error.share()))));
+                Err(error)
+            }
+        }
+    }
+
+    fn join<T: Send + Sync + 'static>(&self, key: &str) -> Role<T> {
+        let mut in_flight = self.lock();
+        match in_flight.get(key) {
+            Some(entry) => match entry.downcast_ref::<watch::Receiver<Outcome<T>>>() {
+                Some(receiver) => Role::Waiter(receiver.clone()),
+                None => Role::Alone,
+            },
+            None => {
+                let (sender, receiver) = watch::channel(None);
+                in_flight.insert(key.to_string(), Box::new(receiver));
+                Role::Leader(sender)
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Box<dyn Any + Send + Sync>>> {
+        self.in_flight.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    async fn slow_call(calls: &AtomicUsize, result: Result<u32, SwanError>) -> Result<u32, SwanError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        result
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_result() {
+        let coalescer = Coalescer::default();
+        let calls = AtomicUsize::new(0);
+
+        let results = futures_util::future::join_all(
+            (0..5).map(|_| coalescer.run("key".to_string(), slow_call(&calls, Ok(7)))),
+        ).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|result| result.unwrap() == 7));
+
+        // 完成后不再合并
+        coalescer.run("key".to_string(), slow_call(&calls, Ok(8))).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_error_reaches_every_waiter() {
+        let coalescer = Coalescer::default();
+        let calls = AtomicUsize::new(0);
+
+        let results = futures_util::future::join_all(
+            (0..3).map(|_| coalescer.run("key".to_string(), slow_call(&calls, Err(SwanError::decode("bad json"))))),
+        ).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|result| result.as_ref().unwrap_err().kind() == "decode"));
+    }
+
+    #[tokio::test]
+    async fn test_leader_keeps_original_error() {
+        let coalescer = Coalescer::default();
+        let calls = AtomicUsize::new(0);
+
+        let (leader, waiter) = tokio::join!(
+            coalescer.run("key".to_string(), slow_call(&calls, Err(SwanError::decode("bad json")))),
+            coalescer.run("key".to_string(), slow_call(&calls, Ok(7))),
+        );
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(matches!(leader.unwrap_err(), SwanError::Decode(_)));
+        let waiter = waiter.unwrap_err();
+        assert!(matches!(waiter, SwanError::Shared(_)));
+        assert_eq!(waiter.to_string(), "Failed to decode response: bad json");
+    }
+
+    #[tokio::test]
+    async fn test_waiter_takes_over_cancelled_leader() {
+        let coalescer = Arc::new(Coalescer::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let leader = tokio::spawn({
+            let (coalescer, calls) = (coalescer.clone(), calls.clone());
+            async move { coalescer.run("key".to_string(), slow_call(&calls, Ok(1))).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let waiter = tokio::spawn({
+            let (coalescer, calls) = (coalescer.clone(), calls.clone());
+            async move { coalescer.run("key".to_string(), slow_call(&calls, Ok(2))).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        leader.abort();
+
+        assert_eq!(waiter.await.unwrap().unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_key_includes_selected_headers() {
+        let mut request = reqwest::Request::new(
+            reqwest::Method::GET,
+            reqwest::Url::parse("https://api.example.com/users?page=2").unwrap(),
+        );
+        request.headers_mut().insert("authorization", "Bearer a".parse().unwrap());
+        request.headers_mut().insert("x-request-id", "1".parse().unwrap());
+
+        let key = Coalescer::key("list_users", &request, &["authorization", "accept"]);
+        assert_eq!(key, "list_users GET https://api.example.com/users?page=2\nauthorization: Bearer a");
+    }
+}
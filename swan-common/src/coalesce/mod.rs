@@ -0,0 +1,5 @@
+pub mod config;
+pub mod flight;
+
+pub use config::{CoalesceConfig, DEFAULT_KEY_HEADERS};
+pub use flight::Coalescer;
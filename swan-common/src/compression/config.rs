@@ -0,0 +1,65 @@
+/// 压缩算法，取值即 `Content-Encoding` / `Accept-Encoding` 中的名称
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    Gzip,
+    Zstd,
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// 解析 `gzip`、`zstd` 或 `br`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "gzip" => Some(ContentEncoding::Gzip),
+            "zstd" => Some(ContentEncoding::Zstd),
+            "br" => Some(ContentEncoding::Brotli),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Zstd => "zstd",
+            ContentEncoding::Brotli => "br",
+        }
+    }
+}
+
+/// 请求体压缩配置（`compress = gzip` 或 `compress = gzip(min_size = 4096)`）
+///
+/// 小于 `min_size` 字节的请求体不压缩。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressConfig {
+    pub encoding: ContentEncoding,
+    pub min_size: usize,
+}
+
+impl CompressConfig {
+    /// 默认压缩阈值：1 KiB 以下的请求体压缩收益通常抵不上开销
+    pub const DEFAULT_MIN_SIZE: usize = 1024;
+
+    pub fn new(encoding: ContentEncoding) -> Self {
+        Self { encoding, min_size: Self::DEFAULT_MIN_SIZE }
+    }
+
+    /// 设置压缩阈值（字节）
+    pub fn min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encoding() {
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Zstd, ContentEncoding::Brotli] {
+            assert_eq!(ContentEncoding::parse(encoding.as_str()), Some(encoding));
+        }
+        assert_eq!(ContentEncoding::parse("brotli"), None);
+        assert_eq!(ContentEncoding::parse("deflate"), None);
+    }
+}
@@ -0,0 +1,115 @@
+use super::{CompressConfig, ContentEncoding};
+use crate::error::SwanError;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_LENGTH, HeaderValue};
+use std::io::Write;
+
+/// Brotli 质量：默认的 11 过慢，5 在速度与压缩率之间更适合在线请求
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW: u32 = 22;
+
+impl ContentEncoding {
+    /// 压缩数据（gzip 与 zstd 使用各自的默认级别）
+    pub fn encode(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            ContentEncoding::Zstd => zstd::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL),
+            ContentEncoding::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+                encoder.write_all(data)?;
+                encoder.flush()?;
+                Ok(encoder.into_inner())
+            }
+        }
+    }
+}
+
+impl CompressConfig {
+    /// 压缩请求体并设置 `Content-Encoding`
+    ///
+    /// 以下情况保持请求不变：没有请求体或请求体为流、小于 `min_size`、
+    /// 已经带有 `Content-Encoding`（如拦截器自行编码）。
+    pub fn compress_request(&self, request: &mut reqwest::Request) -> Result<(), SwanError> {
+        if request.headers().contains_key(CONTENT_ENCODING) {
+            return Ok(());
+        }
+        let Some(body) = request.body().and_then(|body| body.as_bytes()) else {
+            return Ok(());
+        };
+        if body.is_empty() || body.len() < self.min_size {
+            return Ok(());
+        }
+
+        let compressed = self.encoding.encode(body)?;
+        *request.body_mut() = Some(compressed.into());
+        let headers = request.headers_mut();
+        headers.remove(CONTENT_LENGTH);
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(self.encoding.as_str()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn decode(encoding: ContentEncoding, data: &[u8]) -> Vec<u8> {
+        let mut decoded = Vec::new();
+        match encoding {
+            ContentEncoding::Gzip => flate2::read::GzDecoder::new(data).read_to_end(&mut decoded).unwrap(),
+            ContentEncoding::Zstd => zstd::Decoder::new(data).unwrap().read_to_end(&mut decoded).unwrap(),
+            ContentEncoding::Brotli => brotli::Decompressor::new(data, 4096).read_to_end(&mut decoded).unwrap(),
+        };
+        decoded
+    }
+
+    fn request(body: &str) -> reqwest::Request {
+        let mut request = reqwest::Request::new(reqwest::Method::POST, "https://ingest.example.com/events".parse().unwrap());
+        *request.body_mut() = Some(body.to_string().into());
+        request
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let data = r#"{"event":"click"}"#.repeat(100);
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Zstd, ContentEncoding::Brotli] {
+            let encoded = encoding.encode(data.as_bytes()).unwrap();
+            assert!(encoded.len() < data.len());
+            assert_eq!(decode(encoding, &encoded), data.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_compress_request() {
+        let data = "x".repeat(2048);
+        let mut request = request(&data);
+        CompressConfig::new(ContentEncoding::Gzip).compress_request(&mut request).unwrap();
+
+        assert_eq!(request.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        let body = request.body().and_then(|body| body.as_bytes()).unwrap();
+        assert_eq!(decode(ContentEncoding::Gzip, body), data.as_bytes());
+    }
+
+    #[test]
+    fn test_compress_request_skips_small_or_encoded_bodies() {
+        let config = CompressConfig::new(ContentEncoding::Zstd);
+
+        let mut small = request("{}");
+        config.compress_request(&mut small).unwrap();
+        assert!(small.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(small.body().and_then(|body| body.as_bytes()), Some(&b"{}"[..]));
+
+        let mut encoded = request(&"x".repeat(2048));
+        encoded.headers_mut().insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+        config.compress_request(&mut encoded).unwrap();
+        assert_eq!(encoded.body().and_then(|body| body.as_bytes()).map(<[u8]>::len), Some(2048));
+
+        let mut no_body = reqwest::Request::new(reqwest::Method::POST, "https://ingest.example.com/events".parse().unwrap());
+        config.min_size(0).compress_request(&mut no_body).unwrap();
+        assert!(no_body.headers().get(CONTENT_ENCODING).is_none());
+    }
+}
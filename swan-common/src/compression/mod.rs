@@ -0,0 +1,4 @@
+pub mod config;
+pub mod encode;
+
+pub use config::{CompressConfig, ContentEncoding};
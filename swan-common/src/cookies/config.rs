@@ -0,0 +1,8 @@
+/// `#[http_client(cookies = ...)]` 解析得到的 Cookie 配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieConfig {
+    /// `cookies = true`：进程内存储
+    Memory,
+    /// `cookies = file("session.json")`：启动时从文件加载，`save()` 或存储被丢弃时写回
+    File(String),
+}
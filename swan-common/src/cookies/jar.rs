@@ -0,0 +1,258 @@
+use crate::error::SwanError;
+use cookie_store::{CookieError, CookieStore, RawCookie};
+use reqwest::header::HeaderValue;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// 可在多个客户端之间共享的 Cookie 存储
+///
+/// 按 RFC 6265 的域名、路径与过期规则保存响应中的 `Set-Cookie`，并为后续请求添加 `Cookie` 请求头。
+/// 克隆得到的 `CookieJar` 共享同一份存储。
+///
+/// 使用 [`CookieJar::persistent`] 创建时，存储与 JSON 文件关联：创建时加载，调用 [`CookieJar::save`]
+/// 或最后一个克隆被丢弃时写回有变化的内容（包括没有过期时间的会话 Cookie）。
+/// 收到 Cookie 时不读写文件，因此不会阻塞异步运行时；丢弃时的写回失败只记录日志。
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    store: Mutex<CookieStore>,
+    path: Option<PathBuf>,
+    /// 上次写回后是否有变化
+    dirty: AtomicBool,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if *self.dirty.get_mut()
+            && let Err(error) = self.save()
+        {
+            log::warn!("Failed to save cookies: {}", error);
+        }
+    }
+}
+
+impl CookieJar {
+    /// 创建进程内的空存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建与 `path` 关联的存储，文件存在时加载其中未过期的 Cookie
+    pub fn persistent(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let store = match std::fs::File::open(&path) {
+            Ok(file) => cookie_store::serde::json::load(BufReader::new(file)).unwrap_or_else(|error| {
+                log::warn!("Failed to load cookies from {}: {}", path.display(), error);
+                CookieStore::default()
+            }),
+            Err(error) => {
+                if error.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!("Failed to open cookie file {}: {}", path.display(), error);
+                }
+                CookieStore::default()
+            }
+        };
+
+        Self {
+            inner: Arc::new(Inner {
+                store: Mutex::new(store),
+                path: Some(path),
+                dirty: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// 关联的文件路径
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    /// 请求 `url` 时会发送的 Cookie，按 `(名称, 取值)` 返回
+    pub fn cookies(&self, url: &str) -> Vec<(String, String)> {
+        let Ok(url) = reqwest::Url::parse(url) else {
+            return Vec::new();
+        };
+        self.lock()
+            .get_request_values(&url)
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// 请求 `url` 时会发送的名为 `name` 的 Cookie 取值
+    pub fn get(&self, url: &str, name: &str) -> Option<String> {
+        self.cookies(url).into_iter().find(|(cookie, _)| cookie == name).map(|(_, value)| value)
+    }
+
+    /// 以 `Set-Cookie` 语法写入 Cookie，如 `insert("https://api.example.com", "session=abc; Path=/")`
+    ///
+    /// 效果与从 `url` 收到该响应头相同，用于预置登录态；已过期的 Cookie 会移除同名 Cookie。
+    pub fn insert(&self, url: &str, set_cookie: &str) -> Result<(), SwanError> {
+        let url = reqwest::Url::parse(url).map_err(SwanError::request)?;
+        match self.lock().parse(set_cookie, &url) {
+            // 已过期且存储中没有同名 Cookie，无需写入
+            Ok(_) | Err(CookieError::Expired) => {}
+            Err(error) => return Err(SwanError::request(error)),
+        }
+        self.mark_dirty();
+        Ok(())
+    }
+
+    /// 移除全部 Cookie
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.mark_dirty();
+    }
+
+    /// 立即写入关联的文件，未关联文件时不做任何事
+    ///
+    /// 同步读写文件，在异步代码中可放入 `tokio::task::spawn_blocking`。
+    pub fn save(&self) -> Result<(), SwanError> {
+        self.inner.save()
+    }
+
+    fn mark_dirty(&self) {
+        if self.inner.path.is_some() {
+            self.inner.dirty.store(true, Ordering::Release);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CookieStore> {
+        self.inner.lock()
+    }
+}
+
+impl Inner {
+    fn save(&self) -> Result<(), SwanError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        // 先清除标记，写入期间的新变化会留到下次写回
+        self.dirty.store(false, Ordering::Release);
+        let mut contents = Vec::new();
+        let saved = cookie_store::serde::json::save_incl_expired_and_nonpersistent(&self.lock(), &mut contents)
+            .map_err(SwanError::request)
+            .and_then(|()| write_atomically(path, &contents));
+        if saved.is_err() {
+            self.dirty.store(true, Ordering::Release);
+        }
+        saved
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CookieStore> {
+        self.store.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 先写临时文件再重命名，进程中途退出不会留下写了一半的文件
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), SwanError> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension(format!("{}.tmp", fastrand::u64(..)));
+    std::fs::write(&temp, contents)?;
+    if let Err(error) = std::fs::rename(&temp, path) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &reqwest::Url) {
+        let cookies: Vec<_> = cookie_headers
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| RawCookie::parse(value.to_string()).ok())
+            .collect();
+        if cookies.is_empty() {
+            return;
+        }
+
+        self.lock().store_response_cookies(cookies.into_iter(), url);
+        self.mark_dirty();
+    }
+
+    fn cookies(&self, url: &reqwest::Url) -> Option<HeaderValue> {
+        let header = self.lock()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        if header.is_empty() {
+            return None;
+        }
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::cookie::CookieStore as _;
+
+    #[test]
+    fn test_store_and_send_cookies() {
+        let jar = CookieJar::new();
+        let url = reqwest::Url::parse("https://api.example.com/login").unwrap();
+        let headers = [
+            HeaderValue::from_static("session=abc; Path=/"),
+            HeaderValue::from_static("admin=1; Path=/admin"),
+        ];
+        jar.set_cookies(&mut headers.iter(), &url);
+
+        let items = reqwest::Url::parse("https://api.example.com/items").unwrap();
+        assert_eq!(reqwest::cookie::CookieStore::cookies(&jar, &items).unwrap(), "session=abc");
+        assert_eq!(jar.get("https://api.example.com/admin/users", "admin").as_deref(), Some("1"));
+        assert!(jar.cookies("https://other.example.com/").is_empty());
+
+        // 克隆共享同一份存储
+        let shared = jar.clone();
+        shared.insert("https://api.example.com", "session=def; Path=/").unwrap();
+        assert_eq!(jar.get("https://api.example.com/", "session").as_deref(), Some("def"));
+
+        jar.clear();
+        assert!(shared.cookies("https://api.example.com/").is_empty());
+    }
+
+    #[test]
+    fn test_insert_rejects_invalid_input() {
+        let jar = CookieJar::new();
+        assert!(jar.insert("not a url", "a=b").is_err());
+        assert!(jar.insert("https://api.example.com", "=").is_err());
+        assert!(jar.insert("https://api.example.com", "a=b; Domain=other.com").is_err());
+    }
+
+    #[test]
+    fn test_persistent_jar_round_trip() {
+        let dir = std::env::temp_dir().join(format!("swan-cookies-{}", fastrand::u64(..)));
+        let path = dir.join("session.json");
+
+        let jar = CookieJar::persistent(&path);
+        assert!(jar.cookies("https://api.example.com/").is_empty());
+        jar.insert("https://api.example.com", "session=abc").unwrap();
+        jar.insert("https://api.example.com", "stale=1").unwrap();
+        jar.insert("https://api.example.com", "stale=1; Max-Age=0").unwrap();
+        jar.insert("https://api.example.com", "never=1; Max-Age=0").unwrap();
+        // 变化只在 save() 或丢弃时写回
+        assert!(!path.exists());
+        drop(jar);
+
+        let reloaded = CookieJar::persistent(&path);
+        assert_eq!(reloaded.cookies("https://api.example.com/"), [("session".to_string(), "abc".to_string())]);
+        assert_eq!(reloaded.path(), Some(path.as_path()));
+
+        reloaded.insert("https://api.example.com", "theme=dark").unwrap();
+        reloaded.save().unwrap();
+        assert_eq!(CookieJar::persistent(&path).get("https://api.example.com/", "theme").as_deref(), Some("dark"));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(CookieJar::persistent(&path).cookies("https://api.example.com/").is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod jar;
+
+pub use config::CookieConfig;
+pub use jar::CookieJar;
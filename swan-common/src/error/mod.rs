@@ -0,0 +1,3 @@
+pub mod swan_error;
+
+pub use swan_error::{SwanError, SharedError, BoxError};
@@ -0,0 +1,384 @@
+use bytes::Bytes;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::fmt;
+use crate::types::RetryErrorClass;
+
+/// 装箱的底层错误类型
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Swan HTTP 客户端的错误类型
+///
+/// 生成的方法内部统一产生 `SwanError`，再通过 `From` 转换为方法声明的错误类型，
+/// 因此方法可以返回 `anyhow::Result<T>`、`Result<T, SwanError>`，
+/// 或任意满足 `E: From<SwanError>` 的 `Result<T, E>`。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// match client.get_user(1).await {
+///     Err(SwanError::Status { status, body, .. }) if status == 404 => { /* 未找到 */ }
+///     Err(e) if e.is_timeout() => { /* 超时 */ }
+///     other => { /* ... */ }
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SwanError {
+    /// 请求超时
+    Timeout(reqwest::Error),
+    /// 建立连接失败（DNS、拒绝连接、TLS 握手等）
+    Connect(reqwest::Error),
+    /// 服务端返回非 2xx 状态码
+    ///
+    /// 响应头装箱存放，避免 `Result<T, SwanError>` 整体过大。
+    Status {
+        status: StatusCode,
+        headers: Box<HeaderMap>,
+        body: Bytes,
+    },
+    /// 响应体读取或反序列化失败
+    Decode(BoxError),
+    /// 拦截器返回错误
+    Interceptor(anyhow::Error),
+    /// 请求无法构建或发送（URL 非法、请求体不可克隆等）
+    Request(BoxError),
+    /// 本地文件读写失败（如 `download_to` 写入目标文件）
+    Io(std::io::Error),
+    /// 目标主机的熔断器处于打开状态，请求未发送
+    CircuitOpen {
+        host: String,
+    },
+    /// 超出客户端限流（`on_exceed = error`），请求未发送
+    RateLimited {
+        /// 距离下一个令牌可用的时间
+        retry_after: std::time::Duration,
+    },
+    /// 无法获取凭据或为请求签名（环境变量缺失、令牌端点返回错误、流式请求体无法签名等），请求未发送
+    Auth(BoxError),
+    /// 合并请求（`coalesce`）的等待方收到的、由发起方产生且无法复制的错误
+    ///
+    /// 判断方法（`is_timeout`、`kind` 等）与 `Display` 与发起方收到的错误一致。
+    Shared(SharedError),
+}
+
+/// 合并请求的等待方收到的发起方错误副本
+///
+/// 底层错误（如 `reqwest::Error`）无法复制，因此只保留错误类别与说明文字，
+/// 不再提供 `source()`；发起方本身收到的是原始错误。
+#[derive(Debug, Clone)]
+pub struct SharedError {
+    kind: &'static str,
+    retry_error_class: Option<RetryErrorClass>,
+    message: String,
+}
+
+impl SharedError {
+    /// 原始错误的类别，与 [`SwanError::kind`] 相同
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    /// 原始错误的说明文字
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for SharedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl SwanError {
+    /// 从非 2xx 响应构建 [`SwanError::Status`]，读取完整响应体
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let headers = Box::new(response.headers().clone());
+        let body = response.bytes().await.unwrap_or_default();
+        SwanError::Status { status, headers, body }
+    }
+
+    /// 构建反序列化错误
+    pub fn decode<E>(error: E) -> Self
+    where
+        E: Into<BoxError>,
+    {
+        SwanError::Decode(error.into())
+    }
+
+    /// 构建请求错误
+    pub fn request<E>(error: E) -> Self
+    where
+        E: Into<BoxError>,
+    {
+        SwanError::Request(error.into())
+    }
+
+    /// 构建凭据或签名错误
+    pub fn auth<E>(error: E) -> Self
+    where
+        E: Into<BoxError>,
+    {
+        SwanError::Auth(error.into())
+    }
+
+    /// 是否为超时错误
+    pub fn is_timeout(&self) -> bool {
+        self.kind() == "timeout"
+    }
+
+    /// 是否为连接错误
+    pub fn is_connect(&self) -> bool {
+        self.kind() == "connect"
+    }
+
+    /// 是否因熔断器打开而快速失败
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self, SwanError::CircuitOpen { .. })
+    }
+
+    /// 是否因超出客户端限流而失败
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, SwanError::RateLimited { .. })
+    }
+
+    /// 复制一份错误交给合并请求的等待方
+    ///
+    /// 状态码、熔断与限流错误按原样复制，其余错误复制类别与说明文字，包装为 [`SwanError::Shared`]。
+    pub fn share(&self) -> Self {
+        match self {
+            SwanError::Status { status, headers, body } => SwanError::Status {
+                status: *status,
+                headers: headers.clone(),
+                body: body.clone(),
+            },
+            SwanError::CircuitOpen { host } => SwanError::CircuitOpen { host: host.clone() },
+            SwanError::RateLimited { retry_after } => SwanError::RateLimited { retry_after: *retry_after },
+            SwanError::Shared(shared) => SwanError::Shared(shared.clone()),
+            other => SwanError::Shared(SharedError {
+                kind: other.kind(),
+                retry_error_class: other.retry_error_class(),
+                message: other.to_string(),
+            }),
+        }
+    }
+
+    /// 是否为可重试的传输层错误
+    ///
+    /// 超时、连接失败以及发送过程中的网络错误可以重试；
+    /// 状态码错误、解码错误、拦截器错误与本地构建错误不会因重试而改变结果。
+    pub fn is_retryable(&self) -> bool {
+        self.retry_error_class().is_some()
+    }
+
+    /// 可重试错误的分类，供 `retry = "...(on_error=[...])"` 筛选
+    ///
+    /// 不可重试的错误返回 `None`。
+    pub fn retry_error_class(&self) -> Option<RetryErrorClass> {
+        match self {
+            SwanError::Timeout(_) => Some(RetryErrorClass::Timeout),
+            SwanError::Connect(_) => Some(RetryErrorClass::Connect),
+            SwanError::Request(e) if e.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_request) => {
+                Some(RetryErrorClass::Request)
+            }
+            SwanError::Shared(shared) => shared.retry_error_class,
+            _ => None,
+        }
+    }
+
+    /// 非 2xx 响应的状态码
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            SwanError::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// 错误类别的简短名称，适合作为指标标签或 span 字段
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SwanError::Timeout(_) => "timeout",
+            SwanError::Connect(_) => "connect",
+            SwanError::Status { .. } => "status",
+            SwanError::Decode(_) => "decode",
+            SwanError::Interceptor(_) => "interceptor",
+            SwanError::Request(_) => "request",
+            SwanError::Io(_) => "io",
+            SwanError::CircuitOpen { .. } => "circuit_open",
+            SwanError::RateLimited { .. } => "rate_limited",
+            SwanError::Auth(_) => "auth",
+            SwanError::Shared(shared) => shared.kind,
+        }
+    }
+}
+
+impl fmt::Display for SwanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwanError::Timeout(e) => write!(f, "Request timed out: {}", e),
+            SwanError::Connect(e) => write!(f, "Failed to connect: {}", e),
+            SwanError::Status { status, .. } => write!(f, "Request failed with status: {}", status),
+            SwanError::Decode(e) => write!(f, "Failed to decode response: {}", e),
+            SwanError::Interceptor(e) => write!(f, "Interceptor failed: {}", e),
+            SwanError::Request(e) => write!(f, "Request execution failed: {}", e),
+            SwanError::Io(e) => write!(f, "I/O error: {}", e),
+            SwanError::CircuitOpen { host } => write!(f, "Circuit breaker is open for {}", host),
+            SwanError::RateLimited { retry_after } => {
+                write!(f, "Client rate limit exceeded, next permit in {:?}", retry_after)
+            }
+            SwanError::Auth(e) => write!(f, "Failed to authenticate request: {}", e),
+            SwanError::Shared(shared) => shared.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SwanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SwanError::Timeout(e) | SwanError::Connect(e) => Some(e),
+            SwanError::Decode(e) | SwanError::Request(e) | SwanError::Auth(e) => Some(e.as_ref()),
+            SwanError::Interceptor(e) => Some(e.as_ref()),
+            SwanError::Io(e) => Some(e),
+            SwanError::Status { .. }
+            | SwanError::CircuitOpen { .. }
+            | SwanError::RateLimited { .. }
+            | SwanError::Shared(_) => None,
+        }
+    }
+}
+
+/// 按错误性质对 `reqwest::Error` 分类
+impl From<reqwest::Error> for SwanError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            SwanError::Timeout(error)
+        } else if error.is_connect() {
+            SwanError::Connect(error)
+        } else if error.is_decode() || error.is_body() {
+            SwanError::Decode(Box::new(error))
+        } else {
+            SwanError::Request(Box::new(error))
+        }
+    }
+}
+
+impl From<std::io::Error> for SwanError {
+    fn from(error: std::io::Error) -> Self {
+        SwanError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SwanError {
+    fn from(error: serde_json::Error) -> Self {
+        SwanError::Decode(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_error_accessors() {
+        let error = SwanError::Status {
+            status: StatusCode::NOT_FOUND,
+            headers: Box::default(),
+            body: Bytes::from_static(b"missing"),
+        };
+
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert!(!error.is_timeout());
+        assert_eq!(error.kind(), "status");
+        assert_eq!(error.to_string(), "Request failed with status: 404 Not Found");
+    }
+
+    #[test]
+    fn test_decode_error_from_serde() {
+        let serde_error = serde_json::from_slice::<u32>(b"not json").unwrap_err();
+        let error = SwanError::from(serde_error);
+
+        assert!(matches!(error, SwanError::Decode(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_converts_into_anyhow() {
+        let error: anyhow::Error = SwanError::request("invalid url").into();
+
+        let swan_error = error.downcast_ref::<SwanError>().unwrap();
+        assert!(matches!(swan_error, SwanError::Request(_)));
+    }
+
+    #[tokio::test]
+    async fn test_connect_error_classification() {
+        // 绑定后立即释放端口，保证连接被拒绝
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let error = reqwest::get(format!("http://{}", addr)).await.unwrap_err();
+
+        let error = SwanError::from(error);
+        assert!(error.is_connect());
+        assert_eq!(error.kind(), "connect");
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_error_class(), Some(RetryErrorClass::Connect));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_error_classification() {
+        // 接受连接但从不响应
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _connection = listener.accept().await;
+            std::future::pending::<()>().await;
+        });
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let error = client.get(format!("http://{}", addr)).send().await.unwrap_err();
+
+        let error = SwanError::from(error);
+        assert!(error.is_timeout());
+        assert_eq!(error.retry_error_class(), Some(RetryErrorClass::Timeout));
+        assert!(error.is_retryable());
+
+        let shared = error.share();
+        assert!(matches!(shared, SwanError::Shared(_)));
+        assert!(shared.is_timeout());
+        assert_eq!(shared.retry_error_class(), Some(RetryErrorClass::Timeout));
+        assert_eq!(shared.to_string(), error.to_string());
+    }
+
+    #[test]
+    fn test_share() {
+        let status = SwanError::Status {
+            status: StatusCode::NOT_FOUND,
+            headers: Box::default(),
+            body: Bytes::from_static(b"missing"),
+        };
+        assert!(matches!(status.share(), SwanError::Status { status, .. } if status == StatusCode::NOT_FOUND));
+
+        let decode = SwanError::decode("bad json");
+        let shared = decode.share();
+        assert!(matches!(shared, SwanError::Shared(_)));
+        assert_eq!(shared.kind(), "decode");
+        assert_eq!(shared.to_string(), decode.to_string());
+        assert!(!shared.is_retryable());
+        assert_eq!(shared.share().to_string(), decode.to_string());
+    }
+
+    #[test]
+    fn test_non_transport_errors_are_not_retryable() {
+        assert!(!SwanError::request("no mock matches").is_retryable());
+        assert!(!SwanError::Interceptor(anyhow::anyhow!("denied")).is_retryable());
+        assert!(!SwanError::decode("bad json").is_retryable());
+        assert!(!SwanError::CircuitOpen { host: "api.example.com".into() }.is_retryable());
+        assert!(!SwanError::auth("API_TOKEN is not set").is_retryable());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::SwanError;
+use crate::interceptor::traits::{SwanInterceptor, SwanStatefulInterceptor};
+use std::borrow::Cow;
+
+/// 执行无状态拦截器的请求前处理
+///
+/// 将已构建的请求还原为构建器交给拦截器处理，若拦截器返回了新的请求体，
+/// 则用其替换原有请求体，最后重新构建请求。
+pub async fn apply_before_request<I>(
+    client: &reqwest::Client,
+    interceptor: &I,
+    request: reqwest::Request,
+) -> Result<reqwest::Request, SwanError>
+where
+    I: SwanInterceptor + ?Sized,
+{
+    let body = request_body_bytes(&request);
+    let builder = reqwest::RequestBuilder::from_parts(client.clone(), request);
+    let (builder, new_body) = interceptor.before_request(builder, &body)
+        .await
+        .map_err(SwanError::Interceptor)?;
+    rebuild_request(builder, new_body)
+}
+
+/// 执行有状态拦截器的请求前处理
+///
+/// 与 [`apply_before_request`] 相同，但会把客户端状态传递给拦截器。
+pub async fn apply_stateful_before_request<State, I>(
+    client: &reqwest::Client,
+    interceptor: &I,
+    request: reqwest::Request,
+    state: Option<&State>,
+) -> Result<reqwest::Request, SwanError>
+where
+    I: SwanStatefulInterceptor<State> + ?Sized,
+{
+    let body = request_body_bytes(&request);
+    let builder = reqwest::RequestBuilder::from_parts(client.clone(), request);
+    let (builder, new_body) = interceptor.before_request(builder, &body, state)
+        .await
+        .map_err(SwanError::Interceptor)?;
+    rebuild_request(builder, new_body)
+}
+
+/// 复制请求体字节（流式请求体视为空）
+fn request_body_bytes(request: &reqwest::Request) -> Vec<u8> {
+    request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|bytes| bytes.to_vec())
+        .unwrap_or_default()
+}
+
+/// 根据拦截器返回结果重新构建请求
+///
+/// 仅当拦截器返回 `Cow::Owned` 时才替换请求体，`Cow::Borrowed` 表示请求体未被修改。
+fn rebuild_request(
+    builder: reqwest::RequestBuilder,
+    new_body: Cow<'_, [u8]>,
+) -> Result<reqwest::Request, SwanError> {
+    let builder = match new_body {
+        Cow::Owned(body) => builder.body(body),
+        Cow::Borrowed(_) => builder,
+    };
+
+    builder.build().map_err(SwanError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct RewriteInterceptor;
+
+    #[async_trait]
+    impl SwanInterceptor for RewriteInterceptor {
+        async fn before_request<'a>(
+            &self,
+            request: reqwest::RequestBuilder,
+            request_body: &'a [u8],
+        ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+            let mut body = request_body.to_vec();
+            body.extend_from_slice(b"-rewritten");
+            Ok((request.header("X-Rewritten", "1"), Cow::Owned(body)))
+        }
+
+        async fn after_response(
+            &self,
+            response: reqwest::Response,
+        ) -> anyhow::Result<reqwest::Response> {
+            Ok(response)
+        }
+    }
+
+    struct TokenInterceptor;
+
+    #[async_trait]
+    impl SwanStatefulInterceptor<String> for TokenInterceptor {
+        async fn before_request<'a>(
+            &self,
+            request: reqwest::RequestBuilder,
+            request_body: &'a [u8],
+            state: Option<&String>,
+        ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+            let token = state.cloned().unwrap_or_default();
+            Ok((request.header("Authorization", token), Cow::Borrowed(request_body)))
+        }
+
+        async fn after_response(
+            &self,
+            response: reqwest::Response,
+            _state: Option<&String>,
+        ) -> anyhow::Result<reqwest::Response> {
+            Ok(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_before_request_replaces_owned_body() {
+        let client = reqwest::Client::new();
+        let request = client.post("http://localhost/echo").body("payload").build().unwrap();
+
+        let request = apply_before_request(&client, &RewriteInterceptor, request).await.unwrap();
+
+        assert_eq!(request.headers().get("X-Rewritten").unwrap(), "1");
+        assert_eq!(request.body().unwrap().as_bytes().unwrap(), b"payload-rewritten");
+    }
+
+    struct RejectInterceptor;
+
+    #[async_trait]
+    impl SwanInterceptor for RejectInterceptor {
+        async fn before_request<'a>(
+            &self,
+            _request: reqwest::RequestBuilder,
+            _request_body: &'a [u8],
+        ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+            Err(anyhow::anyhow!("missing credentials"))
+        }
+
+        async fn after_response(
+            &self,
+            response: reqwest::Response,
+        ) -> anyhow::Result<reqwest::Response> {
+            Ok(response)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_before_request_maps_interceptor_error() {
+        let client = reqwest::Client::new();
+        let request = client.get("http://localhost/echo").build().unwrap();
+
+        let error = apply_before_request(&client, &RejectInterceptor, request).await.unwrap_err();
+
+        assert!(matches!(error, SwanError::Interceptor(_)));
+        assert!(error.to_string().contains("missing credentials"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_stateful_before_request_passes_state() {
+        let client = reqwest::Client::new();
+        let request = client.post("http://localhost/echo").body("payload").build().unwrap();
+        let state = "Bearer abc".to_string();
+
+        let request = apply_stateful_before_request(&client, &TokenInterceptor, request, Some(&state))
+            .await
+            .unwrap();
+
+        assert_eq!(request.headers().get("Authorization").unwrap(), "Bearer abc");
+        assert_eq!(request.body().unwrap().as_bytes().unwrap(), b"payload");
+    }
+}
@@ -1,5 +1,7 @@
 pub mod traits;
 pub mod cache;
+pub mod chain;
 
 pub use traits::{SwanInterceptor, SwanStatefulInterceptor, NoOpInterceptor, ClientStateMarker};
-pub use cache::InterceptorCache;
\ No newline at end of file
+pub use cache::InterceptorCache;
+pub use chain::{apply_before_request, apply_stateful_before_request};
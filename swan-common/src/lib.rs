@@ -6,11 +6,45 @@
 pub mod types;
 pub mod parsing;
 pub mod interceptor;
+pub mod error;
+pub mod client;
+pub mod url;
+pub mod response;
+pub mod multipart;
+pub mod testing;
+pub mod retry;
+pub mod circuit;
+pub mod rate_limit;
+pub mod telemetry;
+pub mod metrics;
+pub mod cache;
+pub mod coalesce;
+pub mod pagination;
+pub mod auth;
+pub mod signing;
+pub mod cookies;
+pub mod tls;
+pub mod compression;
 
 // Re-export commonly used types and traits for convenience
-pub use types::{HttpMethod, ContentType, HandlerArgs, HttpClientArgs, RetryPolicy, RetryConfig, ProxyConfig, ProxyType};
-pub use parsing::{parse_handler_args, parse_http_client_args};
+pub use types::{HttpMethod, ContentType, HandlerArgs, HttpClientArgs, RetryPolicy, RetryConfig, RetryErrorClass, ProxyConfig, ProxyType, ParamRole};
+pub use parsing::{parse_handler_args, parse_request_args, parse_http_client_args, parse_param_role};
 pub use interceptor::{SwanInterceptor, SwanStatefulInterceptor, InterceptorCache, NoOpInterceptor, ClientStateMarker};
+pub use error::SwanError;
+pub use client::ClientOptions;
+pub use response::{Response, ByteStream, StreamItem};
+pub use bytes::Bytes;
+pub use multipart::{FilePart, SwanMultipart};
+pub use retry::{RetryOutcome, SwanRetryPredicate};
+pub use circuit::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+pub use rate_limit::{OnExceed, RateLimitConfig, RateLimiter};
+pub use metrics::{AttemptInfo, NoopMetrics, RequestInfo, RequestOutcome, SwanMetrics};
+pub use cache::{CacheConfig, DiskCacheStore, MemoryCacheStore, SwanCacheStore};
+pub use auth::{ApiKeyAuth, BasicAuth, BearerAuth, OAuth2ClientCredentials, Secret, SwanAuth};
+pub use signing::{AwsSigV4Signer, HmacSigner, SwanSigner};
+pub use cookies::CookieJar;
+pub use tls::{TlsConfig, TlsVersion};
+pub use compression::{CompressConfig, ContentEncoding};
 
 #[cfg(test)]
 mod integration_tests {
@@ -0,0 +1,233 @@
+use super::{AttemptInfo, RequestInfo, RequestOutcome, SwanMetrics};
+use crate::error::SwanError;
+use reqwest::{Method, StatusCode, Url};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// 一次方法调用的指标记录器
+///
+/// 生成的方法在发送前创建，传给每次尝试；析构时触发 `on_request_end`，
+/// 因此提前返回错误或被取消的调用同样会报告最终结果。
+/// 客户端未注册 [`SwanMetrics`] 时所有方法都是空操作。
+#[derive(Debug)]
+pub struct CallMetrics<'a> {
+    recorder: Option<Recorder<'a>>,
+}
+
+#[derive(Debug)]
+struct Recorder<'a> {
+    metrics: &'a dyn SwanMetrics,
+    method: &'static str,
+    http_method: Method,
+    url: Url,
+    started_at: Instant,
+    state: Mutex<CallState>,
+}
+
+#[derive(Debug, Default)]
+struct CallState {
+    attempts: u32,
+    in_flight: bool,
+    cached: bool,
+    status: Option<StatusCode>,
+    error: Option<&'static str>,
+}
+
+impl<'a> CallMetrics<'a> {
+    /// 开始记录一次调用，触发 `on_request_start`
+    pub fn start(metrics: Option<&'a dyn SwanMetrics>, method: &'static str, request: &reqwest::Request) -> Self {
+        let recorder = metrics.map(|metrics| Recorder {
+            metrics,
+            method,
+            http_method: request.method().clone(),
+            url: request.url().clone(),
+            started_at: Instant::now(),
+            state: Mutex::new(CallState::default()),
+        });
+
+        if let Some(recorder) = &recorder {
+            recorder.metrics.on_request_start(&recorder.info());
+        }
+        Self { recorder }
+    }
+
+    /// 一次尝试开始发送，返回开始时间
+    pub fn attempt_start(&self) -> Option<Instant> {
+        let recorder = self.recorder.as_ref()?;
+        let mut state = recorder.lock();
+        state.attempts += 1;
+        state.in_flight = true;
+        Some(Instant::now())
+    }
+
+    /// 一次尝试结束，触发 `on_attempt_end`
+    pub fn attempt_end(&self, started_at: Option<Instant>, result: &Result<reqwest::Response, SwanError>) {
+        let (Some(recorder), Some(started_at)) = (&self.recorder, started_at) else { return };
+
+        let (status, error) = match result {
+            Ok(response) => (Some(response.status()), None),
+            Err(error) => (None, Some(error.kind())),
+        };
+        let attempt = {
+            let mut state = recorder.lock();
+            state.in_flight = false;
+            state.status = status;
+            state.error = error;
+            state.attempts
+        };
+
+        recorder.metrics.on_attempt_end(
+            &recorder.info(),
+            &AttemptInfo {
+                attempt,
+                status,
+                duration: started_at.elapsed(),
+                error,
+            },
+        );
+    }
+
+    /// 响应由 HTTP 缓存提供（新鲜命中或 304 重新验证），以缓存响应的状态码作为调用结果
+    pub fn cached(&self, status: StatusCode) {
+        if let Some(recorder) = &self.recorder {
+            let mut state = recorder.lock();
+            state.cached = true;
+            state.status = Some(status);
+            state.error = None;
+        }
+    }
+
+    /// 即将重试，触发 `on_retry`
+    pub fn retry(&self, attempt: u32, delay: Duration) {
+        if let Some(recorder) = &self.recorder {
+            recorder.metrics.on_retry(&recorder.info(), attempt, delay);
+        }
+    }
+}
+
+impl Recorder<'_> {
+    fn info(&self) -> RequestInfo<'_> {
+        RequestInfo {
+            method: self.method,
+            http_method: &self.http_method,
+            url: &self.url,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CallState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for CallMetrics<'_> {
+    fn drop(&mut self) {
+        let Some(recorder) = &self.recorder else { return };
+
+        let outcome = {
+            let state = recorder.lock();
+            let (status, error) = if state.in_flight {
+                (None, Some("cancelled"))
+            } else if state.attempts == 0 && !state.cached {
+                // 第一次尝试前失败（如请求体无法克隆）
+                (None, Some("request"))
+            } else {
+                (state.status, state.error)
+            };
+            RequestOutcome {
+                status,
+                attempts: state.attempts,
+                duration: recorder.started_at.elapsed(),
+                error,
+            }
+        };
+
+        recorder.metrics.on_request_end(&recorder.info(), &outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MetricsEvent, RecordingMetrics};
+
+    fn request() -> reqwest::Request {
+        reqwest::Request::new(Method::GET, Url::parse("https://api.example.com/users").unwrap())
+    }
+
+    fn response(status: u16) -> Result<reqwest::Response, SwanError> {
+        Ok(http::Response::builder().status(status).body("").unwrap().into())
+    }
+
+    #[test]
+    fn test_records_attempts_retries_and_outcome() {
+        let metrics = RecordingMetrics::new();
+        {
+            let call = CallMetrics::start(Some(&metrics), "list_users", &request());
+            let started = call.attempt_start();
+            call.attempt_end(started, &response(503));
+            call.retry(2, Duration::from_millis(10));
+            let started = call.attempt_start();
+            call.attempt_end(started, &response(200));
+        }
+
+        let events = metrics.events();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(&events[0], MetricsEvent::RequestStart { method: "list_users", url, .. } if url.path() == "/users"));
+        assert!(matches!(&events[1], MetricsEvent::AttemptEnd { attempt, .. } if attempt.attempt == 1 && attempt.status == Some(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(matches!(&events[2], MetricsEvent::Retry { attempt: 2, .. }));
+        assert!(matches!(&events[3], MetricsEvent::AttemptEnd { attempt, .. } if attempt.attempt == 2 && attempt.error.is_none()));
+
+        let outcome = metrics.outcomes().pop().unwrap();
+        assert!(outcome.is_success());
+        assert_eq!(outcome.attempts, 2);
+    }
+
+    #[test]
+    fn test_outcome_reports_last_error() {
+        let metrics = RecordingMetrics::new();
+        {
+            let call = CallMetrics::start(Some(&metrics), "list_users", &request());
+            let started = call.attempt_start();
+            call.attempt_end(started, &Err(SwanError::CircuitOpen { host: "api.example.com".into() }));
+        }
+
+        let outcome = metrics.outcomes().pop().unwrap();
+        assert_eq!(outcome.status, None);
+        assert_eq!(outcome.error, Some("circuit_open"));
+    }
+
+    #[test]
+    fn test_cache_hit_without_attempts() {
+        let metrics = RecordingMetrics::new();
+        {
+            let call = CallMetrics::start(Some(&metrics), "list_users", &request());
+            call.cached(StatusCode::OK);
+        }
+
+        let outcome = metrics.outcomes().pop().unwrap();
+        assert!(outcome.is_success());
+        assert_eq!(outcome.attempts, 0);
+        assert_eq!(outcome.error, None);
+    }
+
+    #[test]
+    fn test_dropped_in_flight_is_cancelled() {
+        let metrics = RecordingMetrics::new();
+        {
+            let call = CallMetrics::start(Some(&metrics), "list_users", &request());
+            let _started = call.attempt_start();
+        }
+
+        let outcome = metrics.outcomes().pop().unwrap();
+        assert_eq!(outcome.error, Some("cancelled"));
+        assert_eq!(outcome.attempts, 1);
+    }
+
+    #[test]
+    fn test_without_metrics_is_noop() {
+        let call = CallMetrics::start(None, "list_users", &request());
+        assert!(call.attempt_start().is_none());
+        call.attempt_end(None, &response(200));
+        call.retry(2, Duration::ZERO);
+    }
+}
@@ -0,0 +1,95 @@
+use reqwest::{Method, StatusCode, Url};
+use std::fmt;
+use std::time::Duration;
+
+/// 一次方法调用的请求信息
+#[derive(Debug, Clone, Copy)]
+pub struct RequestInfo<'a> {
+    /// 客户端方法名
+    pub method: &'static str,
+    /// 请求方法
+    pub http_method: &'a Method,
+    /// 请求 URL
+    pub url: &'a Url,
+}
+
+/// 一次发送尝试的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttemptInfo {
+    /// 尝试序号，从 1 开始
+    pub attempt: u32,
+    /// 响应状态码；传输层失败时为 `None`
+    pub status: Option<StatusCode>,
+    /// 本次尝试的耗时（含限流等待）
+    pub duration: Duration,
+    /// 传输层失败的错误类别，见 [`SwanError::kind`](crate::SwanError::kind)
+    pub error: Option<&'static str>,
+}
+
+/// 一次方法调用的最终结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestOutcome {
+    /// 最终响应的状态码；调用以错误结束时为 `None`
+    pub status: Option<StatusCode>,
+    /// 实际发送的尝试次数
+    pub attempts: u32,
+    /// 从开始发送到调用结束（含读取响应体）的耗时
+    pub duration: Duration,
+    /// 最后一次尝试的错误类别；调用在尝试完成前被取消时为 `"cancelled"`
+    pub error: Option<&'static str>,
+}
+
+impl RequestOutcome {
+    /// 是否以 2xx 响应结束
+    pub fn is_success(&self) -> bool {
+        self.status.is_some_and(|status| status.is_success())
+    }
+}
+
+/// 请求指标钩子
+///
+/// 通过 `#[http_client(metrics = MyMetrics)]`（类型需实现 `Default`）或构建器的 `metrics()` 注册，
+/// 可以将调用结果导出到 Prometheus 等监控系统。所有回调都有空的默认实现，只需覆盖关心的部分。
+/// 未注册时生成的代码跳过所有计时与回调。
+///
+/// 一次调用依次触发：`on_request_start`，每次尝试结束时的 `on_attempt_end`，
+/// 每次重试等待前的 `on_retry`，以及调用结束时的 `on_request_end`。
+///
+/// ```rust,ignore
+/// #[derive(Default)]
+/// struct PrometheusMetrics;
+///
+/// impl SwanMetrics for PrometheusMetrics {
+///     fn on_attempt_end(&self, request: &RequestInfo<'_>, attempt: &AttemptInfo) {
+///         let status = attempt.status.map_or("none".into(), |status| status.as_u16().to_string());
+///         HTTP_LATENCY
+///             .with_label_values(&[request.method, &status])
+///             .observe(attempt.duration.as_secs_f64());
+///     }
+/// }
+/// ```
+pub trait SwanMetrics: Send + Sync {
+    /// 调用开始发送（请求前拦截器之后）
+    fn on_request_start(&self, _request: &RequestInfo<'_>) {}
+
+    /// 一次发送尝试结束
+    fn on_attempt_end(&self, _request: &RequestInfo<'_>, _attempt: &AttemptInfo) {}
+
+    /// 即将重试，`attempt` 为下一次尝试的序号，`delay` 为重试前的等待时间
+    fn on_retry(&self, _request: &RequestInfo<'_>, _attempt: u32, _delay: Duration) {}
+
+    /// 调用结束
+    fn on_request_end(&self, _request: &RequestInfo<'_>, _outcome: &RequestOutcome) {}
+}
+
+impl fmt::Debug for dyn SwanMetrics + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SwanMetrics")
+    }
+}
+
+/// 不记录任何指标的空实现
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopMetrics;
+
+impl SwanMetrics for NoopMetrics {}
@@ -0,0 +1,5 @@
+pub mod hooks;
+pub mod call;
+
+pub use hooks::{AttemptInfo, NoopMetrics, RequestInfo, RequestOutcome, SwanMetrics};
+pub use call::CallMetrics;
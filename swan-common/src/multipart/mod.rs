@@ -0,0 +1,3 @@
+pub mod part;
+
+pub use part::{FilePart, SwanMultipart};
@@ -0,0 +1,159 @@
+use reqwest::multipart::{Form, Part};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use tokio::io::AsyncRead;
+use crate::error::SwanError;
+
+/// 可转换为 `multipart/form-data` 表单的类型
+///
+/// 通常通过 `#[derive(SwanMultipart)]` 实现。`content_type = form_multipart`
+/// 的方法会在请求前调用 [`SwanMultipart::into_form`] 构建表单，
+/// 边界（boundary）与 `Content-Type` 头由 reqwest 的编码器生成。
+pub trait SwanMultipart {
+    /// 构建 multipart 表单（文件部分在此时打开）
+    fn into_form(self) -> impl Future<Output = Result<Form, SwanError>> + Send;
+}
+
+/// 文件部分的数据来源
+enum Source {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+    Reader(Pin<Box<dyn AsyncRead + Send + Sync>>),
+}
+
+/// multipart 表单中的文件部分
+///
+/// 支持内存字节、本地文件路径与任意异步读取器，可指定文件名与 MIME 类型。
+/// 文件路径未指定时，文件名取路径的最后一段，MIME 类型按扩展名推断。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[derive(SwanMultipart)]
+/// struct Upload {
+///     title: String,
+///     #[multipart(file_name = "report.csv", mime = "text/csv")]
+///     report: FilePart,
+/// }
+///
+/// let upload = Upload {
+///     title: "Q3".into(),
+///     report: FilePart::reader(tokio::fs::File::open("q3.csv").await?),
+/// };
+/// ```
+pub struct FilePart {
+    source: Source,
+    file_name: Option<String>,
+    mime: Option<String>,
+}
+
+impl FilePart {
+    /// 使用内存中的字节
+    pub fn bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self::new(Source::Bytes(bytes.into()))
+    }
+
+    /// 使用本地文件，发送时以流的方式读取
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Self::new(Source::Path(path.into()))
+    }
+
+    /// 使用异步读取器，发送时以流的方式读取
+    pub fn reader<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        Self::new(Source::Reader(Box::pin(reader)))
+    }
+
+    /// 设置文件名
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// 设置 MIME 类型
+    pub fn mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    fn new(source: Source) -> Self {
+        Self { source, file_name: None, mime: None }
+    }
+
+    /// 转换为 reqwest 的表单部分
+    pub async fn into_part(self) -> Result<Part, SwanError> {
+        let part = match self.source {
+            Source::Bytes(bytes) => Part::bytes(bytes),
+            Source::Path(path) => Part::file(path).await?,
+            Source::Reader(reader) => {
+                let stream = tokio_util::io::ReaderStream::new(reader);
+                Part::stream(reqwest::Body::wrap_stream(stream))
+            }
+        };
+
+        let part = match self.file_name {
+            Some(file_name) => part.file_name(file_name),
+            None => part,
+        };
+
+        match self.mime {
+            Some(mime) => part.mime_str(&mime).map_err(SwanError::from),
+            None => Ok(part),
+        }
+    }
+}
+
+impl std::fmt::Debug for FilePart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.source {
+            Source::Bytes(bytes) => format!("Bytes({})", bytes.len()),
+            Source::Path(path) => format!("Path({})", path.display()),
+            Source::Reader(_) => "Reader".to_string(),
+        };
+        f.debug_struct("FilePart")
+            .field("source", &source)
+            .field("file_name", &self.file_name)
+            .field("mime", &self.mime)
+            .finish()
+    }
+}
+
+impl From<Vec<u8>> for FilePart {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::bytes(bytes)
+    }
+}
+
+impl From<PathBuf> for FilePart {
+    fn from(path: PathBuf) -> Self {
+        Self::path(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_missing_file_is_io_error() {
+        let result = FilePart::path("/definitely/not/here.bin").into_part().await;
+        assert!(matches!(result, Err(SwanError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_mime_is_rejected() {
+        let result = FilePart::bytes(b"data".to_vec()).mime("not a mime").into_part().await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_debug_hides_contents() {
+        let part = FilePart::bytes(vec![1, 2, 3]).file_name("a.bin");
+        let debug = format!("{:?}", part);
+        assert!(debug.contains("Bytes(3)"));
+        assert!(debug.contains("a.bin"));
+    }
+}
@@ -0,0 +1,48 @@
+/// 翻页方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageMode {
+    /// 跟随响应头 `Link` 中 `rel="next"` 的 URL
+    LinkHeader,
+    /// 从响应体 `next` 指向的字段读取游标，作为下一页的查询参数 `param`
+    Cursor { param: String, next: String },
+    /// 查询参数 `param` 从 `start` 开始逐页加一，遇到空页时结束
+    Offset { param: String, start: u64 },
+}
+
+/// 方法级分页配置（`paginate = ...`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    /// 翻页方式
+    pub mode: PageMode,
+    /// 每页条目所在的 JSON Pointer（如 `/data`），未设置时响应体本身是数组
+    pub items: Option<String>,
+}
+
+impl Pagination {
+    /// 跟随 `Link: <...>; rel="next"` 翻页
+    pub fn link_header() -> Self {
+        Self { mode: PageMode::LinkHeader, items: None }
+    }
+
+    /// 游标翻页，`next` 为响应体中下一页游标的 JSON Pointer
+    pub fn cursor(param: impl Into<String>, next: impl Into<String>) -> Self {
+        Self {
+            mode: PageMode::Cursor { param: param.into(), next: next.into() },
+            items: None,
+        }
+    }
+
+    /// 页码翻页，第一页为 `start`
+    pub fn offset(param: impl Into<String>, start: u64) -> Self {
+        Self {
+            mode: PageMode::Offset { param: param.into(), start },
+            items: None,
+        }
+    }
+
+    /// 设置每页条目所在的 JSON Pointer
+    pub fn items(mut self, pointer: impl Into<String>) -> Self {
+        self.items = Some(pointer.into());
+        self
+    }
+}
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod stream;
+
+pub use config::{PageMode, Pagination};
+pub use stream::paginate;
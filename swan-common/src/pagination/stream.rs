@@ -0,0 +1,253 @@
+use super::{PageMode, Pagination};
+use crate::error::SwanError;
+use crate::url::set_query_param;
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
+
+/// 按 `pagination` 逐页请求，返回逐条产出条目的流
+///
+/// `first` 是第一页的请求；`fetch` 发送一页请求（含拦截器、限流与重试）并返回响应。
+/// 只有在消费者读完上一页的条目后才会请求下一页。出错时产出一次错误后结束。
+pub fn paginate<'a, T, E, F, Fut>(
+    pagination: Pagination,
+    first: Result<reqwest::Request, SwanError>,
+    fetch: F,
+) -> impl Stream<Item = Result<T, E>> + 'a
+where
+    T: DeserializeOwned + 'a,
+    E: From<SwanError> + 'a,
+    F: FnMut(reqwest::Request) -> Fut + 'a,
+    Fut: Future<Output = Result<reqwest::Response, SwanError>> + 'a,
+{
+    let first = first.map(|mut request| {
+        if let PageMode::Offset { param, start } = &pagination.mode
+            && query_value(request.url(), param).is_none()
+        {
+            set_query_param(request.url_mut(), param, &start.to_string());
+        }
+        request
+    });
+
+    let pager = Pager { pagination, fetch, next: Some(first) };
+    stream::unfold(pager, |mut pager| async move {
+        let request = pager.next.take()?;
+        let page = match request {
+            Ok(request) => pager.fetch_page(request).await,
+            Err(error) => Err(error),
+        };
+        Some((page, pager))
+    })
+    .flat_map(|page: Result<Vec<T>, SwanError>| {
+        let items: Vec<Result<T, E>> = match page {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(E::from(error))],
+        };
+        stream::iter(items)
+    })
+}
+
+struct Pager<F> {
+    pagination: Pagination,
+    fetch: F,
+    /// 下一页的请求，`None` 表示已经结束
+    next: Option<Result<reqwest::Request, SwanError>>,
+}
+
+impl<F, Fut> Pager<F>
+where
+    F: FnMut(reqwest::Request) -> Fut,
+    Fut: Future<Output = Result<reqwest::Response, SwanError>>,
+{
+    /// 请求一页并解码条目，同时准备下一页的请求
+    async fn fetch_page<T: DeserializeOwned>(&mut self, request: reqwest::Request) -> Result<Vec<T>, SwanError> {
+        let template = request.try_clone();
+        let response = (self.fetch)(request).await?;
+        if !response.status().is_success() {
+            return Err(SwanError::from_response(response).await);
+        }
+
+        let next_link = next_link(response.headers(), response.url());
+        let bytes = response.bytes().await?;
+        let mut body: Value = serde_json::from_slice(&bytes)?;
+
+        let items = match &self.pagination.items {
+            Some(pointer) => body.pointer_mut(pointer)
+                .map(Value::take)
+                .ok_or_else(|| SwanError::decode(format!("Page items not found at {}", pointer)))?,
+            None => body.take(),
+        };
+        let items: Vec<T> = serde_json::from_value(items)?;
+
+        // 把 `next` 留到解码条目之后，避免 `items` 与 `next` 指向同一字段时互相影响
+        let Some(mut next) = template else { return Ok(items) };
+        let has_next = match &self.pagination.mode {
+            PageMode::LinkHeader => match next_link {
+                Some(url) if &url != next.url() => {
+                    *next.url_mut() = url;
+                    true
+                }
+                _ => false,
+            },
+            PageMode::Cursor { param, next: pointer } => match cursor_value(&body, pointer) {
+                Some(cursor) if query_value(next.url(), param).as_deref() != Some(cursor.as_str()) => {
+                    set_query_param(next.url_mut(), param, &cursor);
+                    true
+                }
+                _ => false,
+            },
+            PageMode::Offset { param, start } => {
+                let current = query_value(next.url(), param).and_then(|page| page.parse().ok()).unwrap_or(*start);
+                set_query_param(next.url_mut(), param, &(current + 1).to_string());
+                !items.is_empty()
+            }
+        };
+
+        if has_next {
+            self.next = Some(Ok(next));
+        }
+        Ok(items)
+    }
+}
+
+/// 读取游标，字符串与数字都可以作为游标；`null`、缺失或空字符串表示没有下一页
+fn cursor_value(body: &Value, pointer: &str) -> Option<String> {
+    match body.pointer(pointer)? {
+        Value::String(cursor) if !cursor.is_empty() => Some(cursor.clone()),
+        Value::Number(cursor) => Some(cursor.to_string()),
+        _ => None,
+    }
+}
+
+/// 解析 `Link` 响应头中 `rel="next"` 的 URL，相对地址按当前页 URL 解析
+fn next_link(headers: &HeaderMap, base: &Url) -> Option<Url> {
+    for value in headers.get_all(LINK) {
+        let Ok(mut rest) = value.to_str() else { continue };
+
+        while let Some(start) = rest.find('<') {
+            let end = start + rest[start..].find('>')?;
+            let target = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+
+            let params = &rest[..rest.find('<').unwrap_or(rest.len())];
+            let is_next = params.split(';').any(|param| {
+                let param = param.trim().trim_end_matches(',').trim();
+                param.split_once('=').is_some_and(|(name, value)| {
+                    name.trim().eq_ignore_ascii_case("rel")
+                        && value.trim().trim_matches('"').split_whitespace().any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+            });
+            if is_next {
+                return base.join(target).ok();
+            }
+        }
+    }
+    None
+}
+
+fn query_value(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs().find(|(key, _)| key == name).map(|(_, value)| value.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::TryStreamExt;
+    use reqwest::header::HeaderValue;
+    use std::collections::HashMap;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    fn link(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(LINK, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_next_link() {
+        let base = url("https://api.example.com/users?page=1");
+
+        let headers = link(r#"<https://api.example.com/users?page=2&a=1,2>; rel="next", <https://api.example.com/users?page=9>; rel="last""#);
+        assert_eq!(next_link(&headers, &base).unwrap().as_str(), "https://api.example.com/users?page=2&a=1,2");
+
+        let headers = link(r#"</users?page=1>; rel="prev first", </users?page=3>; rel=next"#);
+        assert_eq!(next_link(&headers, &base).unwrap().as_str(), "https://api.example.com/users?page=3");
+
+        assert!(next_link(&link(r#"</users?page=1>; rel="prev""#), &base).is_none());
+        assert!(next_link(&HeaderMap::new(), &base).is_none());
+    }
+
+    /// 按 URL 返回预设响应体的 `fetch`
+    fn responses(pages: &[(&str, &str)]) -> impl FnMut(reqwest::Request) -> std::future::Ready<Result<reqwest::Response, SwanError>> {
+        let pages: HashMap<String, String> = pages.iter().map(|(url, body)| (url.to_string(), body.to_string())).collect();
+        move |request| {
+            let response = match pages.get(request.url().as_str()) {
+                Some(body) => http::Response::builder().status(200).body(body.clone()).unwrap(),
+                None => http::Response::builder().status(404).body(String::new()).unwrap(),
+            };
+            std::future::ready(Ok(response.into()))
+        }
+    }
+
+    fn first(target: &str) -> Result<reqwest::Request, SwanError> {
+        Ok(reqwest::Request::new(reqwest::Method::GET, url(target)))
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages() {
+        let fetch = responses(&[
+            ("https://api.example.com/users", r#"{"data":[1,2],"next_cursor":"c2"}"#),
+            ("https://api.example.com/users?cursor=c2", r#"{"data":[3],"next_cursor":null}"#),
+        ]);
+        let pagination = Pagination::cursor("cursor", "/next_cursor").items("/data");
+
+        let items: Vec<u32> = paginate::<u32, SwanError, _, _>(pagination, first("https://api.example.com/users"), fetch)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_offset_stops_on_empty_page() {
+        let fetch = responses(&[
+            ("https://api.example.com/users?page=1", "[1,2]"),
+            ("https://api.example.com/users?page=2", "[3]"),
+            ("https://api.example.com/users?page=3", "[]"),
+        ]);
+
+        let items: Vec<u32> = paginate::<u32, SwanError, _, _>(Pagination::offset("page", 1), first("https://api.example.com/users"), fetch)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_error_ends_the_stream() {
+        let fetch = responses(&[("https://api.example.com/users?page=1", "[1]")]);
+
+        let results: Vec<Result<u32, SwanError>> =
+            paginate(Pagination::offset("page", 1), first("https://api.example.com/users"), fetch).collect().await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(*results[0].as_ref().unwrap(), 1);
+        assert_eq!(results[1].as_ref().unwrap_err().status().map(|status| status.as_u16()), Some(404));
+    }
+
+    #[tokio::test]
+    async fn test_missing_items_is_a_decode_error() {
+        let fetch = responses(&[("https://api.example.com/users", r#"{"items":[]}"#)]);
+        let pagination = Pagination::link_header().items("/data");
+
+        let results: Vec<Result<u32, SwanError>> =
+            paginate(pagination, first("https://api.example.com/users"), fetch).collect().await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].as_ref().unwrap_err().kind(), "decode");
+    }
+}
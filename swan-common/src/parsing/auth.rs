@@ -0,0 +1,199 @@
+use crate::auth::{ApiKeyLocation, AuthConfig, Secret};
+use std::collections::HashMap;
+
+const AUTH_USAGE: &str = "auth must be bearer(...), basic(...), api_key(...) or oauth2_client_credentials(...)";
+
+/// 解析 `auth = bearer(env = "API_TOKEN")` 等客户端认证配置
+///
+/// 只有一个凭据的方式用 `env = "VAR"` 从环境变量读取；有多个凭据时，每个凭据 `name`
+/// 既可以写成 `name = "..."`，也可以写成 `name_env = "VAR"`。
+pub(crate) fn parse_auth_value(value: &syn::Expr) -> syn::Result<AuthConfig> {
+    let syn::Expr::Call(call) = value else {
+        return Err(syn::Error::new_spanned(value, AUTH_USAGE));
+    };
+    let provider = match &*call.func {
+        syn::Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    };
+
+    let mut args = ProviderArgs::parse(call)?;
+    let config = match provider.as_deref() {
+        Some("bearer") => AuthConfig::Bearer { token: args.secret("token", "env")? },
+        Some("basic") => AuthConfig::Basic {
+            username: args.secret("username", "username_env")?,
+            password: args.secret("password", "password_env")?,
+        },
+        Some("api_key") => {
+            let location = match (args.header_name("header")?, args.string("query")) {
+                (Some(header), None) => ApiKeyLocation::Header(header),
+                (None, Some(query)) => ApiKeyLocation::Query(query),
+                _ => return Err(syn::Error::new_spanned(value, "api_key requires exactly one of 'header' or 'query'")),
+            };
+            AuthConfig::ApiKey { location, key: args.secret("key", "env")? }
+        }
+        Some("oauth2_client_credentials") => AuthConfig::OAuth2ClientCredentials {
+            token_url: args.string("token_url")
+                .ok_or_else(|| syn::Error::new_spanned(value, "oauth2_client_credentials requires 'token_url'"))?,
+            client_id: args.secret("client_id", "client_id_env")?,
+            client_secret: args.secret("client_secret", "client_secret_env")?,
+            scopes: args.list("scopes").unwrap_or_default(),
+        },
+        _ => return Err(syn::Error::new_spanned(&call.func, AUTH_USAGE)),
+    };
+
+    args.finish()?;
+    Ok(config)
+}
+
+/// `provider(key = "value", ...)` 中的参数，取用后移除，剩余的参数视为不支持
+pub(super) struct ProviderArgs<'a> {
+    call: &'a syn::ExprCall,
+    values: HashMap<String, (syn::Ident, String)>,
+    lists: HashMap<String, (syn::Ident, Vec<String>)>,
+    flags: HashMap<String, (syn::Ident, bool)>,
+}
+
+impl<'a> ProviderArgs<'a> {
+    pub(super) fn parse(call: &'a syn::ExprCall) -> syn::Result<Self> {
+        let mut values = HashMap::new();
+        let mut lists = HashMap::new();
+        let mut flags = HashMap::new();
+
+        for arg in &call.args {
+            let syn::Expr::Assign(assign) = arg else {
+                return Err(syn::Error::new_spanned(arg, "expected key = value"));
+            };
+            let key = match &*assign.left {
+                syn::Expr::Path(path) => path.path.get_ident().cloned(),
+                _ => None,
+            };
+            let key = key.ok_or_else(|| syn::Error::new_spanned(&assign.left, "expected identifier as key"))?;
+
+            match &*assign.right {
+                syn::Expr::Array(array) => {
+                    let mut items = Vec::new();
+                    for element in &array.elems {
+                        let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = element else {
+                            return Err(syn::Error::new_spanned(element, format!("{} must contain string literals", key)));
+                        };
+                        items.push(lit.value());
+                    }
+                    lists.insert(key.to_string(), (key, items));
+                }
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(lit), .. }) => {
+                    flags.insert(key.to_string(), (key, lit.value));
+                }
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) if !lit.value().is_empty() => {
+                    values.insert(key.to_string(), (key, lit.value()));
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(other, format!("{} must be a non-empty string literal", key)));
+                }
+            }
+        }
+
+        Ok(Self { call, values, lists, flags })
+    }
+
+    pub(super) fn string(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key).map(|(_, value)| value)
+    }
+
+    /// 读取并校验请求头名称
+    pub(super) fn header_name(&mut self, key: &str) -> syn::Result<Option<String>> {
+        match self.values.remove(key) {
+            Some((ident, name)) if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() => {
+                Err(syn::Error::new_spanned(ident, format!("Invalid header name: {}", name)))
+            }
+            other => Ok(other.map(|(_, name)| name)),
+        }
+    }
+
+    pub(super) fn list(&mut self, key: &str) -> Option<Vec<String>> {
+        self.lists.remove(key).map(|(_, items)| items)
+    }
+
+    pub(super) fn flag(&mut self, key: &str) -> Option<bool> {
+        self.flags.remove(key).map(|(_, value)| value)
+    }
+
+    /// 读取 `key = "..."` 或 `env_key = "VAR"`，两者最多出现一个
+    pub(super) fn optional_secret(&mut self, key: &str, env_key: &str) -> syn::Result<Option<Secret>> {
+        match (self.string(key), self.string(env_key)) {
+            (Some(value), None) => Ok(Some(Secret::Value(value))),
+            (None, Some(name)) => Ok(Some(Secret::Env(name))),
+            (None, None) => Ok(None),
+            (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+                self.call,
+                format!("only one of '{}' or '{}' is allowed", key, env_key),
+            )),
+        }
+    }
+
+    /// 读取 `key = "..."` 或 `env_key = "VAR"`，两者必须且只能出现一个
+    pub(super) fn secret(&mut self, key: &str, env_key: &str) -> syn::Result<Secret> {
+        self.optional_secret(key, env_key)?.ok_or_else(|| syn::Error::new_spanned(
+            self.call,
+            format!("exactly one of '{}' or '{}' is required", key, env_key),
+        ))
+    }
+
+    pub(super) fn finish(self) -> syn::Result<()> {
+        let unused = self.values.into_values().map(|(ident, _)| ident)
+            .chain(self.lists.into_values().map(|(ident, _)| ident))
+            .chain(self.flags.into_values().map(|(ident, _)| ident))
+            .next();
+        match unused {
+            Some(ident) => Err(syn::Error::new_spanned(&ident, format!("'{}' is not supported here", ident))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_auth_value() {
+        let config = parse_auth_value(&parse_quote! { bearer(env = "API_TOKEN") }).unwrap();
+        assert_eq!(config, AuthConfig::Bearer { token: Secret::env("API_TOKEN") });
+
+        let config = parse_auth_value(&parse_quote! { basic(username = "svc", password_env = "SVC_PASSWORD") }).unwrap();
+        assert_eq!(config, AuthConfig::Basic { username: "svc".into(), password: Secret::env("SVC_PASSWORD") });
+
+        let config = parse_auth_value(&parse_quote! { api_key(header = "X-Api-Key", env = "API_KEY") }).unwrap();
+        assert_eq!(config, AuthConfig::ApiKey {
+            location: ApiKeyLocation::Header("X-Api-Key".into()),
+            key: Secret::env("API_KEY"),
+        });
+
+        let config = parse_auth_value(&parse_quote! {
+            oauth2_client_credentials(
+                token_url = "https://auth.example.com/token",
+                client_id = "swan",
+                client_secret_env = "CLIENT_SECRET",
+                scopes = ["read", "write"],
+            )
+        }).unwrap();
+        assert_eq!(config, AuthConfig::OAuth2ClientCredentials {
+            token_url: "https://auth.example.com/token".into(),
+            client_id: "swan".into(),
+            client_secret: Secret::env("CLIENT_SECRET"),
+            scopes: vec!["read".into(), "write".into()],
+        });
+    }
+
+    #[test]
+    fn test_parse_auth_value_errors() {
+        assert!(parse_auth_value(&parse_quote! { bearer }).is_err());
+        assert!(parse_auth_value(&parse_quote! { digest(env = "X") }).is_err());
+        assert!(parse_auth_value(&parse_quote! { bearer(token = "t", env = "API_TOKEN") }).is_err());
+        assert!(parse_auth_value(&parse_quote! { bearer(env = "API_TOKEN", header = "X") }).is_err());
+        assert!(parse_auth_value(&parse_quote! { bearer(env = "API_TOKEN", scopes = ["a"]) }).is_err());
+        assert!(parse_auth_value(&parse_quote! { api_key(env = "API_KEY") }).is_err());
+        assert!(parse_auth_value(&parse_quote! { api_key(header = "bad header", env = "API_KEY") }).is_err());
+        assert!(parse_auth_value(&parse_quote! { oauth2_client_credentials(client_id = "a", client_secret = "b") }).is_err());
+    }
+}
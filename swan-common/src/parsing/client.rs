@@ -2,6 +2,14 @@ use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{LitStr, Path, Token};
 use crate::types::{HttpClientArgs, ProxyConfig, ProxyType};
+use crate::circuit::CircuitBreakerConfig;
+use crate::cookies::CookieConfig;
+use crate::compression::ContentEncoding;
+use super::duration::parse_duration_value;
+use super::rate_limit::parse_rate_limit_value;
+use super::auth::parse_auth_value;
+use super::signer::parse_signer_value;
+use super::tls::parse_tls_value;
 
 impl Parse for HttpClientArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -9,6 +17,16 @@ impl Parse for HttpClientArgs {
         let mut interceptor = None;
         let mut state = None;
         let mut proxy = None;
+        let mut timeout_ms = None;
+        let mut connect_timeout_ms = None;
+        let mut circuit_breaker = None;
+        let mut rate_limit = None;
+        let mut metrics = None;
+        let mut auth = None;
+        let mut signer = None;
+        let mut cookies = None;
+        let mut tls = None;
+        let mut decompress = Vec::new();
 
         let pairs = Punctuated::<syn::Meta, Token![,]>::parse_terminated(input)?;
         for meta in pairs {
@@ -22,16 +40,37 @@ impl Parse for HttpClientArgs {
                         state = Some(parse_state_value(&nv.value)?);
                     } else if nv.path.is_ident("proxy") {
                         proxy = Some(parse_proxy_simple_value(&nv.value)?);
+                    } else if nv.path.is_ident("timeout") {
+                        timeout_ms = Some(parse_duration_value(&nv.value, "timeout")?);
+                    } else if nv.path.is_ident("connect_timeout") {
+                        connect_timeout_ms = Some(parse_duration_value(&nv.value, "connect_timeout")?);
+                    } else if nv.path.is_ident("circuit_breaker") {
+                        circuit_breaker = Some(parse_circuit_breaker_value(&nv.value)?);
+                    } else if nv.path.is_ident("rate_limit") {
+                        rate_limit = Some(parse_rate_limit_value(&nv.value)?);
+                    } else if nv.path.is_ident("metrics") {
+                        metrics = Some(parse_metrics_value(&nv.value)?);
+                    } else if nv.path.is_ident("auth") {
+                        auth = Some(parse_auth_value(&nv.value)?);
+                    } else if nv.path.is_ident("signer") {
+                        signer = Some(parse_signer_value(&nv.value)?);
+                    } else if nv.path.is_ident("cookies") {
+                        cookies = parse_cookies_value(&nv.value)?;
+                    } else if nv.path.is_ident("decompress") {
+                        decompress = parse_decompress_value(&nv.value)?;
                     } else {
                         return Err(syn::Error::new_spanned(
                             nv.path,
-                            "Only 'base_url', 'interceptor', 'state', or 'proxy' are supported",
+                            "Only 'base_url', 'interceptor', 'state', 'proxy', 'timeout', 'connect_timeout', 'circuit_breaker', 'rate_limit', 'metrics', 'auth', 'signer', 'cookies', or 'decompress' are supported",
                         ));
                     }
                 }
                 syn::Meta::List(ml) if ml.path.is_ident("proxy") => {
                     proxy = Some(parse_proxy_full_value(&ml)?);
                 }
+                syn::Meta::List(ml) if ml.path.is_ident("tls") => {
+                    tls = Some(parse_tls_value(&ml)?);
+                }
                 _ => {
                     return Err(syn::Error::new_spanned(meta, "Expected key-value pair or function-like macro"));
                 }
@@ -51,10 +90,35 @@ impl Parse for HttpClientArgs {
             interceptor,
             state,
             proxy,
+            timeout_ms,
+            connect_timeout_ms,
+            circuit_breaker,
+            rate_limit,
+            metrics,
+            auth,
+            signer,
+            cookies,
+            tls,
+            decompress,
         })
     }
 }
 
+fn parse_circuit_breaker_value(value: &syn::Expr) -> syn::Result<CircuitBreakerConfig> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit),
+        ..
+    }) = value
+    {
+        CircuitBreakerConfig::parse(&lit.value()).map_err(|msg| syn::Error::new(lit.span(), msg))
+    } else {
+        Err(syn::Error::new_spanned(
+            value,
+            "circuit_breaker must be a string literal (e.g., \"failures=5, window=30s, cooldown=10s\")",
+        ))
+    }
+}
+
 fn parse_base_url_value(value: &syn::Expr) -> syn::Result<LitStr> {
     if let syn::Expr::Lit(syn::ExprLit {
         lit: syn::Lit::Str(lit),
@@ -81,6 +145,58 @@ fn parse_interceptor_value(value: &syn::Expr) -> syn::Result<Path> {
     }
 }
 
+fn parse_metrics_value(value: &syn::Expr) -> syn::Result<Path> {
+    if let syn::Expr::Path(expr_path) = value {
+        Ok(expr_path.path.clone())
+    } else {
+        Err(syn::Error::new_spanned(
+            value,
+            "metrics must be a type path",
+        ))
+    }
+}
+
+/// 解析 `cookies = true`、`cookies = false` 或 `cookies = file("session.json")`
+fn parse_cookies_value(value: &syn::Expr) -> syn::Result<Option<CookieConfig>> {
+    const USAGE: &str = "cookies must be true, false or file(\"path\")";
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(enabled), .. }) => {
+            Ok(enabled.value.then_some(CookieConfig::Memory))
+        }
+        syn::Expr::Call(call) if matches!(&*call.func, syn::Expr::Path(path) if path.path.is_ident("file")) => {
+            match call.args.first() {
+                Some(syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(path), .. }))
+                    if call.args.len() == 1 && !path.value().is_empty() =>
+                {
+                    Ok(Some(CookieConfig::File(path.value())))
+                }
+                _ => Err(syn::Error::new_spanned(call, USAGE)),
+            }
+        }
+        _ => Err(syn::Error::new_spanned(value, USAGE)),
+    }
+}
+
+/// 解析 `decompress = [gzip, br, zstd]`
+fn parse_decompress_value(value: &syn::Expr) -> syn::Result<Vec<ContentEncoding>> {
+    const USAGE: &str = "decompress must be a list of gzip, br or zstd (e.g., decompress = [gzip, br])";
+    let syn::Expr::Array(array) = value else {
+        return Err(syn::Error::new_spanned(value, USAGE));
+    };
+    let mut encodings = Vec::new();
+    for element in &array.elems {
+        let encoding = match element {
+            syn::Expr::Path(path) => path.path.get_ident().and_then(|ident| ContentEncoding::parse(&ident.to_string())),
+            _ => None,
+        };
+        let encoding = encoding.ok_or_else(|| syn::Error::new_spanned(element, USAGE))?;
+        if !encodings.contains(&encoding) {
+            encodings.push(encoding);
+        }
+    }
+    Ok(encodings)
+}
+
 fn parse_state_value(value: &syn::Expr) -> syn::Result<Path> {
     if let syn::Expr::Path(expr_path) = value {
         Ok(expr_path.path.clone())
@@ -255,4 +371,108 @@ mod tests {
         let result = syn::parse2::<HttpClientArgs>(tokens);
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_timeouts() {
+        let tokens = quote! { base_url = "https://api.example.com", timeout = "5s", connect_timeout = "500ms" };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        assert_eq!(args.timeout_ms, Some(5000));
+        assert_eq!(args.connect_timeout_ms, Some(500));
+
+        let tokens = quote! { timeout = 5 };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_circuit_breaker() {
+        let tokens = quote! { base_url = "https://api.example.com", circuit_breaker = "failures=5, window=30s, cooldown=10s" };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        let config = args.circuit_breaker.unwrap();
+        assert_eq!(config.failures, 5);
+        assert_eq!(config.cooldown, std::time::Duration::from_secs(10));
+
+        let tokens = quote! { circuit_breaker = "failures=five" };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let tokens = quote! { base_url = "https://api.example.com", rate_limit = "10/s, burst=20" };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        let config = args.rate_limit.unwrap();
+        assert_eq!(config.requests, 10);
+        assert_eq!(config.burst, 20);
+
+        let tokens = quote! { rate_limit = "fast" };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_metrics() {
+        let tokens = quote! { base_url = "https://api.example.com", metrics = crate::PrometheusMetrics };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        let metrics = args.metrics.unwrap();
+        assert_eq!(quote! { #metrics }.to_string(), "crate :: PrometheusMetrics");
+
+        let tokens = quote! { metrics = "PrometheusMetrics" };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_auth() {
+        let tokens = quote! { base_url = "https://api.example.com", auth = bearer(env = "API_TOKEN") };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        assert!(matches!(args.auth, Some(crate::auth::AuthConfig::Bearer { .. })));
+
+        let tokens = quote! { auth = "Bearer abc" };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+
+    #[test]
+    fn test_parse_cookies() {
+        let args = syn::parse2::<HttpClientArgs>(quote! { cookies = true }).unwrap();
+        assert_eq!(args.cookies, Some(CookieConfig::Memory));
+
+        let args = syn::parse2::<HttpClientArgs>(quote! { cookies = false }).unwrap();
+        assert_eq!(args.cookies, None);
+
+        let args = syn::parse2::<HttpClientArgs>(quote! { cookies = file("session.json") }).unwrap();
+        assert_eq!(args.cookies, Some(CookieConfig::File("session.json".into())));
+
+        assert!(syn::parse2::<HttpClientArgs>(quote! { cookies = "session.json" }).is_err());
+        assert!(syn::parse2::<HttpClientArgs>(quote! { cookies = file() }).is_err());
+    }
+
+    #[test]
+    fn test_parse_decompress() {
+        let args = syn::parse2::<HttpClientArgs>(quote! { decompress = [gzip, br, zstd] }).unwrap();
+        assert_eq!(args.decompress, [ContentEncoding::Gzip, ContentEncoding::Brotli, ContentEncoding::Zstd]);
+
+        let args = syn::parse2::<HttpClientArgs>(quote! { base_url = "https://api.example.com" }).unwrap();
+        assert!(args.decompress.is_empty());
+
+        assert!(syn::parse2::<HttpClientArgs>(quote! { decompress = gzip }).is_err());
+        assert!(syn::parse2::<HttpClientArgs>(quote! { decompress = [deflate] }).is_err());
+    }
+
+    #[test]
+    fn test_parse_tls() {
+        let tokens = quote! { base_url = "https://internal.example.com", tls(ca = "ca.pem", min_version = "1.2") };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        let tls = args.tls.unwrap();
+        assert_eq!(tls.ca, [std::path::PathBuf::from("ca.pem")]);
+        assert_eq!(tls.min_version, Some(crate::tls::TlsVersion::Tls12));
+
+        assert!(syn::parse2::<HttpClientArgs>(quote! { tls = "ca.pem" }).is_err());
+    }
+
+    #[test]
+    fn test_parse_signer() {
+        let tokens = quote! { base_url = "https://s3.amazonaws.com", signer = aws_sigv4(region = "us-east-1", service = "s3") };
+        let args = syn::parse2::<HttpClientArgs>(tokens).unwrap();
+        assert!(matches!(args.signer, Some(crate::signing::SignerConfig::AwsSigV4 { .. })));
+
+        let tokens = quote! { signer = hmac };
+        assert!(syn::parse2::<HttpClientArgs>(tokens).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::types::RetryConfig;
+
+/// 解析时长参数（如 `timeout = "5s"`），返回毫秒数
+///
+/// 语法与重试配置相同，见 [`RetryConfig::parse_duration`]；时长必须大于 0。
+pub(crate) fn parse_duration_value(value: &syn::Expr, key: &str) -> syn::Result<u64> {
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit),
+        ..
+    }) = value
+    else {
+        return Err(syn::Error::new_spanned(
+            value,
+            format!("{} must be a duration string literal (e.g., \"5s\" or \"500ms\")", key),
+        ));
+    };
+
+    match RetryConfig::parse_duration(&lit.value()) {
+        Ok(0) => Err(syn::Error::new_spanned(lit, format!("{} must be greater than zero", key))),
+        Ok(millis) => Ok(millis),
+        Err(message) => Err(syn::Error::new_spanned(lit, format!("Invalid {}: {}", key, message))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_duration_value() {
+        assert_eq!(parse_duration_value(&parse_quote! { "5s" }, "timeout").unwrap(), 5000);
+        assert_eq!(parse_duration_value(&parse_quote! { "250ms" }, "timeout").unwrap(), 250);
+    }
+
+    #[test]
+    fn test_parse_duration_value_errors() {
+        assert!(parse_duration_value(&parse_quote! { 5 }, "timeout").is_err());
+        assert!(parse_duration_value(&parse_quote! { "soon" }, "timeout").is_err());
+        assert!(parse_duration_value(&parse_quote! { "0s" }, "timeout").is_err());
+    }
+}
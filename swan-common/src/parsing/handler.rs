@@ -1,74 +1,366 @@
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{LitStr, Meta, Path, Token};
+use crate::cache::CacheConfig;
+use crate::coalesce::CoalesceConfig;
+use crate::compression::{CompressConfig, ContentEncoding};
+use crate::pagination::Pagination;
 use crate::types::{ContentType, HandlerArgs, HttpMethod, RetryConfig, ProxyConfig, ProxyType};
+use super::duration::parse_duration_value;
+use super::rate_limit::parse_rate_limit_value;
 
 impl Parse for HandlerArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let method = None;
-        let mut url = None;
-        let mut content_type = None;
-        let mut headers = Punctuated::new();
-        let mut interceptor = None;
-        let mut retry = None;
-        let mut proxy = None;
-
-        let pairs = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
-        for pair in pairs {
-            match pair {
-                Meta::NameValue(name_value) => {
-                    let key = name_value.path.get_ident().ok_or_else(|| {
-                        syn::Error::new_spanned(&name_value.path, "expected identifier as key")
-                    })?;
-
-                    match key.to_string().as_str() {
-                        "url" => {
-                            url = Some(parse_url_value(&name_value.value)?);
-                        }
-                        "content_type" => {
-                            content_type = Some(parse_content_type_value(&name_value.value)?);
-                        }
-                        "header" => {
-                            headers.push(parse_header_value(&name_value.value)?);
-                        }
-                        "interceptor" => {
-                            interceptor = Some(parse_interceptor_value(&name_value.value)?);
-                        }
-                        "retry" => {
-                            retry = Some(parse_retry_value(&name_value.value)?);
-                        }
-                        "proxy" => {
-                            proxy = Some(parse_proxy_simple_value(&name_value.value)?);
-                        }
-                        _ => {
-                            return Err(syn::Error::new_spanned(
-                                key,
-                                "Only 'url', 'content_type', 'header', 'interceptor', 'retry', and 'proxy' are supported",
-                            ));
-                        }
+        parse_args(input, false)
+    }
+}
+
+/// 解析处理器参数
+/// 
+/// `method_attr` 为 true 时表示 `#[request]` 宏，必须通过 `method = "..."` 指定方法；
+/// 其余方法宏的 HTTP 方法由宏本身决定，不允许出现 `method` 参数。
+fn parse_args(input: ParseStream, method_attr: bool) -> syn::Result<HandlerArgs> {
+    let mut method = None;
+    let mut url = None;
+    let mut content_type = None;
+    let mut headers = Punctuated::new();
+    let mut interceptor = None;
+    let mut retry = None;
+    let mut retry_predicate = None;
+    let mut proxy = None;
+    let mut download_to = None;
+    let mut timeout_ms = None;
+    let mut rate_limit = None;
+    let mut cache = None;
+    let mut coalesce = None;
+    let mut paginate = None;
+    let mut compress = None;
+
+    let pairs = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+    for pair in pairs {
+        match pair {
+            Meta::NameValue(name_value) => {
+                let key = name_value.path.get_ident().ok_or_else(|| {
+                    syn::Error::new_spanned(&name_value.path, "expected identifier as key")
+                })?;
+
+                match key.to_string().as_str() {
+                    "method" if method_attr => {
+                        method = Some(parse_method_value(&name_value.value)?);
+                    }
+                    "method" => {
+                        return Err(syn::Error::new_spanned(
+                            key,
+                            "'method' is only supported by #[request]; use the matching method macro instead",
+                        ));
+                    }
+                    "url" => {
+                        url = Some(parse_url_value(&name_value.value)?);
+                    }
+                    "content_type" => {
+                        content_type = Some(parse_content_type_value(&name_value.value)?);
+                    }
+                    "header" => {
+                        headers.push(parse_header_value(&name_value.value)?);
+                    }
+                    "interceptor" => {
+                        interceptor = Some(parse_interceptor_value(&name_value.value)?);
+                    }
+                    "retry" => {
+                        retry = Some(parse_retry_value(&name_value.value)?);
+                    }
+                    "retry_predicate" => {
+                        retry_predicate = Some(parse_retry_predicate_value(&name_value.value)?);
+                    }
+                    "proxy" => {
+                        proxy = Some(parse_proxy_simple_value(&name_value.value)?);
+                    }
+                    "download_to" => {
+                        download_to = Some(parse_download_to_value(&name_value.value)?);
+                    }
+                    "timeout" => {
+                        timeout_ms = Some(parse_duration_value(&name_value.value, "timeout")?);
+                    }
+                    "rate_limit" => {
+                        rate_limit = Some(parse_rate_limit_value(&name_value.value)?);
+                    }
+                    "cache" => {
+                        cache = parse_cache_value(&name_value.value)?;
+                    }
+                    "coalesce" => {
+                        coalesce = parse_coalesce_value(&name_value.value)?;
+                    }
+                    "paginate" => {
+                        paginate = Some(parse_paginate_value(&name_value.value)?);
+                    }
+                    "compress" => {
+                        compress = Some(parse_compress_value(&name_value.value)?);
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            key,
+                            "Only 'url', 'content_type', 'header', 'interceptor', 'retry', 'retry_predicate', 'proxy', 'download_to', 'timeout', 'rate_limit', 'cache', 'coalesce', 'paginate', and 'compress' are supported",
+                        ));
                     }
                 }
-                Meta::List(meta_list) if meta_list.path.is_ident("proxy") => {
-                    proxy = Some(parse_proxy_full_value(&meta_list)?);
-                }
-                _ => {
-                    return Err(syn::Error::new_spanned(pair, "expected key-value pair or function-like macro"));
-                }
             }
+            Meta::List(meta_list) if meta_list.path.is_ident("proxy") => {
+                proxy = Some(parse_proxy_full_value(&meta_list)?);
+            }
+            Meta::List(meta_list) if meta_list.path.is_ident("coalesce") => {
+                coalesce = Some(parse_coalesce_list(&meta_list)?);
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(pair, "expected key-value pair or function-like macro"));
+            }
+        }
+    }
+
+    let url = url.ok_or_else(|| syn::Error::new(input.span(), "Missing required 'url' parameter"))?;
+    let method = match method {
+        Some(method) => method,
+        None if method_attr => {
+            return Err(syn::Error::new(input.span(), "Missing required 'method' parameter"));
+        }
+        None => HttpMethod::Get,
+    };
+
+    if let (Some(predicate), None) = (&retry_predicate, &retry) {
+        return Err(syn::Error::new_spanned(
+            predicate,
+            "retry_predicate requires a retry policy, e.g. retry = \"exponential(3, 100ms)\"",
+        ));
+    }
+
+    // multipart 请求体以流的方式发送，无法克隆后重放
+    if let (Some(ContentType::FormMultipart), Some(retry)) = (&content_type, &retry) {
+        return Err(syn::Error::new_spanned(
+            &retry.raw_config,
+            "retry is not supported with content_type = form_multipart because multipart bodies are streamed and cannot be replayed",
+        ));
+    }
+
+    if let (Some(ContentType::FormMultipart), Some(_)) = (&content_type, &compress) {
+        return Err(syn::Error::new(
+            input.span(),
+            "compress is not supported with content_type = form_multipart because multipart bodies are streamed",
+        ));
+    }
+    
+    Ok(HandlerArgs {
+        method,
+        url,
+        content_type,
+        headers,
+        interceptor,
+        retry,
+        retry_predicate,
+        proxy,
+        download_to,
+        timeout_ms,
+        rate_limit,
+        cache,
+        coalesce,
+        paginate,
+        compress,
+    })
+}
+
+/// 解析 `paginate = link_header`、`paginate = cursor(param = "cursor", next = "/next_cursor")`
+/// 或 `paginate = offset(param = "page", start = 1)`，三种方式都可以用 `items = "/data"` 指定条目位置
+fn parse_paginate_value(value: &syn::Expr) -> syn::Result<Pagination> {
+    const USAGE: &str = "paginate must be link_header, cursor(param = \"...\", next = \"/...\") or offset(param = \"...\")";
+
+    let (mode, args) = match value {
+        syn::Expr::Path(path) => (path.path.get_ident(), Punctuated::new()),
+        syn::Expr::Call(call) => match &*call.func {
+            syn::Expr::Path(path) => (path.path.get_ident(), call.args.clone()),
+            _ => (None, Punctuated::new()),
+        },
+        _ => (None, Punctuated::new()),
+    };
+    let mode = mode.ok_or_else(|| syn::Error::new_spanned(value, USAGE))?;
+
+    let mut param = None;
+    let mut next = None;
+    let mut start = None;
+    let mut items = None;
+    for arg in &args {
+        let syn::Expr::Assign(assign) = arg else {
+            return Err(syn::Error::new_spanned(arg, "expected key = value"));
+        };
+        let key = match &*assign.left {
+            syn::Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+            _ => None,
+        };
+        match key.as_deref() {
+            Some("param") => param = Some(parse_string_value(&assign.right, "param")?),
+            Some("next") => next = Some(parse_pointer_value(&assign.right, "next")?),
+            Some("items") => items = Some(parse_pointer_value(&assign.right, "items")?),
+            Some("start") => match &*assign.right {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => start = Some(lit.base10_parse::<u64>()?),
+                other => return Err(syn::Error::new_spanned(other, "start must be an integer literal")),
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &assign.left,
+                    "Only 'param', 'next', 'start', and 'items' are supported in paginate",
+                ));
+            }
+        }
+    }
+
+    let unexpected = |name: &str, present: bool| -> syn::Result<()> {
+        if present {
+            return Err(syn::Error::new_spanned(value, format!("'{}' is not supported by paginate = {}", name, mode)));
+        }
+        Ok(())
+    };
+    let pagination = match mode.to_string().as_str() {
+        "link_header" => {
+            unexpected("param", param.is_some())?;
+            unexpected("next", next.is_some())?;
+            unexpected("start", start.is_some())?;
+            Pagination::link_header()
+        }
+        "cursor" => {
+            unexpected("start", start.is_some())?;
+            match (param, next) {
+                (Some(param), Some(next)) => Pagination::cursor(param, next),
+                _ => return Err(syn::Error::new_spanned(value, "paginate = cursor(...) requires 'param' and 'next'")),
+            }
+        }
+        "offset" => {
+            unexpected("next", next.is_some())?;
+            let param = param.ok_or_else(|| syn::Error::new_spanned(value, "paginate = offset(...) requires 'param'"))?;
+            Pagination::offset(param, start.unwrap_or(1))
+        }
+        _ => return Err(syn::Error::new_spanned(mode, USAGE)),
+    };
+
+    Ok(match items {
+        Some(items) => pagination.items(items),
+        None => pagination,
+    })
+}
+
+fn parse_string_value(value: &syn::Expr, name: &str) -> syn::Result<String> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) if !lit.value().is_empty() => Ok(lit.value()),
+        _ => Err(syn::Error::new_spanned(value, format!("{} must be a non-empty string literal", name))),
+    }
+}
+
+/// JSON Pointer 必须以 `/` 开头（如 `/data`、`/meta/next`）
+fn parse_pointer_value(value: &syn::Expr, name: &str) -> syn::Result<String> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) if lit.value().starts_with('/') => Ok(lit.value()),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            format!("{} must be a JSON Pointer string literal starting with '/' (e.g., \"/data\")", name),
+        )),
+    }
+}
+
+/// 解析 `compress = gzip` 或 `compress = zstd(min_size = 4096)`，算法为 `gzip`、`zstd` 或 `br`
+fn parse_compress_value(value: &syn::Expr) -> syn::Result<CompressConfig> {
+    const USAGE: &str = "compress must be gzip, zstd or br, optionally with (min_size = <bytes>)";
+    let (func, args) = match value {
+        syn::Expr::Path(path) => (path, None),
+        syn::Expr::Call(call) => match &*call.func {
+            syn::Expr::Path(path) => (path, Some(&call.args)),
+            _ => return Err(syn::Error::new_spanned(value, USAGE)),
+        },
+        _ => return Err(syn::Error::new_spanned(value, USAGE)),
+    };
+    let encoding = func.path.get_ident()
+        .and_then(|ident| ContentEncoding::parse(&ident.to_string()))
+        .ok_or_else(|| syn::Error::new_spanned(func, USAGE))?;
+
+    let mut config = CompressConfig::new(encoding);
+    for arg in args.into_iter().flatten() {
+        let syn::Expr::Assign(assign) = arg else {
+            return Err(syn::Error::new_spanned(arg, USAGE));
+        };
+        if !matches!(&*assign.left, syn::Expr::Path(path) if path.path.is_ident("min_size")) {
+            return Err(syn::Error::new_spanned(&assign.left, "Only 'min_size' is supported in compress"));
+        }
+        let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(min_size), .. }) = &*assign.right else {
+            return Err(syn::Error::new_spanned(&assign.right, "min_size must be an integer number of bytes"));
+        };
+        config = config.min_size(min_size.base10_parse()?);
+    }
+    Ok(config)
+}
+
+/// 解析 `coalesce = true`，`coalesce = false` 等同于不设置
+fn parse_coalesce_value(value: &syn::Expr) -> syn::Result<Option<CoalesceConfig>> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(lit), .. }) => Ok(lit.value.then(CoalesceConfig::new)),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "coalesce must be a bool or coalesce(headers = [\"X-Tenant\"])",
+        )),
+    }
+}
+
+/// 解析 `coalesce(headers = ["X-Tenant"])`，列出的请求头追加到默认的合并键请求头之后
+fn parse_coalesce_list(meta_list: &syn::MetaList) -> syn::Result<CoalesceConfig> {
+    let mut config = CoalesceConfig::new();
+
+    let nested = meta_list.parse_args_with(Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated)?;
+    for nv in nested {
+        if !nv.path.is_ident("headers") {
+            return Err(syn::Error::new_spanned(&nv.path, "Only 'headers' is supported in coalesce(...)"));
+        }
+        let syn::Expr::Array(array) = &nv.value else {
+            return Err(syn::Error::new_spanned(&nv.value, "headers must be an array of string literals"));
+        };
+
+        for element in &array.elems {
+            let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = element else {
+                return Err(syn::Error::new_spanned(element, "header name must be a string literal"));
+            };
+            if reqwest::header::HeaderName::from_bytes(lit.value().as_bytes()).is_err() {
+                return Err(syn::Error::new_spanned(lit, format!("Invalid header name: {}", lit.value())));
+            }
+            config = config.key_header(lit.value());
         }
+    }
+
+    Ok(config)
+}
 
-        let url = url.ok_or_else(|| syn::Error::new(input.span(), "Missing required 'url' parameter"))?;
-        let method = method.unwrap_or(HttpMethod::Get);
-        
-        Ok(HandlerArgs {
-            method,
-            url,
-            content_type,
-            headers,
-            interceptor,
-            retry,
-            proxy,
+/// 解析 `cache = true` 或 `cache = "max_age=60s"`，`cache = false` 等同于不设置
+fn parse_cache_value(value: &syn::Expr) -> syn::Result<Option<CacheConfig>> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Bool(lit), .. }) => {
+            Ok(lit.value.then(CacheConfig::new))
+        }
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) => CacheConfig::parse(&lit.value())
+            .map(Some)
+            .map_err(|message| syn::Error::new_spanned(lit, message)),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            "cache must be a bool or a string literal (e.g., cache = true or cache = \"max_age=60s\")",
+        )),
+    }
+}
+
+fn parse_method_value(value: &syn::Expr) -> syn::Result<HttpMethod> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit),
+        ..
+    }) = value
+    {
+        HttpMethod::from_name(&lit.value()).ok_or_else(|| {
+            syn::Error::new_spanned(lit, "method must be a valid HTTP method token (e.g., \"PROPFIND\")")
         })
+    } else {
+        Err(syn::Error::new_spanned(
+            value,
+            "method must be a string literal (e.g., \"PROPFIND\")",
+        ))
     }
 }
 
@@ -140,6 +432,17 @@ fn parse_interceptor_value(value: &syn::Expr) -> syn::Result<Path> {
     }
 }
 
+fn parse_retry_predicate_value(value: &syn::Expr) -> syn::Result<Path> {
+    if let syn::Expr::Path(expr_path) = value {
+        Ok(expr_path.path.clone())
+    } else {
+        Err(syn::Error::new_spanned(
+            value,
+            "retry_predicate must be a type path implementing SwanRetryPredicate",
+        ))
+    }
+}
+
 fn parse_retry_value(value: &syn::Expr) -> syn::Result<RetryConfig> {
     if let syn::Expr::Lit(syn::ExprLit {
         lit: syn::Lit::Str(lit),
@@ -155,6 +458,18 @@ fn parse_retry_value(value: &syn::Expr) -> syn::Result<RetryConfig> {
     }
 }
 
+fn parse_download_to_value(value: &syn::Expr) -> syn::Result<syn::Ident> {
+    if let syn::Expr::Path(expr_path) = value
+        && let Some(ident) = expr_path.path.get_ident()
+    {
+        return Ok(ident.clone());
+    }
+    Err(syn::Error::new_spanned(
+        value,
+        "download_to must be the name of a path parameter (e.g., download_to = dest)",
+    ))
+}
+
 fn parse_proxy_simple_value(value: &syn::Expr) -> syn::Result<ProxyConfig> {
     match value {
         syn::Expr::Lit(syn::ExprLit {
@@ -274,6 +589,11 @@ pub fn parse_handler_args(input: ParseStream) -> syn::Result<HandlerArgs> {
     HandlerArgs::parse(input)
 }
 
+/// 解析 `#[request]` 宏参数的公共函数（必须包含 `method`）
+pub fn parse_request_args(input: ParseStream) -> syn::Result<HandlerArgs> {
+    parse_args(input, true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,6 +613,156 @@ mod tests {
         assert_eq!(result, ContentType::Json);
     }
 
+    #[test]
+    fn test_parse_request_args_custom_method() {
+        let args = syn::parse::Parser::parse2(
+            parse_request_args,
+            quote::quote! { method = "PROPFIND", url = "/dav" },
+        ).unwrap();
+        assert_eq!(args.method, HttpMethod::Custom("PROPFIND".to_string()));
+
+        let args = syn::parse::Parser::parse2(
+            parse_request_args,
+            quote::quote! { method = "PATCH", url = "/users" },
+        ).unwrap();
+        assert_eq!(args.method, HttpMethod::Patch);
+    }
+
+    #[test]
+    fn test_parse_request_args_requires_method() {
+        let result = syn::parse::Parser::parse2(parse_request_args, quote::quote! { url = "/dav" });
+        assert!(result.is_err());
+
+        let result = syn::parse::Parser::parse2(
+            parse_request_args,
+            quote::quote! { method = "BAD METHOD", url = "/dav" },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_method_rejected_outside_request() {
+        let result = syn::parse2::<HandlerArgs>(quote::quote! { method = "PUT", url = "/users" });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_multipart_rejects_retry() {
+        let result = syn::parse2::<HandlerArgs>(quote::quote! {
+            url = "/upload", content_type = form_multipart, retry = "exponential(3, 100ms)"
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_retry_predicate() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! {
+            url = "/orders", retry = "exponential(3, 100ms)", retry_predicate = RetryOnConflict
+        }).unwrap();
+        assert!(args.retry_predicate.unwrap().is_ident("RetryOnConflict"));
+
+        let result = syn::parse2::<HandlerArgs>(quote::quote! { url = "/orders", retry_predicate = RetryOnConflict });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! {
+            url = "/search", rate_limit = "2/s, on_exceed=error"
+        }).unwrap();
+        let config = args.rate_limit.unwrap();
+        assert_eq!(config.requests, 2);
+        assert_eq!(config.on_exceed, crate::OnExceed::Error);
+
+        let result = syn::parse2::<HandlerArgs>(quote::quote! { url = "/search", rate_limit = 10 });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/countries", cache = true }).unwrap();
+        assert_eq!(args.cache, Some(CacheConfig::new()));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/countries", cache = "max_age=60s" }).unwrap();
+        assert_eq!(args.cache.unwrap().max_age, Some(std::time::Duration::from_secs(60)));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/countries", cache = false }).unwrap();
+        assert!(args.cache.is_none());
+
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/countries", cache = "ttl=60s" }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/countries", cache = 60 }).is_err());
+    }
+
+    #[test]
+    fn test_parse_compress() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = gzip }).unwrap();
+        assert_eq!(args.compress, Some(CompressConfig::new(ContentEncoding::Gzip)));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = br(min_size = 4096) }).unwrap();
+        assert_eq!(args.compress, Some(CompressConfig::new(ContentEncoding::Brotli).min_size(4096)));
+
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = deflate }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = "gzip" }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = zstd(level = 3) }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/events", compress = zstd(min_size = "1kb") }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! {
+            url = "/upload", content_type = form_multipart, compress = gzip
+        }).is_err());
+    }
+
+    #[test]
+    fn test_parse_coalesce() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", coalesce = true }).unwrap();
+        assert_eq!(args.coalesce, Some(CoalesceConfig::new()));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", coalesce(headers = ["X-Tenant"]) }).unwrap();
+        assert_eq!(args.coalesce.unwrap().key_headers.last().unwrap(), "x-tenant");
+
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", coalesce = false }).unwrap().coalesce.is_none());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", coalesce = "yes" }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", coalesce(headers = ["bad header"]) }).is_err());
+    }
+
+    #[test]
+    fn test_parse_paginate() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = link_header }).unwrap();
+        assert_eq!(args.paginate, Some(Pagination::link_header()));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! {
+            url = "/users", paginate = cursor(param = "cursor", next = "/next_cursor", items = "/data")
+        }).unwrap();
+        assert_eq!(args.paginate, Some(Pagination::cursor("cursor", "/next_cursor").items("/data")));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = offset(param = "page") }).unwrap();
+        assert_eq!(args.paginate, Some(Pagination::offset("page", 1)));
+
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = offset(param = "page", start = 0) }).unwrap();
+        assert_eq!(args.paginate, Some(Pagination::offset("page", 0)));
+
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = cursor(param = "cursor") }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = offset(param = "page", next = "/next") }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = link_header(items = "data") }).is_err());
+        assert!(syn::parse2::<HandlerArgs>(quote::quote! { url = "/users", paginate = pages }).is_err());
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/reports", timeout = "30s" }).unwrap();
+        assert_eq!(args.timeout_ms, Some(30_000));
+
+        let result = syn::parse2::<HandlerArgs>(quote::quote! { url = "/reports", timeout = "forever" });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_download_to() {
+        let args = syn::parse2::<HandlerArgs>(quote::quote! { url = "/files/{id}", download_to = dest }).unwrap();
+        assert_eq!(args.download_to.unwrap(), "dest");
+
+        let result = syn::parse2::<HandlerArgs>(quote::quote! { url = "/files", download_to = "out.bin" });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_header_value() {
         let expr = parse_quote! { "Authorization: Bearer token" };
@@ -1,5 +1,12 @@
 pub mod handler;
 pub mod client;
+pub mod param;
+mod duration;
+mod rate_limit;
+mod auth;
+mod signer;
+mod tls;
 
-pub use handler::parse_handler_args;
-pub use client::parse_http_client_args;
\ No newline at end of file
+pub use handler::{parse_handler_args, parse_request_args};
+pub use client::parse_http_client_args;
+pub use param::parse_param_role;
\ No newline at end of file
@@ -0,0 +1,133 @@
+use syn::{Attribute, LitStr, Meta};
+use crate::types::{HttpMethod, ParamRole};
+
+/// 解析参数上的角色属性
+///
+/// 未标注角色属性时返回 `None`；同一参数上出现多个角色属性、
+/// 或属性参数不合法时返回编译错误。
+pub fn parse_param_role(attrs: &[Attribute]) -> syn::Result<Option<(ParamRole, &Attribute)>> {
+    let mut found: Option<(ParamRole, &Attribute)> = None;
+
+    for attr in attrs.iter().filter(|attr| ParamRole::is_role_attribute(attr)) {
+        let role = parse_role_attribute(attr)?;
+
+        if let Some((existing, _)) = &found {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!(
+                    "conflicting parameter roles: {} and {} cannot be used on the same parameter",
+                    existing.attribute(),
+                    role.attribute()
+                ),
+            ));
+        }
+
+        found = Some((role, attr));
+    }
+
+    Ok(found)
+}
+
+/// 解析单个角色属性
+fn parse_role_attribute(attr: &Attribute) -> syn::Result<ParamRole> {
+    let name = attr.path().get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+
+    match name.as_str() {
+        "path" => {
+            require_no_args(attr, "#[path]")?;
+            Ok(ParamRole::Path)
+        }
+        "body" => {
+            require_no_args(attr, "#[body]")?;
+            Ok(ParamRole::Body)
+        }
+        "query" => match &attr.meta {
+            Meta::Path(_) => Ok(ParamRole::Query(None)),
+            Meta::List(_) => {
+                let name: LitStr = attr.parse_args().map_err(|_| {
+                    syn::Error::new_spanned(attr, "#[query] expects a string literal name, e.g. #[query(\"pageSize\")]")
+                })?;
+                if name.value().is_empty() {
+                    return Err(syn::Error::new_spanned(name, "query parameter name must not be empty"));
+                }
+                Ok(ParamRole::Query(Some(name.value())))
+            }
+            Meta::NameValue(_) => Err(syn::Error::new_spanned(
+                attr,
+                "#[query] expects a string literal name, e.g. #[query(\"pageSize\")]",
+            )),
+        },
+        "header" => {
+            let name: LitStr = match &attr.meta {
+                Meta::List(_) => attr.parse_args().map_err(|_| {
+                    syn::Error::new_spanned(attr, "#[header] requires a header name, e.g. #[header(\"X-Tenant\")]")
+                })?,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "#[header] requires a header name, e.g. #[header(\"X-Tenant\")]",
+                    ));
+                }
+            };
+            if !HttpMethod::is_valid_token(&name.value()) {
+                return Err(syn::Error::new_spanned(name, "invalid header name"));
+            }
+            Ok(ParamRole::Header(name.value()))
+        }
+        _ => unreachable!("is_role_attribute guarantees a known role"),
+    }
+}
+
+/// 校验属性不带参数
+fn require_no_args(attr: &Attribute, display: &str) -> syn::Result<()> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(()),
+        _ => Err(syn::Error::new_spanned(attr, format!("{} does not take arguments", display))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn role_of(param: syn::FnArg) -> syn::Result<Option<ParamRole>> {
+        match param {
+            syn::FnArg::Typed(pat_type) => {
+                parse_param_role(&pat_type.attrs).map(|found| found.map(|(role, _)| role))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_roles() {
+        assert_eq!(role_of(parse_quote! { #[path] id: u32 }).unwrap(), Some(ParamRole::Path));
+        assert_eq!(role_of(parse_quote! { #[query] page: u32 }).unwrap(), Some(ParamRole::Query(None)));
+        assert_eq!(
+            role_of(parse_quote! { #[query("pageSize")] size: u32 }).unwrap(),
+            Some(ParamRole::Query(Some("pageSize".into())))
+        );
+        assert_eq!(
+            role_of(parse_quote! { #[header("X-Tenant")] tenant: String }).unwrap(),
+            Some(ParamRole::Header("X-Tenant".into()))
+        );
+        assert_eq!(role_of(parse_quote! { #[body] user: User }).unwrap(), Some(ParamRole::Body));
+        assert_eq!(role_of(parse_quote! { #[allow(unused)] id: u32 }).unwrap(), None);
+    }
+
+    #[test]
+    fn test_conflicting_roles() {
+        let error = role_of(parse_quote! { #[query] #[header("X-Id")] id: u32 }).unwrap_err();
+        assert!(error.to_string().contains("conflicting parameter roles"));
+    }
+
+    #[test]
+    fn test_invalid_role_arguments() {
+        assert!(role_of(parse_quote! { #[header] tenant: String }).is_err());
+        assert!(role_of(parse_quote! { #[header("Bad Header")] tenant: String }).is_err());
+        assert!(role_of(parse_quote! { #[query(123)] page: u32 }).is_err());
+        assert!(role_of(parse_quote! { #[body(json)] user: User }).is_err());
+        assert!(role_of(parse_quote! { #[path = "id"] id: u32 }).is_err());
+    }
+}
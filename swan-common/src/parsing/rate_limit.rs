@@ -0,0 +1,17 @@
+use crate::rate_limit::RateLimitConfig;
+
+/// 解析 `rate_limit = "10/s, burst=20"` 参数，客户端与方法宏共用
+pub(crate) fn parse_rate_limit_value(value: &syn::Expr) -> syn::Result<RateLimitConfig> {
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(lit),
+        ..
+    }) = value
+    else {
+        return Err(syn::Error::new_spanned(
+            value,
+            "rate_limit must be a string literal (e.g., \"10/s, burst=20\")",
+        ));
+    };
+
+    RateLimitConfig::parse(&lit.value()).map_err(|message| syn::Error::new_spanned(lit, message))
+}
@@ -0,0 +1,109 @@
+use super::auth::ProviderArgs;
+use crate::signing::SignerConfig;
+
+const SIGNER_USAGE: &str = "signer must be aws_sigv4(...) or hmac(...)";
+
+/// 解析 `signer = aws_sigv4(region = "us-east-1", service = "s3")` 等请求签名配置
+///
+/// 凭据的写法与 `auth` 相同：`name = "..."` 或 `name_env = "VAR"`。
+pub(crate) fn parse_signer_value(value: &syn::Expr) -> syn::Result<SignerConfig> {
+    let syn::Expr::Call(call) = value else {
+        return Err(syn::Error::new_spanned(value, SIGNER_USAGE));
+    };
+    let signer = match &*call.func {
+        syn::Expr::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    };
+
+    let mut args = ProviderArgs::parse(call)?;
+    let config = match signer.as_deref() {
+        Some("aws_sigv4") => {
+            let region = args.string("region");
+            let service = args.string("service");
+            let (Some(region), Some(service)) = (region, service) else {
+                return Err(syn::Error::new_spanned(value, "aws_sigv4 requires 'region' and 'service'"));
+            };
+            let access_key_id = args.optional_secret("access_key_id", "access_key_id_env")?;
+            let secret_access_key = args.optional_secret("secret_access_key", "secret_access_key_env")?;
+            let credentials = match (access_key_id, secret_access_key) {
+                (Some(access_key_id), Some(secret_access_key)) => Some((access_key_id, secret_access_key)),
+                (None, None) => None,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "aws_sigv4 requires both the access key id and the secret access key, or neither",
+                    ));
+                }
+            };
+            SignerConfig::AwsSigV4 {
+                region,
+                service,
+                credentials,
+                session_token: args.optional_secret("session_token", "session_token_env")?,
+            }
+        }
+        Some("hmac") => SignerConfig::Hmac {
+            secret: args.secret("secret", "secret_env")?,
+            key_id: args.string("key_id"),
+            signature_header: args.header_name("signature_header")?,
+            timestamp_header: args.header_name("timestamp_header")?,
+            key_id_header: args.header_name("key_id_header")?,
+            signed_headers: args.list("signed_headers").unwrap_or_default(),
+        },
+        _ => return Err(syn::Error::new_spanned(&call.func, SIGNER_USAGE)),
+    };
+
+    args.finish()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Secret;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_signer_value() {
+        let config = parse_signer_value(&parse_quote! { aws_sigv4(region = "us-east-1", service = "s3") }).unwrap();
+        assert_eq!(config, SignerConfig::AwsSigV4 {
+            region: "us-east-1".into(),
+            service: "s3".into(),
+            credentials: None,
+            session_token: None,
+        });
+
+        let config = parse_signer_value(&parse_quote! {
+            aws_sigv4(
+                region = "eu-west-1",
+                service = "execute-api",
+                access_key_id_env = "KEY_ID",
+                secret_access_key_env = "SECRET_KEY",
+            )
+        }).unwrap();
+        assert!(matches!(config, SignerConfig::AwsSigV4 { credentials: Some((Secret::Env(_), Secret::Env(_))), .. }));
+
+        let config = parse_signer_value(&parse_quote! {
+            hmac(secret_env = "SIGNING_SECRET", key_id = "svc-a", signed_headers = ["content-type"])
+        }).unwrap();
+        assert_eq!(config, SignerConfig::Hmac {
+            secret: Secret::env("SIGNING_SECRET"),
+            key_id: Some("svc-a".into()),
+            signature_header: None,
+            timestamp_header: None,
+            key_id_header: None,
+            signed_headers: vec!["content-type".into()],
+        });
+    }
+
+    #[test]
+    fn test_parse_signer_value_errors() {
+        assert!(parse_signer_value(&parse_quote! { "aws" }).is_err());
+        assert!(parse_signer_value(&parse_quote! { sigv2(region = "us-east-1") }).is_err());
+        assert!(parse_signer_value(&parse_quote! { aws_sigv4(region = "us-east-1") }).is_err());
+        assert!(parse_signer_value(&parse_quote! { aws_sigv4(region = "us-east-1", service = "s3", access_key_id = "AKID") }).is_err());
+        assert!(parse_signer_value(&parse_quote! { hmac(key_id = "svc-a") }).is_err());
+        assert!(parse_signer_value(&parse_quote! { hmac(secret = "s", signature_header = "bad header") }).is_err());
+        assert!(parse_signer_value(&parse_quote! { hmac(secret = "s", scopes = ["a"]) }).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use super::auth::ProviderArgs;
+use crate::tls::verifier::decode_pin;
+use crate::tls::{TlsConfig, TlsVersion};
+use quote::ToTokens;
+
+/// 解析 `tls(ca = "ca.pem", identity = "client.p12", identity_password_env = "VAR", ...)`
+///
+/// `ca` 可以是单个路径或路径列表；`pins` 为 `sha256/<base64>` 列表。
+pub(crate) fn parse_tls_value(meta_list: &syn::MetaList) -> syn::Result<TlsConfig> {
+    let call: syn::ExprCall = syn::parse2(meta_list.to_token_stream())?;
+    let mut args = ProviderArgs::parse(&call)?;
+
+    let mut config = TlsConfig::new();
+    config.ca = args.string("ca").map(|ca| vec![ca]).or_else(|| args.list("ca"))
+        .unwrap_or_default()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    config.identity = args.string("identity").map(Into::into);
+    config.identity_password = args.optional_secret("identity_password", "identity_password_env")?;
+    if config.identity_password.is_some() && config.identity.is_none() {
+        return Err(syn::Error::new_spanned(meta_list, "identity_password requires 'identity'"));
+    }
+    if let Some(version) = args.string("min_version") {
+        config.min_version = Some(TlsVersion::parse(&version).ok_or_else(|| {
+            syn::Error::new_spanned(meta_list, format!("Unsupported min_version '{}' (expected \"1.2\" or \"1.3\")", version))
+        })?);
+    }
+    config.danger_accept_invalid_certs = args.flag("danger_accept_invalid_certs").unwrap_or_default();
+    config.pins = args.list("pins").unwrap_or_default();
+    if let Some(pin) = config.pins.iter().find(|pin| decode_pin(pin).is_none()) {
+        return Err(syn::Error::new_spanned(meta_list, format!("Invalid pin '{}' (expected sha256/<base64 SHA-256 of the SPKI>)", pin)));
+    }
+
+    args.finish()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Secret;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_tls_value() {
+        let config = parse_tls_value(&parse_quote! {
+            tls(
+                ca = "certs/ca.pem",
+                identity = "certs/client.p12",
+                identity_password_env = "CLIENT_P12_PASSWORD",
+                min_version = "1.3",
+                danger_accept_invalid_certs = false,
+                pins = ["sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="],
+            )
+        }).unwrap();
+        assert_eq!(config, TlsConfig::new()
+            .ca("certs/ca.pem")
+            .identity("certs/client.p12")
+            .identity_password(Secret::env("CLIENT_P12_PASSWORD"))
+            .min_version(TlsVersion::Tls13)
+            .pin("sha256/47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="));
+
+        let config = parse_tls_value(&parse_quote! { tls(ca = ["a.pem", "b.pem"], danger_accept_invalid_certs = true) }).unwrap();
+        assert_eq!(config.ca.len(), 2);
+        assert!(config.danger_accept_invalid_certs);
+    }
+
+    #[test]
+    fn test_parse_tls_value_errors() {
+        assert!(parse_tls_value(&parse_quote! { tls(min_version = "1.1") }).is_err());
+        assert!(parse_tls_value(&parse_quote! { tls(identity_password = "secret") }).is_err());
+        assert!(parse_tls_value(&parse_quote! { tls(pins = ["md5/abc"]) }).is_err());
+        assert!(parse_tls_value(&parse_quote! { tls(ca = "ca.pem", verify = false) }).is_err());
+        assert!(parse_tls_value(&parse_quote! { tls(ca = 1) }).is_err());
+    }
+}
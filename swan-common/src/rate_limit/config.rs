@@ -0,0 +1,176 @@
+use crate::error::SwanError;
+use crate::types::RetryConfig;
+use std::time::Duration;
+
+/// 超出速率限制时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnExceed {
+    /// 异步等待令牌
+    #[default]
+    Wait,
+    /// 立即返回 [`SwanError::RateLimited`](crate::SwanError::RateLimited)
+    Error,
+}
+
+/// 令牌桶限流配置
+///
+/// 每 `per` 时间补充 `requests` 个令牌，桶容量为 `burst`，每次请求（含重试）消耗一个令牌。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// 每个周期补充的令牌数
+    pub requests: u32,
+    /// 补充周期
+    pub per: Duration,
+    /// 桶容量，即允许的突发请求数
+    pub burst: u32,
+    /// 令牌不足时的处理方式
+    pub on_exceed: OnExceed,
+}
+
+impl RateLimitConfig {
+    /// 每 `per` 时间允许 `requests` 个请求，突发容量默认等于 `requests`
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self {
+            requests,
+            per,
+            burst: requests,
+            on_exceed: OnExceed::Wait,
+        }
+    }
+
+    /// 设置突发容量
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// 设置令牌不足时的处理方式
+    pub fn on_exceed(mut self, on_exceed: OnExceed) -> Self {
+        self.on_exceed = on_exceed;
+        self
+    }
+
+    /// 检查配置是否有效：`requests`、`burst` 与 `per` 均须大于零
+    ///
+    /// 生成的构建器在 `build()` 时调用，无效配置以 [`SwanError::Request`] 返回。
+    pub fn validate(&self) -> Result<(), SwanError> {
+        if self.requests == 0 {
+            return Err(SwanError::request("Rate limit requests must be greater than zero"));
+        }
+        if self.burst == 0 {
+            return Err(SwanError::request("Rate limit burst must be greater than zero"));
+        }
+        if self.per.is_zero() {
+            return Err(SwanError::request("Rate limit period must be greater than zero"));
+        }
+        Ok(())
+    }
+
+    /// 解析 `"10/s, burst=20, on_exceed=error"` 形式的配置
+    ///
+    /// 速率周期支持 `s`、`m`、`h` 以及时长（如 `5/100ms`）。
+    pub fn parse(config: &str) -> Result<Self, String> {
+        let mut params = config.split(',').map(str::trim).filter(|param| !param.is_empty());
+
+        let rate = params.next().ok_or_else(|| "rate_limit must not be empty".to_string())?;
+        let mut result = Self::parse_rate(rate)?;
+
+        for param in params {
+            let (key, value) = param.split_once('=')
+                .ok_or_else(|| format!("Expected key=value in rate_limit config: {}", param))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "burst" => {
+                    result.burst = value.parse()
+                        .ok()
+                        .filter(|burst| *burst > 0)
+                        .ok_or_else(|| format!("Invalid burst: {}", value))?;
+                }
+                "on_exceed" => {
+                    result.on_exceed = match value {
+                        "wait" => OnExceed::Wait,
+                        "error" => OnExceed::Error,
+                        _ => return Err(format!("Invalid on_exceed: {} (expected wait or error)", value)),
+                    };
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown rate_limit parameter: {} (expected burst or on_exceed)",
+                        key
+                    ));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_rate(rate: &str) -> Result<Self, String> {
+        let (requests, per) = rate.split_once('/')
+            .ok_or_else(|| format!("Invalid rate (expected e.g. 10/s): {}", rate))?;
+
+        let requests: u32 = requests.trim().parse()
+            .ok()
+            .filter(|requests| *requests > 0)
+            .ok_or_else(|| format!("Invalid request count in rate: {}", rate))?;
+
+        let per = match per.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            period => match RetryConfig::parse_duration(period)? {
+                0 => return Err(format!("Rate period must be greater than zero: {}", rate)),
+                millis => Duration::from_millis(millis),
+            },
+        };
+
+        Ok(Self::new(requests, per))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_and_burst() {
+        let config = RateLimitConfig::parse("10/s, burst=20").unwrap();
+
+        assert_eq!(config, RateLimitConfig::new(10, Duration::from_secs(1)).burst(20));
+    }
+
+    #[test]
+    fn test_parse_periods() {
+        assert_eq!(RateLimitConfig::parse("100/m").unwrap().per, Duration::from_secs(60));
+        assert_eq!(RateLimitConfig::parse("5/100ms").unwrap().per, Duration::from_millis(100));
+        assert_eq!(RateLimitConfig::parse("1000/h").unwrap().burst, 1000);
+    }
+
+    #[test]
+    fn test_parse_on_exceed() {
+        let config = RateLimitConfig::parse("2/s, on_exceed = error").unwrap();
+        assert_eq!(config.on_exceed, OnExceed::Error);
+        assert_eq!(RateLimitConfig::parse("2/s").unwrap().on_exceed, OnExceed::Wait);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(RateLimitConfig::new(10, Duration::from_secs(1)).validate().is_ok());
+
+        for config in [
+            RateLimitConfig::new(0, Duration::from_secs(1)),
+            RateLimitConfig::new(10, Duration::from_secs(1)).burst(0),
+            RateLimitConfig::new(10, Duration::ZERO),
+        ] {
+            assert_eq!(config.validate().unwrap_err().kind(), "request", "{:?}", config);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_config() {
+        for config in ["", "10", "0/s", "10/week", "10/0ms", "10/s, burst=0", "10/s, on_exceed=drop", "10/s, max=3"] {
+            assert!(RateLimitConfig::parse(config).is_err(), "{}", config);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use super::{OnExceed, RateLimitConfig};
+use crate::error::SwanError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Bucket {
+    /// 当前令牌数，等待中的请求预占令牌后可能为负
+    tokens: f64,
+    updated: Instant,
+}
+
+/// 令牌桶限流器
+///
+/// 等待模式下令牌不足的请求预占一个令牌并按到达顺序等待，
+/// 因此并发请求的发送时间严格受速率约束。
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// 以指定配置创建限流器，初始时令牌桶是满的
+    ///
+    /// 配置应先通过 [`RateLimitConfig::validate`] 检查，生成的构建器会在 `build()` 时完成。
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(config.burst),
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// 限流配置
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// 获取一个令牌
+    ///
+    /// 令牌不足时按 `on_exceed` 异步等待，或返回 [`SwanError::RateLimited`]。
+    pub async fn acquire(&self) -> Result<(), SwanError> {
+        let wait = self.reserve_at(Instant::now())?;
+        if !wait.is_zero() {
+            log::debug!("Rate limit reached, waiting {:?} for a permit", wait);
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// 预占一个令牌，返回需要等待的时间
+    fn reserve_at(&self, now: Instant) -> Result<Duration, SwanError> {
+        let mut bucket = self.lock();
+        let per_second = f64::from(self.config.requests) / self.config.per.as_secs_f64();
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(f64::from(self.config.burst));
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(Duration::ZERO);
+        }
+
+        // 未经 validate 的配置（如 requests 为 0）会得到无穷大的等待时间
+        let wait = Duration::try_from_secs_f64((1.0 - bucket.tokens) / per_second).unwrap_or(Duration::MAX);
+        match self.config.on_exceed {
+            OnExceed::Error => Err(SwanError::RateLimited { retry_after: wait }),
+            OnExceed::Wait => {
+                bucket.tokens -= 1.0;
+                Ok(wait)
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 客户端持有的方法级限流器，每个方法一个令牌桶
+///
+/// 限流器属于客户端实例，不同客户端实例之间互不影响。
+#[derive(Debug, Default)]
+pub struct MethodRateLimiters {
+    limiters: Mutex<HashMap<&'static str, Arc<RateLimiter>>>,
+}
+
+impl MethodRateLimiters {
+    /// 获取方法的限流器，首次调用时按 `config` 创建
+    pub fn get_or_create(&self, method: &'static str, config: RateLimitConfig) -> Arc<RateLimiter> {
+        let mut limiters = self.limiters.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        limiters.entry(method)
+            .or_insert_with(|| Arc::new(RateLimiter::new(config)))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(config: RateLimitConfig) -> (RateLimiter, Instant) {
+        let limiter = RateLimiter::new(config);
+        let start = limiter.lock().updated;
+        (limiter, start)
+    }
+
+    #[test]
+    fn test_burst_then_wait_in_order() {
+        let (limiter, start) = limiter(RateLimitConfig::new(10, Duration::from_secs(1)).burst(2));
+
+        assert_eq!(limiter.reserve_at(start).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(start).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(start).unwrap(), Duration::from_millis(100));
+        assert_eq!(limiter.reserve_at(start).unwrap(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_tokens_refill_up_to_burst() {
+        let (limiter, start) = limiter(RateLimitConfig::new(10, Duration::from_secs(1)).burst(2));
+        limiter.reserve_at(start).unwrap();
+        limiter.reserve_at(start).unwrap();
+
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.reserve_at(later).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve_at(later).unwrap(), Duration::ZERO);
+        assert!(limiter.reserve_at(later).unwrap() > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_on_exceed_error_does_not_reserve() {
+        let config = RateLimitConfig::new(1, Duration::from_secs(1)).on_exceed(OnExceed::Error);
+        let (limiter, start) = limiter(config);
+
+        assert_eq!(limiter.reserve_at(start).unwrap(), Duration::ZERO);
+        let error = limiter.reserve_at(start).unwrap_err();
+        assert!(error.is_rate_limited());
+        assert!(matches!(error, SwanError::RateLimited { retry_after } if retry_after == Duration::from_secs(1)));
+
+        assert_eq!(limiter.reserve_at(start + Duration::from_secs(1)).unwrap(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_method_limiters_are_reused() {
+        let limiters = MethodRateLimiters::default();
+        let config = RateLimitConfig::new(1, Duration::from_secs(1));
+
+        let first = limiters.get_or_create("search", config);
+        let second = limiters.get_or_create("search", config);
+        let other = limiters.get_or_create("list", config);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_permit() {
+        let limiter = RateLimiter::new(RateLimitConfig::new(1, Duration::from_millis(100)));
+
+        let started = Instant::now();
+        limiter.acquire().await.unwrap();
+        limiter.acquire().await.unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(90));
+    }
+}
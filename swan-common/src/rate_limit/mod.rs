@@ -0,0 +1,5 @@
+pub mod config;
+pub mod limiter;
+
+pub use config::{OnExceed, RateLimitConfig};
+pub use limiter::{MethodRateLimiters, RateLimiter};
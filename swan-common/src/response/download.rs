@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use crate::error::SwanError;
+
+/// 将响应体逐块写入文件，返回写入的字节数
+///
+/// 由 `download_to = path_arg` 方法调用。响应体先写入同一目录下的临时文件，
+/// 完整接收后再重命名为目标文件：下载中途失败时删除临时文件，已有的目标文件保持不变。
+/// 响应体不会整体读入内存。
+pub async fn download_to_file(
+    response: reqwest::Response,
+    path: impl AsRef<Path>,
+) -> Result<u64, SwanError> {
+    let path = path.as_ref();
+    let temp = temp_path(path);
+
+    let written = match write_body(response, &temp).await {
+        Ok(written) => written,
+        Err(error) => {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(error);
+        }
+    };
+    if let Err(error) = tokio::fs::rename(&temp, path).await {
+        let _ = tokio::fs::remove_file(&temp).await;
+        return Err(error.into());
+    }
+    Ok(written)
+}
+
+async fn write_body(mut response: reqwest::Response, temp: &Path) -> Result<u64, SwanError> {
+    let mut file = tokio::fs::File::create(temp).await?;
+    let mut written = 0u64;
+
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+
+    file.sync_all().await?;
+    Ok(written)
+}
+
+/// 与目标文件同目录的临时文件，保证重命名不跨文件系统
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map(|name| name.to_os_string()).unwrap_or_default();
+    name.push(format!(".{}.tmp", fastrand::u64(..)));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_path_stays_in_target_directory() {
+        let temp = temp_path(Path::new("downloads/report.csv"));
+
+        assert_eq!(temp.parent(), Some(Path::new("downloads")));
+        let name = temp.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("report.csv.") && name.ends_with(".tmp"), "{}", name);
+    }
+}
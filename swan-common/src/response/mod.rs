@@ -0,0 +1,7 @@
+pub mod wrapper;
+pub mod stream;
+pub mod download;
+
+pub use wrapper::Response;
+pub use stream::{ByteStream, StreamItem};
+pub use download::download_to_file;
@@ -0,0 +1,113 @@
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use crate::error::SwanError;
+
+/// 流式响应体
+///
+/// 方法返回 `Result<swan_common::ByteStream, E>` 时，响应体不会被整体读入内存，
+/// 而是按块产出 `Result<Bytes, SwanError>`，适合下载大文件。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[get(url = "/artifacts/{id}")]
+/// async fn artifact(&self, id: u64) -> anyhow::Result<swan_common::ByteStream> {}
+///
+/// let mut stream = client.artifact(1).await?;
+/// while let Some(chunk) = stream.next().await {
+///     let chunk = chunk?;
+///     // ...
+/// }
+/// ```
+pub struct ByteStream {
+    inner: BoxStream<'static, Result<Bytes, SwanError>>,
+}
+
+impl ByteStream {
+    /// 从响应构建字节流
+    pub fn from_response(response: reqwest::Response) -> Self {
+        Self {
+            inner: response.bytes_stream().map(|chunk| chunk.map_err(SwanError::from)).boxed(),
+        }
+    }
+
+    /// 将每个数据块转换为指定的流元素类型
+    ///
+    /// 用于 `impl Stream<Item = Result<Bytes, E>>` 返回类型，错误通过 `E: From<SwanError>` 转换。
+    pub fn items<T>(self) -> impl Stream<Item = T> + Send + 'static
+    where
+        T: StreamItem + 'static,
+    {
+        self.inner.map(T::from_chunk)
+    }
+}
+
+impl Stream for ByteStream {
+    type Item = Result<Bytes, SwanError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl std::fmt::Debug for ByteStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ByteStream").finish_non_exhaustive()
+    }
+}
+
+/// 流式响应体的元素类型
+///
+/// 为所有 `Result<Bytes, E>`（`E: From<SwanError>`）实现，
+/// 因此 `anyhow::Result<Bytes>` 与 `Result<Bytes, SwanError>` 均可作为流元素。
+pub trait StreamItem {
+    /// 由数据块读取结果构建流元素
+    fn from_chunk(chunk: Result<Bytes, SwanError>) -> Self;
+}
+
+impl<E> StreamItem for Result<Bytes, E>
+where
+    E: From<SwanError>,
+{
+    fn from_chunk(chunk: Result<Bytes, SwanError>) -> Self {
+        chunk.map_err(E::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn byte_stream(chunks: Vec<Result<Bytes, SwanError>>) -> ByteStream {
+        ByteStream { inner: stream::iter(chunks).boxed() }
+    }
+
+    #[tokio::test]
+    async fn test_items_convert_errors() {
+        let stream = byte_stream(vec![
+            Ok(Bytes::from_static(b"ab")),
+            Err(SwanError::request("broken pipe")),
+        ]);
+
+        let items: Vec<anyhow::Result<Bytes>> = stream.items().collect().await;
+
+        assert_eq!(items[0].as_ref().unwrap(), &Bytes::from_static(b"ab"));
+        assert!(items[1].as_ref().unwrap_err().to_string().contains("broken pipe"));
+    }
+
+    #[tokio::test]
+    async fn test_byte_stream_yields_chunks() {
+        let stream = byte_stream(vec![Ok(Bytes::from_static(b"a")), Ok(Bytes::from_static(b"b"))]);
+
+        let chunks: Vec<Bytes> = stream.map(Result::unwrap).collect().await;
+
+        assert_eq!(chunks, vec![Bytes::from_static(b"a"), Bytes::from_static(b"b")]);
+    }
+}
@@ -0,0 +1,100 @@
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+/// 带响应元信息的返回值
+///
+/// 方法返回 `Result<swan_common::Response<T>, E>` 时，除解码后的响应体外，
+/// 还会保留状态码、响应头、最终 URL、耗时与尝试次数。响应体 `T` 的解码规则
+/// 与直接返回 `T` 时相同。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// #[get(url = "/users")]
+/// async fn list_users(&self) -> anyhow::Result<swan_common::Response<Vec<User>>> {}
+///
+/// let response = client.list_users().await?;
+/// let total = response.header("X-Total-Count");
+/// let users = response.into_body();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    /// 响应状态码
+    pub status: StatusCode,
+    /// 响应头
+    pub headers: HeaderMap,
+    /// 最终请求的 URL（重定向后）
+    pub url: reqwest::Url,
+    /// 从发出第一次请求到读取完响应体的耗时（包含重试等待）
+    pub elapsed: Duration,
+    /// 实际发送请求的次数（无重试时为 1，命中新鲜缓存时为 0）
+    pub attempts: u32,
+    /// 解码后的响应体
+    pub body: T,
+}
+
+impl<T> Response<T> {
+    /// 按名称获取响应头的字符串值
+    ///
+    /// 响应头不存在或不是合法的可见 ASCII 时返回 `None`。
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// 取出响应体
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// 转换响应体，保留其余元信息
+    pub fn map<U, F>(self, f: F) -> Response<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Response {
+            status: self.status,
+            headers: self.headers,
+            url: self.url,
+            elapsed: self.elapsed,
+            attempts: self.attempts,
+            body: f(self.body),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> Response<&'static str> {
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Total-Count", "42".parse().unwrap());
+
+        Response {
+            status: StatusCode::OK,
+            headers,
+            url: "https://api.example.com/users".parse().unwrap(),
+            elapsed: Duration::from_millis(5),
+            attempts: 1,
+            body: "[]",
+        }
+    }
+
+    #[test]
+    fn test_header_lookup() {
+        let response = response();
+
+        assert_eq!(response.header("x-total-count"), Some("42"));
+        assert_eq!(response.header("etag"), None);
+    }
+
+    #[test]
+    fn test_map_keeps_metadata() {
+        let response = response().map(|body| body.len());
+
+        assert_eq!(response.body, 2);
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(response.header("X-Total-Count"), Some("42"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod retry_after;
+pub mod predicate;
+
+pub use retry_after::{retry_after, parse_retry_after};
+pub use predicate::{RetryOutcome, SwanRetryPredicate};
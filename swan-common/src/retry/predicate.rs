@@ -0,0 +1,109 @@
+use crate::error::SwanError;
+
+/// 一次请求尝试的结果，交给 [`SwanRetryPredicate`] 判断是否重试
+#[derive(Debug)]
+pub enum RetryOutcome<'a> {
+    /// 收到了响应（任意状态码），可读取状态码、响应头与 URL
+    Response(&'a reqwest::Response),
+    /// 请求失败
+    Error(&'a SwanError),
+}
+
+impl RetryOutcome<'_> {
+    /// 响应状态码；请求失败时为 `None`
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            RetryOutcome::Response(response) => Some(response.status()),
+            RetryOutcome::Error(_) => None,
+        }
+    }
+
+    /// 读取响应头，值不是合法 UTF-8 时返回 `None`
+    pub fn header(&self, name: &str) -> Option<&str> {
+        match self {
+            RetryOutcome::Response(response) => response.headers().get(name)?.to_str().ok(),
+            RetryOutcome::Error(_) => None,
+        }
+    }
+
+    /// 请求失败时的错误
+    pub fn error(&self) -> Option<&SwanError> {
+        match self {
+            RetryOutcome::Response(_) => None,
+            RetryOutcome::Error(error) => Some(error),
+        }
+    }
+}
+
+/// 自定义重试条件
+///
+/// 通过 `retry_predicate = MyPredicate` 挂到带 `retry` 的方法上，类型需实现 `Default`，
+/// 实例由客户端缓存复用。返回 `Some(true)` / `Some(false)` 时直接决定是否重试，
+/// 返回 `None` 时沿用 `on_status` / `on_error` 的判断。
+/// 最大重试次数与幂等性检查仍然生效。
+///
+/// ```rust,ignore
+/// #[derive(Default)]
+/// struct RetryOnLockConflict;
+///
+/// impl SwanRetryPredicate for RetryOnLockConflict {
+///     fn should_retry(&self, outcome: &RetryOutcome<'_>) -> Option<bool> {
+///         if outcome.status()? == 409 {
+///             return Some(outcome.header("X-Error-Code") == Some("LOCK_CONFLICT"));
+///         }
+///         None
+///     }
+/// }
+/// ```
+pub trait SwanRetryPredicate: Send + Sync {
+    /// 判断本次尝试后是否重试
+    fn should_retry(&self, outcome: &RetryOutcome<'_>) -> Option<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RetryOnLockConflict;
+
+    impl SwanRetryPredicate for RetryOnLockConflict {
+        fn should_retry(&self, outcome: &RetryOutcome<'_>) -> Option<bool> {
+            if outcome.status()? == 409 {
+                return Some(outcome.header("X-Error-Code") == Some("LOCK_CONFLICT"));
+            }
+            None
+        }
+    }
+
+    fn response(status: u16, code: Option<&str>) -> reqwest::Response {
+        let mut builder = http::Response::builder().status(status);
+        if let Some(code) = code {
+            builder = builder.header("X-Error-Code", code);
+        }
+        reqwest::Response::from(builder.body(Vec::<u8>::new()).unwrap())
+    }
+
+    #[test]
+    fn test_predicate_inspects_status_and_headers() {
+        let predicate = RetryOnLockConflict;
+
+        let conflict = response(409, Some("LOCK_CONFLICT"));
+        let other_conflict = response(409, Some("DUPLICATE"));
+        let unavailable = response(503, None);
+
+        assert_eq!(predicate.should_retry(&RetryOutcome::Response(&conflict)), Some(true));
+        assert_eq!(predicate.should_retry(&RetryOutcome::Response(&other_conflict)), Some(false));
+        assert_eq!(predicate.should_retry(&RetryOutcome::Response(&unavailable)), None);
+    }
+
+    #[test]
+    fn test_error_outcome() {
+        let error = SwanError::request("boom");
+        let outcome = RetryOutcome::Error(&error);
+
+        assert_eq!(outcome.status(), None);
+        assert_eq!(outcome.header("X-Error-Code"), None);
+        assert!(outcome.error().is_some());
+        assert_eq!(RetryOnLockConflict.should_retry(&outcome), None);
+    }
+}
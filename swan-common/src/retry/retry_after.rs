@@ -0,0 +1,137 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 读取响应头中的 `Retry-After`，返回距当前时刻应等待的时长
+///
+/// 响应头缺失或无法解析时返回 `None`，由调用方回退到退避策略。
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, SystemTime::now())
+}
+
+/// 解析 `Retry-After` 的值（RFC 9110 §10.2.3）
+///
+/// 支持 `delay-seconds`（如 `120`）与 `HTTP-date`（如 `Sun, 06 Nov 1994 08:49:37 GMT`）两种形式，
+/// 已过去的时间点按零延迟处理。
+pub fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        return value.parse().ok().map(Duration::from_secs);
+    }
+
+    let at = parse_http_date(value)?;
+    Some(at.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// 解析 HTTP-date：IMF-fixdate，以及已废弃但仍需接受的 RFC 850 与 asctime 格式
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+
+    let (day, month, year, time) = match parts.as_slice() {
+        // IMF-fixdate: Sun, 06 Nov 1994 08:49:37 GMT
+        [weekday, day, month, year, time, "GMT"] if weekday.ends_with(',') => {
+            (day.parse().ok()?, *month, year.parse().ok()?, *time)
+        }
+        // RFC 850: Sunday, 06-Nov-94 08:49:37 GMT
+        [weekday, date, time, "GMT"] if weekday.ends_with(',') => {
+            let mut fields = date.split('-');
+            let day = fields.next()?.parse().ok()?;
+            let month = fields.next()?;
+            let year: i64 = fields.next()?.parse().ok()?;
+            // RFC 9110：两位年份若看起来超过未来 50 年，按上一个世纪解释
+            let year = if year < 50 { 2000 + year } else if year < 100 { 1900 + year } else { year };
+            (day, month, year, *time)
+        }
+        // asctime: Sun Nov  6 08:49:37 1994
+        [_, month, day, time, year] => (day.parse().ok()?, *month, year.parse().ok()?, *time),
+        _ => return None,
+    };
+
+    let month = MONTHS.iter().position(|m| *m == month)? as i64 + 1;
+    let mut clock = time.split(':').map(|field| field.parse::<i64>().ok());
+    let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+
+    if clock.next().is_some()
+        || !(1..=31).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0..=60).contains(&second)
+    {
+        return None;
+    }
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(u64::try_from(seconds).ok()?))
+}
+
+/// 公历日期到 1970-01-01 起的天数（Howard Hinnant 的 days_from_civil 算法）
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    /// Sun, 06 Nov 1994 08:49:37 GMT
+    const EXAMPLE: u64 = 784_111_777;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_parse_delay_seconds() {
+        assert_eq!(parse_retry_after("120", at(0)), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 0 ", at(0)), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("-1", at(0)), None);
+        assert_eq!(parse_retry_after("1.5", at(0)), None);
+        assert_eq!(parse_retry_after("", at(0)), None);
+    }
+
+    #[test]
+    fn test_parse_http_date_formats() {
+        let now = at(EXAMPLE - 30);
+        let expected = Some(Duration::from_secs(30));
+
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT", now), expected);
+        assert_eq!(parse_retry_after("Sunday, 06-Nov-94 08:49:37 GMT", now), expected);
+        assert_eq!(parse_retry_after("Sun Nov  6 08:49:37 1994", now), expected);
+    }
+
+    #[test]
+    fn test_past_http_date_is_zero() {
+        let now = at(EXAMPLE + 60);
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:49:37 GMT", now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_invalid_http_date() {
+        let now = at(0);
+        assert_eq!(parse_retry_after("Sun, 06 Foo 1994 08:49:37 GMT", now), None);
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 25:49:37 GMT", now), None);
+        assert_eq!(parse_retry_after("Sun, 06 Nov 1994 08:49:37 PST", now), None);
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_retry_after_reads_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(3)));
+    }
+}
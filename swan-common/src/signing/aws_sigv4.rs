@@ -0,0 +1,270 @@
+use super::signer::{hmac_sha256, sha256_hex, utc_timestamp};
+use super::{SigningRequest, SwanSigner};
+use crate::auth::Secret;
+use crate::error::SwanError;
+use async_trait::async_trait;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use reqwest::header::{AUTHORIZATION, HeaderName, HeaderValue};
+use std::time::SystemTime;
+
+/// SigV4 要求编码 unreserved 之外的全部字符
+const AWS_UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// 路径中保留 `/`
+const AWS_PATH: &AsciiSet = &AWS_UNRESERVED.remove(b'/');
+
+/// 不参与签名的请求头：由签名本身生成，或可能被代理改写
+const UNSIGNED_HEADERS: [&str; 6] = ["authorization", "user-agent", "expect", "x-amzn-trace-id", "traceparent", "tracestate"];
+
+const X_AMZ_DATE: HeaderName = HeaderName::from_static("x-amz-date");
+const X_AMZ_SECURITY_TOKEN: HeaderName = HeaderName::from_static("x-amz-security-token");
+const X_AMZ_CONTENT_SHA256: HeaderName = HeaderName::from_static("x-amz-content-sha256");
+
+/// AWS Signature Version 4
+///
+/// 默认从 `AWS_ACCESS_KEY_ID`、`AWS_SECRET_ACCESS_KEY` 与可选的 `AWS_SESSION_TOKEN` 读取凭据。
+/// 除 `Authorization`、`User-Agent` 等少数请求头外，请求头与 `host` 全部参与签名。
+/// `service` 为 `s3` 时按 S3 规则只编码一次路径，并添加 `x-amz-content-sha256`；
+/// 流式请求体以 `UNSIGNED-PAYLOAD` 签名。
+#[derive(Debug, Clone)]
+pub struct AwsSigV4Signer {
+    region: String,
+    service: String,
+    access_key_id: Secret,
+    secret_access_key: Secret,
+    session_token: Option<Secret>,
+    /// 未显式设置凭据，环境变量中存在 `AWS_SESSION_TOKEN` 时一并使用
+    default_credentials: bool,
+}
+
+impl AwsSigV4Signer {
+    pub fn new(region: impl Into<String>, service: impl Into<String>) -> Self {
+        Self {
+            region: region.into(),
+            service: service.into(),
+            access_key_id: Secret::env("AWS_ACCESS_KEY_ID"),
+            secret_access_key: Secret::env("AWS_SECRET_ACCESS_KEY"),
+            session_token: None,
+            default_credentials: true,
+        }
+    }
+
+    /// 设置访问密钥
+    pub fn credentials(mut self, access_key_id: impl Into<Secret>, secret_access_key: impl Into<Secret>) -> Self {
+        self.access_key_id = access_key_id.into();
+        self.secret_access_key = secret_access_key.into();
+        self.default_credentials = false;
+        self
+    }
+
+    /// 设置临时凭据的会话令牌
+    pub fn session_token(mut self, session_token: impl Into<Secret>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    fn sign_at(&self, request: &mut SigningRequest<'_>, time: SystemTime) -> Result<(), SwanError> {
+        let access_key_id = self.access_key_id.resolve()?;
+        let secret_access_key = self.secret_access_key.resolve()?;
+        let session_token = match &self.session_token {
+            Some(token) => Some(token.resolve()?),
+            None if self.default_credentials => std::env::var("AWS_SESSION_TOKEN").ok(),
+            None => None,
+        };
+        let is_s3 = self.service == "s3";
+
+        let (date, timestamp) = utc_timestamp(time);
+        let payload_hash = match request.headers().get(&X_AMZ_CONTENT_SHA256) {
+            Some(value) => value.to_str().map_err(SwanError::auth)?.to_string(),
+            None => request.body().map(sha256_hex).unwrap_or_else(|| "UNSIGNED-PAYLOAD".to_string()),
+        };
+
+        let headers = request.headers_mut();
+        headers.remove(AUTHORIZATION);
+        headers.insert(X_AMZ_DATE, header_value(&timestamp)?);
+        if let Some(token) = session_token {
+            headers.insert(X_AMZ_SECURITY_TOKEN, header_value(&token)?);
+        }
+        if is_s3 {
+            headers.insert(X_AMZ_CONTENT_SHA256, header_value(&payload_hash)?);
+        }
+
+        let (canonical_headers, signed_headers) = canonical_headers(request)?;
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            request.method(),
+            canonical_uri(request.url(), is_s3),
+            canonical_query(request.url()),
+            canonical_headers,
+            signed_headers,
+            payload_hash,
+        );
+
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", timestamp, scope, sha256_hex(canonical_request.as_bytes()));
+
+        let mut key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date.as_bytes());
+        for part in [self.region.as_str(), self.service.as_str(), "aws4_request"] {
+            key = hmac_sha256(&key, part.as_bytes());
+        }
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            access_key_id, scope, signed_headers, signature,
+        );
+        let mut authorization = header_value(&authorization)?;
+        authorization.set_sensitive(true);
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SwanSigner for AwsSigV4Signer {
+    async fn sign(&self, request: &mut SigningRequest<'_>) -> Result<(), SwanError> {
+        self.sign_at(request, SystemTime::now())
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, SwanError> {
+    HeaderValue::from_str(value).map_err(|_| SwanError::auth("Signing value contains characters not allowed in a header"))
+}
+
+/// 路径先解码再按 SigV4 规则编码；S3 之外的服务再编码一次
+fn canonical_uri(url: &reqwest::Url, is_s3: bool) -> String {
+    let path = if url.path().is_empty() { "/" } else { url.path() };
+    let decoded = percent_decode_str(path).decode_utf8_lossy();
+    let encoded = utf8_percent_encode(&decoded, AWS_PATH).to_string();
+    if is_s3 {
+        encoded
+    } else {
+        utf8_percent_encode(&encoded, AWS_PATH).to_string()
+    }
+}
+
+/// 按名称、取值排序并重新编码的查询参数
+fn canonical_query(url: &reqwest::Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            (
+                utf8_percent_encode(&name, AWS_UNRESERVED).to_string(),
+                utf8_percent_encode(&value, AWS_UNRESERVED).to_string(),
+            )
+        })
+        .collect();
+    pairs.sort();
+    pairs.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<_>>().join("&")
+}
+
+/// 返回 `(规范请求头, 签名请求头列表)`
+fn canonical_headers(request: &SigningRequest<'_>) -> Result<(String, String), SwanError> {
+    let url = request.url();
+    let host = match request.headers().get(reqwest::header::HOST) {
+        Some(host) => host.to_str().map_err(SwanError::auth)?.to_string(),
+        None => {
+            let host = url.host_str().ok_or_else(|| SwanError::auth("Cannot sign a request without a host"))?;
+            match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            }
+        }
+    };
+
+    let mut headers = vec![("host".to_string(), host)];
+    for name in request.headers().keys() {
+        if name == reqwest::header::HOST || UNSIGNED_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        let values = request
+            .headers()
+            .get_all(name)
+            .iter()
+            .map(|value| value.to_str().map(|value| value.split_whitespace().collect::<Vec<_>>().join(" ")))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(SwanError::auth)?;
+        headers.push((name.as_str().to_string(), values.join(",")));
+    }
+    headers.sort();
+
+    let canonical = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value)).collect();
+    let signed = headers.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(";");
+    Ok((canonical, signed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// AWS SigV4 测试套件使用的凭据与时间（2015-08-30T12:36:00Z）
+    fn signer(service: &str) -> AwsSigV4Signer {
+        AwsSigV4Signer::new("us-east-1", service)
+            .credentials("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+    }
+
+    fn sign(signer: &AwsSigV4Signer, request: &mut reqwest::Request) {
+        let time = UNIX_EPOCH + Duration::from_secs(1_440_938_160);
+        signer.sign_at(&mut SigningRequest::new(request), time).unwrap();
+    }
+
+    #[test]
+    fn test_get_vanilla() {
+        let mut request = reqwest::Request::new(reqwest::Method::GET, "https://example.amazonaws.com/".parse().unwrap());
+        sign(&signer("service"), &mut request);
+
+        assert_eq!(request.headers().get("x-amz-date").unwrap(), "20150830T123600Z");
+        assert_eq!(
+            request.headers().get(AUTHORIZATION).unwrap(),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31",
+        );
+    }
+
+    #[test]
+    fn test_canonical_query_and_uri() {
+        let url: reqwest::Url = "https://example.amazonaws.com/a%20b/c?b=2&a=x%20y&a=1".parse().unwrap();
+        assert_eq!(canonical_query(&url), "a=1&a=x%20y&b=2");
+        assert_eq!(canonical_uri(&url, true), "/a%20b/c");
+        assert_eq!(canonical_uri(&url, false), "/a%2520b/c");
+    }
+
+    #[test]
+    fn test_s3_signs_payload_hash_and_resigning_replaces_headers() {
+        let mut request = reqwest::Request::new(reqwest::Method::PUT, "https://bucket.s3.amazonaws.com/key".parse().unwrap());
+        *request.body_mut() = Some(reqwest::Body::from("hello"));
+        let signer = signer("s3").session_token("session");
+        sign(&signer, &mut request);
+
+        assert_eq!(
+            request.headers().get("x-amz-content-sha256").unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        );
+        assert_eq!(request.headers().get("x-amz-security-token").unwrap(), "session");
+        let authorization = request.headers().get(AUTHORIZATION).unwrap().clone();
+        assert!(authorization.is_sensitive());
+        assert!(authorization.to_str().unwrap()
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token,"));
+
+        // 再次签名（如重试）覆盖上一次的签名
+        sign(&signer, &mut request);
+        assert_eq!(request.headers().get_all(AUTHORIZATION).iter().count(), 1);
+        assert_eq!(request.headers().get(AUTHORIZATION).unwrap(), authorization);
+    }
+
+    #[test]
+    fn test_missing_credentials() {
+        let mut request = reqwest::Request::new(reqwest::Method::GET, "https://example.amazonaws.com/".parse().unwrap());
+        let signer = AwsSigV4Signer::new("us-east-1", "service")
+            .credentials(Secret::env("SWAN_TEST_AWS_KEY_NOT_SET"), "secret");
+        let error = signer.sign_at(&mut SigningRequest::new(&mut request), SystemTime::now()).unwrap_err();
+        assert_eq!(error.kind(), "auth");
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+}
@@ -0,0 +1,22 @@
+use crate::auth::Secret;
+
+/// `#[http_client(signer = ...)]` 解析得到的签名配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerConfig {
+    /// `aws_sigv4(region = "us-east-1", service = "s3", ...)`，未给出凭据时读取 AWS 标准环境变量
+    AwsSigV4 {
+        region: String,
+        service: String,
+        credentials: Option<(Secret, Secret)>,
+        session_token: Option<Secret>,
+    },
+    /// `hmac(secret_env = "SIGNING_SECRET", key_id = "...", signed_headers = [...])`
+    Hmac {
+        secret: Secret,
+        key_id: Option<String>,
+        signature_header: Option<String>,
+        timestamp_header: Option<String>,
+        key_id_header: Option<String>,
+        signed_headers: Vec<String>,
+    },
+}
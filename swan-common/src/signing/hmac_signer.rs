@@ -0,0 +1,170 @@
+use super::signer::{hmac_sha256, sha256_hex, unix_seconds};
+use super::{SigningRequest, SwanSigner};
+use crate::auth::Secret;
+use crate::error::SwanError;
+use async_trait::async_trait;
+use reqwest::header::{HeaderName, HeaderValue};
+use std::time::SystemTime;
+
+/// 通用 HMAC-SHA256 签名
+///
+/// 待签名字符串由以下各行以 `\n` 连接而成：
+///
+/// ```text
+/// GET                          请求方法
+/// /items?page=2                路径与查询参数
+/// 1700000000                   UNIX 时间戳（秒），同时放在时间戳请求头中
+/// content-type:application/json  `signed_headers` 中的每个请求头，缺失时取值为空
+/// 9f86d08...                   请求体 SHA-256 的十六进制
+/// ```
+///
+/// 签名的十六进制放在签名请求头（默认 `X-Signature`）中，时间戳放在 `X-Timestamp` 中，
+/// 设置了 `key_id` 时放在 `X-Key-Id` 中。流式请求体无法签名，返回 [`SwanError::Auth`]。
+#[derive(Debug, Clone)]
+pub struct HmacSigner {
+    secret: Secret,
+    key_id: Option<String>,
+    signature_header: String,
+    timestamp_header: String,
+    key_id_header: String,
+    signed_headers: Vec<String>,
+}
+
+impl HmacSigner {
+    pub fn new(secret: impl Into<Secret>) -> Self {
+        Self {
+            secret: secret.into(),
+            key_id: None,
+            signature_header: "X-Signature".to_string(),
+            timestamp_header: "X-Timestamp".to_string(),
+            key_id_header: "X-Key-Id".to_string(),
+            signed_headers: Vec::new(),
+        }
+    }
+
+    /// 设置密钥 ID，服务端据此选择密钥
+    pub fn key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+
+    /// 设置签名请求头名称
+    pub fn signature_header(mut self, name: impl Into<String>) -> Self {
+        self.signature_header = name.into();
+        self
+    }
+
+    /// 设置时间戳请求头名称
+    pub fn timestamp_header(mut self, name: impl Into<String>) -> Self {
+        self.timestamp_header = name.into();
+        self
+    }
+
+    /// 设置密钥 ID 请求头名称
+    pub fn key_id_header(mut self, name: impl Into<String>) -> Self {
+        self.key_id_header = name.into();
+        self
+    }
+
+    /// 设置参与签名的请求头
+    pub fn signed_headers<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.signed_headers = names.into_iter().map(|name| name.into().to_ascii_lowercase()).collect();
+        self
+    }
+
+    /// 按上述格式生成待签名字符串
+    fn string_to_sign(&self, request: &SigningRequest<'_>, timestamp: u64) -> Result<String, SwanError> {
+        let body = request.body().ok_or_else(|| SwanError::auth("Streaming request bodies cannot be signed"))?;
+        let url = request.url();
+        let mut lines = vec![request.method().to_string(), url.path().to_string(), timestamp.to_string()];
+        if let Some(query) = url.query() {
+            lines[1] = format!("{}?{}", url.path(), query);
+        }
+        for name in &self.signed_headers {
+            let value = match request.headers().get(name.as_str()) {
+                Some(value) => value.to_str().map_err(SwanError::auth)?,
+                None => "",
+            };
+            lines.push(format!("{}:{}", name, value.trim()));
+        }
+        lines.push(sha256_hex(body));
+        Ok(lines.join("\n"))
+    }
+
+    fn sign_at(&self, request: &mut SigningRequest<'_>, time: SystemTime) -> Result<(), SwanError> {
+        let secret = self.secret.resolve()?;
+        let timestamp = unix_seconds(time);
+        let string_to_sign = self.string_to_sign(request, timestamp)?;
+        let signature = hex::encode(hmac_sha256(secret.as_bytes(), string_to_sign.as_bytes()));
+
+        let headers = request.headers_mut();
+        headers.insert(header_name(&self.timestamp_header)?, HeaderValue::from(timestamp));
+        if let Some(key_id) = &self.key_id {
+            let value = HeaderValue::from_str(key_id).map_err(|_| SwanError::auth("Invalid HMAC key id"))?;
+            headers.insert(header_name(&self.key_id_header)?, value);
+        }
+        let mut signature = HeaderValue::from_str(&signature).map_err(SwanError::auth)?;
+        signature.set_sensitive(true);
+        headers.insert(header_name(&self.signature_header)?, signature);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SwanSigner for HmacSigner {
+    async fn sign(&self, request: &mut SigningRequest<'_>) -> Result<(), SwanError> {
+        self.sign_at(request, SystemTime::now())
+    }
+}
+
+fn header_name(name: &str) -> Result<HeaderName, SwanError> {
+    HeaderName::from_bytes(name.as_bytes()).map_err(|_| SwanError::auth(format!("Invalid signing header name: {}", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn request() -> reqwest::Request {
+        let mut request = reqwest::Request::new(reqwest::Method::POST, "https://svc.internal/items?page=2".parse().unwrap());
+        request.headers_mut().insert("content-type", HeaderValue::from_static("application/json"));
+        *request.body_mut() = Some(reqwest::Body::from("{}"));
+        request
+    }
+
+    #[test]
+    fn test_sign() {
+        let signer = HmacSigner::new("s3cret").key_id("svc-a").signed_headers(["Content-Type"]);
+        let mut request = request();
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let string_to_sign = signer.string_to_sign(&SigningRequest::new(&mut request), 1_700_000_000).unwrap();
+        assert_eq!(
+            string_to_sign,
+            "POST\n/items?page=2\n1700000000\ncontent-type:application/json\n\
+             44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a",
+        );
+
+        signer.sign_at(&mut SigningRequest::new(&mut request), time).unwrap();
+        let expected = hex::encode(hmac_sha256(b"s3cret", string_to_sign.as_bytes()));
+        assert_eq!(request.headers().get("x-signature").unwrap(), expected.as_str());
+        assert_eq!(request.headers().get("x-timestamp").unwrap(), "1700000000");
+        assert_eq!(request.headers().get("x-key-id").unwrap(), "svc-a");
+    }
+
+    #[test]
+    fn test_streaming_body_cannot_be_signed() {
+        let mut request = request();
+        let stream = futures_util::stream::iter([Ok::<_, std::io::Error>(bytes::Bytes::from_static(b"{}"))]);
+        *request.body_mut() = Some(reqwest::Body::wrap_stream(stream));
+
+        let error = HmacSigner::new("s3cret").sign_at(&mut SigningRequest::new(&mut request), SystemTime::now()).unwrap_err();
+        assert_eq!(error.kind(), "auth");
+        assert!(request.headers().get("x-signature").is_none());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod config;
+pub mod signer;
+pub mod aws_sigv4;
+pub mod hmac_signer;
+
+pub use config::SignerConfig;
+pub use signer::{SigningRequest, SwanSigner};
+pub use aws_sigv4::AwsSigV4Signer;
+pub use hmac_signer::HmacSigner;
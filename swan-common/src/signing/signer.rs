@@ -0,0 +1,133 @@
+use crate::error::SwanError;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 请求签名
+///
+/// 通过 `#[http_client(signer = ...)]` 或客户端构建器的 `signer()` 设置。每次尝试（包括重试与
+/// 认证刷新后的重发）在拦截器与认证之后、发送之前调用，签名覆盖最终的请求方法、URL、请求头与请求体。
+#[async_trait]
+pub trait SwanSigner: Send + Sync {
+    /// 为请求签名，通常是添加签名与时间戳请求头
+    async fn sign(&self, request: &mut SigningRequest<'_>) -> Result<(), SwanError>;
+}
+
+impl fmt::Debug for dyn SwanSigner + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SwanSigner")
+    }
+}
+
+/// 待签名的请求
+#[derive(Debug)]
+pub struct SigningRequest<'a> {
+    request: &'a mut reqwest::Request,
+}
+
+impl<'a> SigningRequest<'a> {
+    pub fn new(request: &'a mut reqwest::Request) -> Self {
+        Self { request }
+    }
+
+    pub fn method(&self) -> &reqwest::Method {
+        self.request.method()
+    }
+
+    pub fn url(&self) -> &reqwest::Url {
+        self.request.url()
+    }
+
+    /// 可修改的 URL，用于把签名放在查询参数中
+    pub fn url_mut(&mut self) -> &mut reqwest::Url {
+        self.request.url_mut()
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        self.request.headers()
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        self.request.headers_mut()
+    }
+
+    /// 请求体字节，没有请求体时为空；流式请求体（如 multipart 文件流）为 `None`
+    pub fn body(&self) -> Option<&[u8]> {
+        match self.request.body() {
+            Some(body) => body.as_bytes(),
+            None => Some(&[]),
+        }
+    }
+}
+
+/// SHA-256 摘要的小写十六进制
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// HMAC-SHA256
+pub(crate) fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// UNIX 时间戳（秒）
+pub(crate) fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or_default()
+}
+
+/// UTC 时间的 `(YYYYMMDD, YYYYMMDD'T'HHMMSS'Z')`
+pub(crate) fn utc_timestamp(time: SystemTime) -> (String, String) {
+    let seconds = unix_seconds(time);
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // 按 Howard Hinnant 的 civil_from_days 算法把天数换算为公历日期
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{:04}{:02}{:02}", year, month, day);
+    let timestamp = format!(
+        "{}T{:02}{:02}{:02}Z",
+        date,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+    );
+    (date, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_utc_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_440_938_160);
+        assert_eq!(utc_timestamp(time), ("20150830".to_string(), "20150830T123600Z".to_string()));
+
+        let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_399);
+        assert_eq!(utc_timestamp(leap_day).1, "20000228T235959Z");
+        assert_eq!(utc_timestamp(leap_day + Duration::from_secs(1)).1, "20000229T000000Z");
+    }
+
+    #[test]
+    fn test_body() {
+        let mut request = reqwest::Request::new(reqwest::Method::GET, "https://api.example.com/".parse().unwrap());
+        assert_eq!(SigningRequest::new(&mut request).body(), Some(&b""[..]));
+
+        *request.body_mut() = Some(reqwest::Body::from("payload"));
+        assert_eq!(SigningRequest::new(&mut request).body(), Some(&b"payload"[..]));
+    }
+}
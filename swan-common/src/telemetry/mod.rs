@@ -0,0 +1,3 @@
+pub mod span;
+
+pub use span::{AttemptSpan, CallSpan};
@@ -0,0 +1,131 @@
+use crate::metrics::{AttemptInfo, RequestInfo, RequestOutcome, SwanMetrics};
+use reqwest::{Method, Url};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// [`RecordingMetrics`] 记录的一次回调
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetricsEvent {
+    /// `on_request_start`
+    RequestStart {
+        method: &'static str,
+        http_method: Method,
+        url: Url,
+    },
+    /// `on_attempt_end`
+    AttemptEnd {
+        method: &'static str,
+        attempt: AttemptInfo,
+    },
+    /// `on_retry`
+    Retry {
+        method: &'static str,
+        attempt: u32,
+        delay: Duration,
+    },
+    /// `on_request_end`
+    RequestEnd {
+        method: &'static str,
+        outcome: RequestOutcome,
+    },
+}
+
+/// 将指标回调按顺序记录在内存中的 [`SwanMetrics`] 实现
+///
+/// 克隆共享同一份记录，测试中把克隆交给构建器即可在调用后检查：
+///
+/// ```rust,ignore
+/// let metrics = RecordingMetrics::new();
+/// let client = ApiClient::builder()
+///     .mock_transport(transport)
+///     .metrics(metrics.clone())
+///     .build()?;
+///
+/// client.get_user(1).await?;
+/// assert_eq!(metrics.attempts().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordingMetrics {
+    events: Arc<Mutex<Vec<MetricsEvent>>>,
+}
+
+impl RecordingMetrics {
+    /// 创建空的记录器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按触发顺序返回全部回调
+    pub fn events(&self) -> Vec<MetricsEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// 全部尝试结果
+    pub fn attempts(&self) -> Vec<AttemptInfo> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                MetricsEvent::AttemptEnd { attempt, .. } => Some(attempt),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 全部重试：(下一次尝试的序号, 等待时间)
+    pub fn retries(&self) -> Vec<(u32, Duration)> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                MetricsEvent::Retry { attempt, delay, .. } => Some((attempt, delay)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 全部调用的最终结果
+    pub fn outcomes(&self) -> Vec<RequestOutcome> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                MetricsEvent::RequestEnd { outcome, .. } => Some(outcome),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn push(&self, event: MetricsEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+impl SwanMetrics for RecordingMetrics {
+    fn on_request_start(&self, request: &RequestInfo<'_>) {
+        self.push(MetricsEvent::RequestStart {
+            method: request.method,
+            http_method: request.http_method.clone(),
+            url: request.url.clone(),
+        });
+    }
+
+    fn on_attempt_end(&self, request: &RequestInfo<'_>, attempt: &AttemptInfo) {
+        self.push(MetricsEvent::AttemptEnd {
+            method: request.method,
+            attempt: attempt.clone(),
+        });
+    }
+
+    fn on_retry(&self, request: &RequestInfo<'_>, attempt: u32, delay: Duration) {
+        self.push(MetricsEvent::Retry {
+            method: request.method,
+            attempt,
+            delay,
+        });
+    }
+
+    fn on_request_end(&self, request: &RequestInfo<'_>, outcome: &RequestOutcome) {
+        self.push(MetricsEvent::RequestEnd {
+            method: request.method,
+            outcome: outcome.clone(),
+        });
+    }
+}
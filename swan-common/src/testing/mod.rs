@@ -0,0 +1,5 @@
+pub mod transport;
+pub mod metrics;
+
+pub use transport::{MockHandle, MockResponse, MockRule, MockTransport, RecordedRequest};
+pub use metrics::{MetricsEvent, RecordingMetrics};
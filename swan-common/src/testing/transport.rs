@@ -0,0 +1,419 @@
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, ResponseBuilderExt, StatusCode, Url};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use crate::error::SwanError;
+
+/// 进程内的模拟传输层
+///
+/// 通过 `#[http_client]` 生成的 `with_mock_transport` 构造函数或构建器的 `mock_transport` 方法
+/// 挂载到客户端后，请求不再经过网络，而是按注册顺序匹配规则并返回预设响应，
+/// 不会打开任何套接字。拦截器、重试与响应解码照常执行。
+///
+/// # 示例
+///
+/// ```rust,ignore
+/// let transport = MockTransport::new();
+/// let users = transport.mock("GET", "/users")
+///     .query("page", "2")
+///     .header("X-Tenant", "acme")
+///     .respond(MockResponse::json(r#"[{"id":1}]"#));
+///
+/// let client = ApiClient::with_mock_transport(transport.clone());
+/// client.list_users(2, "acme").await?;
+///
+/// users.assert_calls(1);
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<TransportState>>,
+    /// 客户端的默认请求头（真实传输层由 `reqwest::Client` 添加）
+    default_headers: HeaderMap,
+}
+
+#[derive(Default)]
+struct TransportState {
+    rules: Vec<Arc<RuleState>>,
+    requests: Vec<RecordedRequest>,
+}
+
+impl MockTransport {
+    /// 创建空的模拟传输层
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始注册一条规则，匹配指定方法与路径（不含查询串）
+    pub fn mock(&self, method: impl AsRef<str>, path: impl Into<String>) -> MockRule {
+        let method = Method::from_bytes(method.as_ref().as_bytes())
+            .unwrap_or_else(|e| panic!("Invalid HTTP method '{}': {}", method.as_ref(), e));
+
+        MockRule {
+            transport: self.clone(),
+            matcher: RequestMatcher {
+                method,
+                path: path.into(),
+                query: Vec::new(),
+                headers: Vec::new(),
+                body: None,
+            },
+        }
+    }
+
+    /// 已收到的全部请求（包括未匹配任何规则的请求）
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// 已收到的请求总数
+    pub fn calls(&self) -> usize {
+        self.lock().requests.len()
+    }
+
+    /// 执行请求：记录请求并返回第一条匹配规则的响应
+    ///
+    /// 没有规则匹配时返回 [`SwanError::Request`]。
+    pub async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response, SwanError> {
+        let mut headers = request.headers().clone();
+        for (name, value) in &self.default_headers {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+
+        let recorded = RecordedRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers,
+            body: request.body().and_then(|body| body.as_bytes()).map(Bytes::copy_from_slice),
+        };
+
+        let rule = {
+            let mut state = self.lock();
+            state.requests.push(recorded.clone());
+            state.rules.iter().find(|rule| rule.matcher.matches(&recorded)).cloned()
+        };
+
+        match rule {
+            Some(rule) => {
+                rule.calls.fetch_add(1, Ordering::SeqCst);
+                rule.next_response().into_response(recorded.url)
+            }
+            None => Err(SwanError::request(format!(
+                "No mock matches {} {}",
+                recorded.method, recorded.url
+            ))),
+        }
+    }
+
+    /// 附带客户端默认请求头的副本，与原传输层共享规则与请求记录
+    pub(crate) fn with_default_headers(&self, default_headers: HeaderMap) -> Self {
+        Self {
+            state: self.state.clone(),
+            default_headers,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TransportState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockTransport")
+            .field("rules", &state.rules.len())
+            .field("requests", &state.requests.len())
+            .finish()
+    }
+}
+
+/// 请求匹配条件
+struct RequestMatcher {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(HeaderName, String)>,
+    body: Option<BodyMatcher>,
+}
+
+enum BodyMatcher {
+    Bytes(Bytes),
+    Json(serde_json::Value),
+}
+
+impl RequestMatcher {
+    fn matches(&self, request: &RecordedRequest) -> bool {
+        request.method == self.method
+            && request.url.path() == self.path
+            && self.query.iter().all(|(name, value)| {
+                request.url.query_pairs().any(|(n, v)| n == name.as_str() && v == value.as_str())
+            })
+            && self.headers.iter().all(|(name, value)| {
+                request.headers.get_all(name).iter().any(|v| v.as_bytes() == value.as_bytes())
+            })
+            && match (&self.body, &request.body) {
+                (None, _) => true,
+                (Some(BodyMatcher::Bytes(expected)), Some(body)) => expected == body,
+                (Some(BodyMatcher::Json(expected)), Some(body)) => {
+                    serde_json::from_slice::<serde_json::Value>(body).is_ok_and(|body| &body == expected)
+                }
+                (Some(_), None) => false,
+            }
+    }
+}
+
+/// 规则构建器，由 [`MockTransport::mock`] 创建
+///
+/// 所有条件都满足时规则才会匹配；调用 `respond` 或 `respond_sequence` 后规则生效。
+#[must_use = "a mock rule is only registered once a response is set"]
+pub struct MockRule {
+    transport: MockTransport,
+    matcher: RequestMatcher,
+}
+
+impl MockRule {
+    /// 要求包含指定查询参数（按解码后的值比较）
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.matcher.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// 要求包含指定请求头（名称不区分大小写）
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .unwrap_or_else(|e| panic!("Invalid header name '{}': {}", name, e));
+        self.matcher.headers.push((name, value.into()));
+        self
+    }
+
+    /// 要求请求体与给定字节完全一致
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.matcher.body = Some(BodyMatcher::Bytes(body.into()));
+        self
+    }
+
+    /// 要求请求体为与给定值相等的 JSON
+    pub fn json_body(mut self, body: serde_json::Value) -> Self {
+        self.matcher.body = Some(BodyMatcher::Json(body));
+        self
+    }
+
+    /// 每次匹配都返回同一个响应
+    pub fn respond(self, response: MockResponse) -> MockHandle {
+        self.respond_sequence([response])
+    }
+
+    /// 依次返回给定响应，最后一个响应在之后的匹配中重复使用
+    ///
+    /// 适合测试重试：例如先返回 503，再返回 200。
+    pub fn respond_sequence(self, responses: impl IntoIterator<Item = MockResponse>) -> MockHandle {
+        let responses: VecDeque<MockResponse> = responses.into_iter().collect();
+        assert!(!responses.is_empty(), "respond_sequence requires at least one response");
+
+        let rule = Arc::new(RuleState {
+            matcher: self.matcher,
+            responses: Mutex::new(responses),
+            calls: AtomicUsize::new(0),
+        });
+        self.transport.lock().rules.push(rule.clone());
+        MockHandle { rule }
+    }
+}
+
+struct RuleState {
+    matcher: RequestMatcher,
+    responses: Mutex<VecDeque<MockResponse>>,
+    calls: AtomicUsize,
+}
+
+impl RuleState {
+    fn next_response(&self) -> MockResponse {
+        let mut responses = self.responses.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if responses.len() > 1 {
+            responses.pop_front().unwrap()
+        } else {
+            responses.front().cloned().unwrap()
+        }
+    }
+}
+
+/// 已注册规则的句柄，用于断言调用次数
+#[derive(Clone)]
+pub struct MockHandle {
+    rule: Arc<RuleState>,
+}
+
+impl MockHandle {
+    /// 规则被匹配的次数
+    pub fn calls(&self) -> usize {
+        self.rule.calls.load(Ordering::SeqCst)
+    }
+
+    /// 断言规则被匹配的次数
+    #[track_caller]
+    pub fn assert_calls(&self, expected: usize) {
+        let actual = self.calls();
+        assert_eq!(
+            actual, expected,
+            "mock {} {} expected {} call(s), got {}",
+            self.rule.matcher.method, self.rule.matcher.path, expected, actual
+        );
+    }
+}
+
+/// 预设响应
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl MockResponse {
+    /// 指定状态码的空响应
+    pub fn new(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status)
+                .unwrap_or_else(|e| panic!("Invalid status code {}: {}", status, e)),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+
+    /// 200 JSON 响应
+    pub fn json(body: impl Into<String>) -> Self {
+        Self::new(200)
+            .header("Content-Type", "application/json")
+            .body(body.into())
+    }
+
+    /// 添加响应头
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .unwrap_or_else(|e| panic!("Invalid header name '{}': {}", name, e));
+        let value = HeaderValue::from_str(value)
+            .unwrap_or_else(|e| panic!("Invalid header value '{}': {}", value, e));
+        self.headers.append(name, value);
+        self
+    }
+
+    /// 设置响应体
+    pub fn body(mut self, body: impl Into<Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    fn into_response(self, url: Url) -> Result<reqwest::Response, SwanError> {
+        let mut builder = http::Response::builder().status(self.status).url(url);
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers);
+        }
+        let response = builder.body(self.body).map_err(SwanError::request)?;
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+/// 模拟传输层收到的请求
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: Url,
+    /// 请求头（包含客户端默认请求头）
+    pub headers: HeaderMap,
+    /// 请求体；流式请求体（如 multipart）为 `None`
+    pub body: Option<Bytes>,
+}
+
+impl RecordedRequest {
+    /// 读取字符串形式的请求头
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, url: &str, body: Option<&'static [u8]>) -> reqwest::Request {
+        let mut request = reqwest::Request::new(method, Url::parse(url).unwrap());
+        request.headers_mut().insert("x-tenant", HeaderValue::from_static("acme"));
+        *request.body_mut() = body.map(reqwest::Body::from);
+        request
+    }
+
+    #[tokio::test]
+    async fn test_matches_method_path_query_and_header() {
+        let transport = MockTransport::new();
+        let handle = transport.mock("GET", "/users")
+            .query("page", "2")
+            .header("X-Tenant", "acme")
+            .respond(MockResponse::json("[]").header("X-Total", "0"));
+
+        let response = transport
+            .execute(request(Method::GET, "http://mock/users?page=2", None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-total"], "0");
+        assert_eq!(response.url().as_str(), "http://mock/users?page=2");
+        handle.assert_calls(1);
+
+        let missed = transport.execute(request(Method::GET, "http://mock/users?page=3", None)).await;
+        assert!(matches!(missed, Err(SwanError::Request(_))));
+        handle.assert_calls(1);
+        assert_eq!(transport.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_json_body_matcher() {
+        let transport = MockTransport::new();
+        let handle = transport.mock("POST", "/users")
+            .json_body(serde_json::json!({ "name": "amy" }))
+            .respond(MockResponse::new(201));
+
+        let response = transport
+            .execute(request(Method::POST, "http://mock/users", Some(br#"{ "name" : "amy" }"#)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(transport.execute(request(Method::POST, "http://mock/users", Some(b"{}"))).await.is_err());
+        handle.assert_calls(1);
+    }
+
+    #[tokio::test]
+    async fn test_response_sequence_repeats_last() {
+        let transport = MockTransport::new();
+        transport.mock("GET", "/flaky")
+            .respond_sequence([MockResponse::new(503), MockResponse::new(200)]);
+
+        let mut statuses = Vec::new();
+        for _ in 0..3 {
+            let response = transport.execute(request(Method::GET, "http://mock/flaky", None)).await.unwrap();
+            statuses.push(response.status().as_u16());
+        }
+
+        assert_eq!(statuses, vec![503, 200, 200]);
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_are_recorded() {
+        let mut defaults = HeaderMap::new();
+        defaults.insert("user-agent", HeaderValue::from_static("swan-test"));
+        defaults.insert("x-tenant", HeaderValue::from_static("other"));
+        let transport = MockTransport::new();
+        let bound = transport.with_default_headers(defaults);
+
+        let _ = bound.execute(request(Method::GET, "http://mock/", None)).await;
+
+        let recorded = &transport.requests()[0];
+        assert_eq!(recorded.header("user-agent"), Some("swan-test"));
+        assert_eq!(recorded.header("x-tenant"), Some("acme"));
+    }
+}
@@ -0,0 +1,204 @@
+use super::verifier::{SwanServerVerifier, decode_pin};
+use crate::auth::Secret;
+use crate::error::SwanError;
+use p12_keystore::KeyStore;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::{ClientConfig, RootCertStore};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// TLS 最低协议版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    Tls12,
+    Tls13,
+}
+
+impl TlsVersion {
+    /// 解析 `"1.2"` 或 `"1.3"`
+    pub fn parse(version: &str) -> Option<Self> {
+        match version {
+            "1.2" => Some(TlsVersion::Tls12),
+            "1.3" => Some(TlsVersion::Tls13),
+            _ => None,
+        }
+    }
+}
+
+/// 客户端 TLS 配置
+///
+/// 设置后客户端改用 rustls：信任 Mozilla 根证书与 `ca` 中的证书，可携带客户端证书（mTLS）
+/// 并按 SPKI 哈希固定服务端公钥；未设置时沿用 reqwest 默认的系统 TLS。
+/// 证书文件在构建客户端时读取，相对路径相对于进程工作目录。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// 额外信任的 CA 证书（PEM，可包含多个证书）
+    pub ca: Vec<PathBuf>,
+    /// 客户端证书：`.p12`/`.pfx` 为 PKCS#12，其他扩展名为包含证书链与私钥的 PEM
+    pub identity: Option<PathBuf>,
+    /// PKCS#12 文件的密码，未设置时为空密码
+    pub identity_password: Option<Secret>,
+    /// 最低协议版本，默认 TLS 1.2
+    pub min_version: Option<TlsVersion>,
+    /// 不校验证书链与主机名，仅用于测试环境；设置了 `pins` 时仍要求服务端证书本身匹配
+    pub danger_accept_invalid_certs: bool,
+    /// `sha256/<base64>` 形式的 SPKI 哈希，经过验证的证书链中任一证书匹配任一哈希即通过
+    pub pins: Vec<String>,
+}
+
+impl TlsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加信任的 CA 证书文件
+    pub fn ca(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca.push(path.into());
+        self
+    }
+
+    /// 设置客户端证书文件
+    pub fn identity(mut self, path: impl Into<PathBuf>) -> Self {
+        self.identity = Some(path.into());
+        self
+    }
+
+    /// 设置 PKCS#12 文件的密码
+    pub fn identity_password(mut self, password: impl Into<Secret>) -> Self {
+        self.identity_password = Some(password.into());
+        self
+    }
+
+    /// 设置最低协议版本
+    pub fn min_version(mut self, version: TlsVersion) -> Self {
+        self.min_version = Some(version);
+        self
+    }
+
+    /// 是否跳过证书链与主机名校验
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// 添加固定的 SPKI 哈希（`sha256/<base64>`，可用 [`spki_pin`](super::spki_pin) 计算）
+    pub fn pin(mut self, pin: impl Into<String>) -> Self {
+        self.pins.push(pin.into());
+        self
+    }
+
+    /// 读取证书文件，生成 rustls 客户端配置
+    pub fn client_config(&self) -> Result<ClientConfig, SwanError> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let mut roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        for path in &self.ca {
+            let certificates = parse_certificates(path, &read(path)?)?;
+            if certificates.is_empty() {
+                return Err(SwanError::request(format!("No certificates found in CA file '{}'", path.display())));
+            }
+            for certificate in certificates {
+                roots.add(certificate).map_err(|e| tls_error(path, e))?;
+            }
+        }
+
+        let pins = self.pins.iter()
+            .map(|pin| decode_pin(pin).ok_or_else(|| SwanError::request(format!("Invalid TLS pin '{}' (expected sha256/<base64>)", pin))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let verifier = SwanServerVerifier::new(roots, self.danger_accept_invalid_certs, pins, provider.clone())?;
+
+        let versions = match self.min_version {
+            Some(TlsVersion::Tls13) => &[&rustls::version::TLS13][..],
+            _ => rustls::DEFAULT_VERSIONS,
+        };
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(versions)
+            .map_err(SwanError::request)?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier));
+
+        let mut config = match &self.identity {
+            Some(path) => {
+                let (chain, key) = self.read_identity(path)?;
+                builder.with_client_auth_cert(chain, key).map_err(|e| tls_error(path, e))?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+
+    /// 读取客户端证书链与私钥
+    fn read_identity(&self, path: &Path) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>), SwanError> {
+        let is_pkcs12 = path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("p12") || extension.eq_ignore_ascii_case("pfx"));
+
+        let data = read(path)?;
+        if !is_pkcs12 {
+            let chain = parse_certificates(path, &data)?;
+            let key = PrivateKeyDer::from_pem_slice(&data).map_err(|e| tls_error(path, e))?;
+            if chain.is_empty() {
+                return Err(SwanError::request(format!("No certificates found in identity file '{}'", path.display())));
+            }
+            return Ok((chain, key));
+        }
+
+        let password = match &self.identity_password {
+            Some(password) => password.resolve()?,
+            None => String::new(),
+        };
+        let store = KeyStore::from_pkcs12(&data, &password).map_err(|e| tls_error(path, e))?;
+        let (_, key_chain) = store.private_key_chain()
+            .ok_or_else(|| SwanError::request(format!("No private key found in identity file '{}'", path.display())))?;
+        let chain = key_chain.chain().iter()
+            .map(|certificate| CertificateDer::from(certificate.as_der().to_vec()))
+            .collect();
+        let key = PrivatePkcs8KeyDer::from(key_chain.key().to_vec()).into();
+        Ok((chain, key))
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, SwanError> {
+    std::fs::read(path).map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", path.display(), e)).into())
+}
+
+fn parse_certificates(path: &Path, pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, SwanError> {
+    CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| tls_error(path, e))
+}
+
+fn tls_error(path: &Path, error: impl std::fmt::Display) -> SwanError {
+    SwanError::request(format!("Invalid TLS file '{}': {}", path.display(), error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(TlsVersion::parse("1.2"), Some(TlsVersion::Tls12));
+        assert_eq!(TlsVersion::parse("1.3"), Some(TlsVersion::Tls13));
+        assert_eq!(TlsVersion::parse("1.1"), None);
+    }
+
+    #[test]
+    fn test_client_config() {
+        let config = TlsConfig::new().min_version(TlsVersion::Tls13).client_config().unwrap();
+        assert_eq!(config.alpn_protocols, [b"h2".to_vec(), b"http/1.1".to_vec()]);
+        assert!(!config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn test_client_config_errors() {
+        let error = TlsConfig::new().ca("does-not-exist.pem").client_config().unwrap_err();
+        assert_eq!(error.kind(), "io");
+        assert!(error.to_string().contains("does-not-exist.pem"));
+
+        let error = TlsConfig::new().pin("sha256/short").client_config().unwrap_err();
+        assert_eq!(error.kind(), "request");
+    }
+}
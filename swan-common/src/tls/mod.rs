@@ -0,0 +1,5 @@
+pub mod config;
+pub mod verifier;
+
+pub use config::{TlsConfig, TlsVersion};
+pub use verifier::spki_pin;
@@ -0,0 +1,210 @@
+use crate::error::SwanError;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{CryptoProvider, WebPkiSupportedAlgorithms};
+use rustls::pki_types::{CertificateDer, ServerName, TrustAnchor, UnixTime};
+use rustls::{CertificateError, DigitallySignedStruct, OtherError, RootCertStore, SignatureScheme};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+
+/// 计算证书的 SPKI 固定值 `sha256/<base64>`
+///
+/// `certificate` 为 DER 编码的证书，结果可直接传给 [`TlsConfig::pin`](super::TlsConfig::pin)。
+pub fn spki_pin(certificate: &[u8]) -> Result<String, SwanError> {
+    let hash = spki_sha256(certificate).ok_or_else(|| SwanError::request("Invalid DER certificate"))?;
+    Ok(format!("sha256/{}", STANDARD.encode(hash)))
+}
+
+/// 解析 `sha256/<base64>` 形式的固定值
+pub(crate) fn decode_pin(pin: &str) -> Option<[u8; 32]> {
+    let hash = STANDARD.decode(pin.strip_prefix("sha256/")?).ok()?;
+    hash.try_into().ok()
+}
+
+fn spki_sha256(certificate: &[u8]) -> Option<[u8; 32]> {
+    let (_, certificate) = x509_parser::parse_x509_certificate(certificate).ok()?;
+    Some(Sha256::digest(certificate.tbs_certificate.subject_pki.raw).into())
+}
+
+/// 证书链中没有与固定值匹配的公钥
+struct PinMismatch;
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no certificate in the server chain matches the pinned public keys")
+    }
+}
+
+/// rustls 以 Debug 格式输出 `CertificateError::Other`，因此输出说明文字
+impl fmt::Debug for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for PinMismatch {}
+
+/// 服务端证书校验：WebPKI 校验（可关闭）加可选的 SPKI 固定
+///
+/// 固定值只与经过验证的证书链比对：校验证书链时为 WebPKI 构建出的
+/// 服务端证书、中间证书与信任锚，服务端额外附带的证书不参与匹配；
+/// 跳过证书链校验时只比对服务端证书。握手签名始终按服务端证书的公钥验证，
+/// 因此只有持有固定公钥对应私钥（或由其签发）的服务端才能通过。
+#[derive(Debug)]
+pub(crate) struct SwanServerVerifier {
+    /// `danger_accept_invalid_certs` 时为 `None`
+    webpki: Option<Arc<WebPkiServerVerifier>>,
+    /// 构建固定值所用证书链的信任锚
+    roots: Vec<TrustAnchor<'static>>,
+    pins: Vec<[u8; 32]>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl SwanServerVerifier {
+    pub(crate) fn new(
+        roots: RootCertStore,
+        danger_accept_invalid_certs: bool,
+        pins: Vec<[u8; 32]>,
+        provider: Arc<CryptoProvider>,
+    ) -> Result<Self, SwanError> {
+        let algorithms = provider.signature_verification_algorithms;
+        let anchors = roots.roots.clone();
+        let webpki = if danger_accept_invalid_certs {
+            None
+        } else {
+            Some(WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(SwanError::request)?)
+        };
+        Ok(Self { webpki, roots: anchors, pins, algorithms })
+    }
+
+    fn is_pinned(&self, certificate: &[u8]) -> bool {
+        spki_sha256(certificate).is_some_and(|hash| self.pins.contains(&hash))
+    }
+
+    /// 证书链中是否有与固定值匹配的公钥
+    ///
+    /// 构建路径时拒绝不含固定公钥的候选链，WebPKI 会继续尝试其余路径（如交叉签名）。
+    fn verified_chain_is_pinned(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> bool {
+        let Ok(certificate) = webpki::EndEntityCert::try_from(end_entity) else {
+            return false;
+        };
+        let pinned_path = |path: &webpki::VerifiedPath<'_>| {
+            let anchor_hash: [u8; 32] = Sha256::digest(der_sequence(&path.anchor().subject_public_key_info)).into();
+            let pinned = self.pins.contains(&anchor_hash)
+                || path.intermediate_certificates().any(|certificate| self.is_pinned(&certificate.der()));
+            if pinned { Ok(()) } else { Err(webpki::Error::UnknownIssuer) }
+        };
+
+        certificate
+            .verify_for_usage(
+                self.algorithms.all,
+                &self.roots,
+                intermediates,
+                now,
+                webpki::KeyUsage::server_auth(),
+                None,
+                Some(&pinned_path),
+            )
+            .is_ok()
+    }
+}
+
+/// 将 DER 内容包装为 SEQUENCE（信任锚只保存 SPKI 的内容部分）
+fn der_sequence(contents: &[u8]) -> Vec<u8> {
+    let mut der = vec![0x30];
+    let length = contents.len();
+    if length < 0x80 {
+        der.push(length as u8);
+    } else {
+        let bytes = length.to_be_bytes();
+        let skip = bytes.iter().take_while(|byte| **byte == 0).count();
+        der.push(0x80 | (bytes.len() - skip) as u8);
+        der.extend_from_slice(&bytes[skip..]);
+    }
+    der.extend_from_slice(contents);
+    der
+}
+
+impl ServerCertVerifier for SwanServerVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if let Some(webpki) = &self.webpki {
+            webpki.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+        }
+
+        let pinned = self.pins.is_empty()
+            || self.is_pinned(end_entity)
+            || (self.webpki.is_some() && self.verified_chain_is_pinned(end_entity, intermediates, now));
+        if !pinned {
+            return Err(rustls::Error::InvalidCertificate(CertificateError::Other(OtherError(Arc::new(PinMismatch)))));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, certificate, signature, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, certificate, signature, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_pin() {
+        let pin = format!("sha256/{}", STANDARD.encode([7u8; 32]));
+        assert_eq!(decode_pin(&pin), Some([7u8; 32]));
+
+        assert_eq!(decode_pin(&STANDARD.encode([7u8; 32])), None);
+        assert_eq!(decode_pin("sha256/AAAA"), None);
+        assert_eq!(decode_pin("sha256/not base64"), None);
+    }
+
+    #[test]
+    fn test_der_sequence() {
+        assert_eq!(der_sequence(&[1, 2]), vec![0x30, 2, 1, 2]);
+
+        let long = der_sequence(&[0; 300]);
+        assert_eq!(long[..4], [0x30, 0x82, 0x01, 0x2c]);
+        assert_eq!(long.len(), 304);
+    }
+
+    #[test]
+    fn test_spki_pin_rejects_invalid_certificate() {
+        assert_eq!(spki_pin(b"not a certificate").unwrap_err().kind(), "request");
+    }
+}
@@ -1,8 +1,18 @@
 use syn::punctuated::Punctuated;
-use syn::{LitStr, Path, Token};
+use syn::{Ident, LitStr, Path, Token};
 use crate::types::http::{HttpMethod, ContentType};
 use crate::types::retry::RetryConfig;
 use crate::types::proxy::ProxyConfig;
+use crate::circuit::CircuitBreakerConfig;
+use crate::rate_limit::RateLimitConfig;
+use crate::cache::CacheConfig;
+use crate::coalesce::CoalesceConfig;
+use crate::pagination::Pagination;
+use crate::auth::AuthConfig;
+use crate::signing::SignerConfig;
+use crate::cookies::CookieConfig;
+use crate::tls::TlsConfig;
+use crate::compression::{CompressConfig, ContentEncoding};
 
 /// HTTP 处理器参数配置
 pub struct HandlerArgs {
@@ -12,7 +22,23 @@ pub struct HandlerArgs {
     pub headers: Punctuated<LitStr, Token![,]>,
     pub interceptor: Option<Path>,
     pub retry: Option<RetryConfig>,
+    /// 自定义重试条件类型（`retry_predicate = MyPredicate`），需配合 `retry` 使用
+    pub retry_predicate: Option<Path>,
     pub proxy: Option<ProxyConfig>,
+    /// 响应体写入的目标文件路径参数（`download_to = path_arg`）
+    pub download_to: Option<Ident>,
+    /// 方法级超时（毫秒），覆盖客户端的 `timeout`
+    pub timeout_ms: Option<u64>,
+    /// 方法级令牌桶限流，与客户端级限流叠加
+    pub rate_limit: Option<RateLimitConfig>,
+    /// HTTP 缓存配置（`cache = true` 或 `cache = "max_age=60s"`），仅支持 GET
+    pub cache: Option<CacheConfig>,
+    /// 合并键相同的并发调用（`coalesce = true`），仅支持 GET
+    pub coalesce: Option<CoalesceConfig>,
+    /// 分页配置（`paginate = link_header` 等），方法返回逐条产出条目的 `Stream`，仅支持 GET
+    pub paginate: Option<Pagination>,
+    /// 请求体压缩（`compress = gzip` 或 `compress = gzip(min_size = 4096)`）
+    pub compress: Option<CompressConfig>,
 }
 
 /// HTTP 客户端参数配置
@@ -21,6 +47,26 @@ pub struct HttpClientArgs {
     pub interceptor: Option<Path>,
     pub state: Option<Path>,
     pub proxy: Option<ProxyConfig>,
+    /// 整个请求的超时时间（毫秒）
+    pub timeout_ms: Option<u64>,
+    /// 建立连接的超时时间（毫秒）
+    pub connect_timeout_ms: Option<u64>,
+    /// 按主机熔断的配置
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// 客户端级令牌桶限流
+    pub rate_limit: Option<RateLimitConfig>,
+    /// 请求指标钩子类型（实现 `SwanMetrics + Default`）
+    pub metrics: Option<Path>,
+    /// 客户端认证（`auth = bearer(env = "API_TOKEN")` 等）
+    pub auth: Option<AuthConfig>,
+    /// 请求签名（`signer = aws_sigv4(...)` 等）
+    pub signer: Option<SignerConfig>,
+    /// Cookie 存储（`cookies = true` 或 `cookies = file("session.json")`）
+    pub cookies: Option<CookieConfig>,
+    /// TLS 配置（`tls(ca = "ca.pem", identity = "client.p12", ...)`）
+    pub tls: Option<TlsConfig>,
+    /// 自动解压的响应编码（`decompress = [gzip, br, zstd]`）
+    pub decompress: Vec<ContentEncoding>,
 }
 
 #[cfg(test)]
@@ -41,7 +87,15 @@ mod tests {
             headers,
             interceptor: None,
             retry: None,
+            retry_predicate: None,
             proxy: None,
+            download_to: None,
+            timeout_ms: None,
+            rate_limit: None,
+            cache: None,
+            coalesce: None,
+            paginate: None,
+            compress: None,
         };
 
         assert_eq!(args.method, HttpMethod::Get);
@@ -58,6 +112,16 @@ mod tests {
             interceptor: None,
             state: None,
             proxy: None,
+            timeout_ms: None,
+            connect_timeout_ms: None,
+            circuit_breaker: None,
+            rate_limit: None,
+            metrics: None,
+            auth: None,
+            signer: None,
+            cookies: None,
+            tls: None,
+            decompress: Vec::new(),
         };
 
         assert!(args.base_url.is_some());
@@ -1,38 +1,108 @@
-use proc_macro2::Ident;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
 use syn::parse::{Parse, ParseStream};
 
 /// HTTP 方法枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum HttpMethod {
     Get,
     Post,
     Put,
+    Patch,
     Delete,
+    Head,
+    Options,
+    /// 自定义方法（如 WebDAV 的 PROPFIND），通过 `#[request(method = "...")]` 指定
+    Custom(String),
 }
 
 impl HttpMethod {
+    /// 从方法名解析 HTTP 方法
+    /// 
+    /// 标准方法映射到对应枚举值，其余合法的方法名（RFC 9110 token）作为自定义方法，
+    /// 方法名为空或包含非法字符时返回 `None`。
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "GET" => Some(HttpMethod::Get),
+            "POST" => Some(HttpMethod::Post),
+            "PUT" => Some(HttpMethod::Put),
+            "PATCH" => Some(HttpMethod::Patch),
+            "DELETE" => Some(HttpMethod::Delete),
+            "HEAD" => Some(HttpMethod::Head),
+            "OPTIONS" => Some(HttpMethod::Options),
+            _ if Self::is_valid_token(name) => Some(HttpMethod::Custom(name.to_string())),
+            _ => None,
+        }
+    }
+
     /// 返回HTTP方法的字符串表示
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             HttpMethod::Get => "GET",
             HttpMethod::Post => "POST",
             HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
             HttpMethod::Delete => "DELETE",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Custom(name) => name,
         }
     }
 
     /// 返回用于客户端的方法标识符
+    /// 
+    /// `reqwest::Client` 没有对应便捷方法的 OPTIONS 与自定义方法返回 `request`，
+    /// 需要配合 [`HttpMethod::reqwest_method`] 使用。
     pub fn client_method(&self) -> Ident {
         Ident::new(
             match self {
                 HttpMethod::Get => "get",
                 HttpMethod::Post => "post",
                 HttpMethod::Put => "put",
+                HttpMethod::Patch => "patch",
                 HttpMethod::Delete => "delete",
+                HttpMethod::Head => "head",
+                HttpMethod::Options | HttpMethod::Custom(_) => "request",
             },
             proc_macro2::Span::call_site(),
         )
     }
+
+    /// 生成对应 `reqwest::Method` 的表达式代码
+    pub fn reqwest_method(&self) -> TokenStream {
+        match self {
+            HttpMethod::Get => quote! { reqwest::Method::GET },
+            HttpMethod::Post => quote! { reqwest::Method::POST },
+            HttpMethod::Put => quote! { reqwest::Method::PUT },
+            HttpMethod::Patch => quote! { reqwest::Method::PATCH },
+            HttpMethod::Delete => quote! { reqwest::Method::DELETE },
+            HttpMethod::Head => quote! { reqwest::Method::HEAD },
+            HttpMethod::Options => quote! { reqwest::Method::OPTIONS },
+            HttpMethod::Custom(name) => {
+                // 方法名在解析阶段已校验为合法 token
+                quote! {
+                    reqwest::Method::from_bytes(#name.as_bytes())
+                        .expect("custom HTTP method validated at compile time")
+                }
+            }
+        }
+    }
+
+    /// 该方法是否通常携带请求体
+    pub fn allows_body(&self) -> bool {
+        matches!(
+            self,
+            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch | HttpMethod::Custom(_)
+        )
+    }
+
+    /// 检查名称是否为合法的 HTTP token（RFC 9110 §5.6.2），方法名与请求头名称均适用
+    pub(crate) fn is_valid_token(name: &str) -> bool {
+        !name.is_empty()
+            && name.bytes().all(|b| {
+                b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+            })
+    }
 }
 
 /// 内容类型枚举
@@ -68,6 +138,10 @@ mod tests {
         assert_eq!(HttpMethod::Post.as_str(), "POST");
         assert_eq!(HttpMethod::Put.as_str(), "PUT");
         assert_eq!(HttpMethod::Delete.as_str(), "DELETE");
+        assert_eq!(HttpMethod::Patch.as_str(), "PATCH");
+        assert_eq!(HttpMethod::Head.as_str(), "HEAD");
+        assert_eq!(HttpMethod::Options.as_str(), "OPTIONS");
+        assert_eq!(HttpMethod::Custom("PROPFIND".into()).as_str(), "PROPFIND");
     }
 
     #[test]
@@ -76,5 +150,37 @@ mod tests {
         assert_eq!(HttpMethod::Post.client_method().to_string(), "post");
         assert_eq!(HttpMethod::Put.client_method().to_string(), "put");
         assert_eq!(HttpMethod::Delete.client_method().to_string(), "delete");
+        assert_eq!(HttpMethod::Patch.client_method().to_string(), "patch");
+        assert_eq!(HttpMethod::Head.client_method().to_string(), "head");
+        assert_eq!(HttpMethod::Options.client_method().to_string(), "request");
+    }
+
+    #[test]
+    fn test_http_method_from_name() {
+        assert_eq!(HttpMethod::from_name("PATCH"), Some(HttpMethod::Patch));
+        assert_eq!(HttpMethod::from_name("OPTIONS"), Some(HttpMethod::Options));
+        assert_eq!(
+            HttpMethod::from_name("PROPFIND"),
+            Some(HttpMethod::Custom("PROPFIND".to_string()))
+        );
+        assert_eq!(HttpMethod::from_name(""), None);
+        assert_eq!(HttpMethod::from_name("BAD METHOD"), None);
+        assert_eq!(HttpMethod::from_name("GET\r\n"), None);
+    }
+
+    #[test]
+    fn test_http_method_reqwest_method() {
+        assert_eq!(HttpMethod::Options.reqwest_method().to_string(), "reqwest :: Method :: OPTIONS");
+        assert!(HttpMethod::Custom("PROPFIND".into()).reqwest_method().to_string().contains("from_bytes"));
+    }
+
+    #[test]
+    fn test_http_method_allows_body() {
+        assert!(HttpMethod::Post.allows_body());
+        assert!(HttpMethod::Patch.allows_body());
+        assert!(HttpMethod::Custom("PROPFIND".into()).allows_body());
+        assert!(!HttpMethod::Get.allows_body());
+        assert!(!HttpMethod::Head.allows_body());
+        assert!(!HttpMethod::Options.allows_body());
     }
 }
\ No newline at end of file
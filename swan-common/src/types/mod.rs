@@ -2,8 +2,10 @@ pub mod http;
 pub mod args;
 pub mod retry;
 pub mod proxy;
+pub mod param;
 
 pub use http::{HttpMethod, ContentType};
 pub use args::{HandlerArgs, HttpClientArgs};
-pub use retry::{RetryPolicy, RetryConfig};
-pub use proxy::{ProxyConfig, ProxyType};
\ No newline at end of file
+pub use retry::{RetryPolicy, RetryConfig, RetryErrorClass};
+pub use proxy::{ProxyConfig, ProxyType};
+pub use param::ParamRole;
\ No newline at end of file
@@ -0,0 +1,56 @@
+/// 方法参数的角色
+///
+/// 通过参数上的属性指定：`#[path]`、`#[query]`、`#[query("pageSize")]`、
+/// `#[header("X-Tenant")]` 与 `#[body]`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamRole {
+    /// URL 路径占位符
+    Path,
+    /// 查询参数，可指定参数名（默认使用参数名称）
+    Query(Option<String>),
+    /// 请求头，需指定请求头名称
+    Header(String),
+    /// 请求体
+    Body,
+}
+
+impl ParamRole {
+    /// 角色对应的属性名
+    pub const ATTRIBUTES: [&'static str; 4] = ["path", "query", "header", "body"];
+
+    /// 角色的属性写法，用于错误提示
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            ParamRole::Path => "#[path]",
+            ParamRole::Query(_) => "#[query]",
+            ParamRole::Header(_) => "#[header]",
+            ParamRole::Body => "#[body]",
+        }
+    }
+
+    /// 判断属性是否为参数角色属性
+    pub fn is_role_attribute(attr: &syn::Attribute) -> bool {
+        Self::ATTRIBUTES.iter().any(|name| attr.path().is_ident(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_is_role_attribute() {
+        let query: syn::Attribute = parse_quote! { #[query("pageSize")] };
+        let doc: syn::Attribute = parse_quote! { #[doc = "page size"] };
+
+        assert!(ParamRole::is_role_attribute(&query));
+        assert!(!ParamRole::is_role_attribute(&doc));
+    }
+
+    #[test]
+    fn test_attribute_name() {
+        assert_eq!(ParamRole::Header("X-Tenant".into()).attribute(), "#[header]");
+        assert_eq!(ParamRole::Query(None).attribute(), "#[query]");
+    }
+}
@@ -16,6 +16,43 @@ pub struct RetryPolicy {
     pub jitter_ratio: f64,
     /// 仅对幂等方法重试
     pub idempotent_only: bool,
+    /// 重试响应携带 `Retry-After` 时以其作为延迟（仍受 `max_delay` 限制）
+    pub respect_retry_after: bool,
+    /// 触发重试的状态码，`None` 时为 5xx、429 与 408
+    pub on_status: Option<Vec<u16>>,
+    /// 触发重试的错误分类，`None` 时为全部可重试的传输层错误
+    pub on_error: Option<Vec<RetryErrorClass>>,
+}
+
+/// 可重试的传输层错误分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryErrorClass {
+    /// 建立连接失败（DNS 解析失败、连接被拒绝等）
+    Connect,
+    /// 请求超时
+    Timeout,
+    /// 发送请求过程中的其他网络错误
+    Request,
+}
+
+impl RetryErrorClass {
+    /// `on_error=[...]` 中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            RetryErrorClass::Connect => "connect",
+            RetryErrorClass::Timeout => "timeout",
+            RetryErrorClass::Request => "request",
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "connect" => Ok(RetryErrorClass::Connect),
+            "timeout" => Ok(RetryErrorClass::Timeout),
+            "request" => Ok(RetryErrorClass::Request),
+            _ => Err(format!("Unknown error class in on_error: {} (expected connect, timeout or request)", name)),
+        }
+    }
 }
 
 impl Default for RetryPolicy {
@@ -27,6 +64,9 @@ impl Default for RetryPolicy {
             exponential_base: 2.0,
             jitter_ratio: 0.1,
             idempotent_only: true,
+            respect_retry_after: true,
+            on_status: None,
+            on_error: None,
         }
     }
 }
@@ -46,10 +86,13 @@ impl RetryPolicy {
         Self {
             max_attempts,
             base_delay_ms: delay_ms,
-            max_delay_ms: delay_ms,
+            max_delay_ms: delay_ms.max(Self::default().max_delay_ms),
             exponential_base: 1.0,
             jitter_ratio: 0.0,
             idempotent_only: true,
+            respect_retry_after: true,
+            on_status: None,
+            on_error: None,
         }
     }
 
@@ -79,6 +122,10 @@ impl RetryPolicy {
 
     /// 判断HTTP状态码是否应该重试
     pub fn should_retry_status(&self, status: u16) -> bool {
+        if let Some(statuses) = &self.on_status {
+            return statuses.contains(&status);
+        }
+
         match status {
             // 5xx 服务器错误 - 应该重试
             500..=599 => true,
@@ -91,12 +138,25 @@ impl RetryPolicy {
         }
     }
 
+    /// 判断错误分类是否应该重试
+    pub fn should_retry_error(&self, class: RetryErrorClass) -> bool {
+        self.on_error.as_ref().is_none_or(|classes| classes.contains(&class))
+    }
+
     /// 判断HTTP方法是否幂等
+    /// 
+    /// 按 RFC 9110 §9.2.2：GET、HEAD、OPTIONS、PUT、DELETE 为幂等方法，
+    /// POST、PATCH 不是。自定义方法中仅 TRACE 和 WebDAV 的只读方法 PROPFIND 视为幂等。
     pub fn is_idempotent_method(method: &crate::types::http::HttpMethod) -> bool {
         use crate::types::http::HttpMethod;
         match method {
-            HttpMethod::Get | HttpMethod::Put | HttpMethod::Delete => true,
-            HttpMethod::Post => false,
+            HttpMethod::Get
+            | HttpMethod::Head
+            | HttpMethod::Options
+            | HttpMethod::Put
+            | HttpMethod::Delete => true,
+            HttpMethod::Post | HttpMethod::Patch => false,
+            HttpMethod::Custom(name) => matches!(name.as_str(), "TRACE" | "PROPFIND"),
         }
     }
 }
@@ -124,6 +184,7 @@ impl RetryConfig {
     /// - "exponential(max_attempts=3, base_delay=100ms)"
     /// - "fixed(max_attempts=5, delay=200ms)"
     /// - "exponential(3, 100ms)" // 简化格式
+    /// - "exponential(3, 100ms, respect_retry_after=false)" // 简化格式后追加命名参数
     pub fn parse(config_str: &LitStr) -> Result<Self, syn::Error> {
         let config_value = config_str.value();
         let policy = Self::parse_policy_string(&config_value)
@@ -150,53 +211,46 @@ impl RetryConfig {
     fn parse_exponential_config(params: &str) -> Result<RetryPolicy, String> {
         let mut policy = RetryPolicy::default();
         
-        // 解析参数
-        for param in params.split(',') {
-            let param = param.trim();
-            if param.is_empty() { continue; }
-            
-            if let Some((key, value)) = param.split_once('=') {
-                let key = key.trim();
-                let value = value.trim();
-                
-                match key {
-                    "max_attempts" => {
-                        policy.max_attempts = value.parse()
-                            .map_err(|_| format!("Invalid max_attempts: {}", value))?;
-                    }
-                    "base_delay" => {
-                        policy.base_delay_ms = Self::parse_duration(value)?;
-                    }
-                    "max_delay" => {
-                        policy.max_delay_ms = Self::parse_duration(value)?;
-                    }
-                    "exponential_base" => {
-                        policy.exponential_base = value.parse()
-                            .map_err(|_| format!("Invalid exponential_base: {}", value))?;
-                    }
-                    "jitter_ratio" => {
-                        policy.jitter_ratio = value.parse()
-                            .map_err(|_| format!("Invalid jitter_ratio: {}", value))?;
-                    }
-                    "idempotent_only" => {
-                        policy.idempotent_only = value.parse()
-                            .map_err(|_| format!("Invalid idempotent_only: {}", value))?;
-                    }
-                    _ => return Err(format!("Unknown parameter: {}", key)),
+        // 简化格式：exponential(3, 100ms)，位置参数之后仍可追加 key=value
+        Self::parse_params(params, |position, key, value| {
+            match (position, key) {
+                (Some(0), _) | (None, "max_attempts") => {
+                    policy.max_attempts = value.parse()
+                        .map_err(|_| format!("Invalid max_attempts: {}", value))?;
                 }
-            } else {
-                // 简化格式：exponential(3, 100ms)
-                let parts: Vec<&str> = params.split(',').map(|s| s.trim()).collect();
-                if parts.len() >= 1 {
-                    policy.max_attempts = parts[0].parse()
-                        .map_err(|_| format!("Invalid max_attempts: {}", parts[0]))?;
+                (Some(1), _) | (None, "base_delay") => {
+                    policy.base_delay_ms = Self::parse_duration(value)?;
+                }
+                (None, "max_delay") => {
+                    policy.max_delay_ms = Self::parse_duration(value)?;
+                }
+                (None, "exponential_base") => {
+                    policy.exponential_base = value.parse()
+                        .map_err(|_| format!("Invalid exponential_base: {}", value))?;
+                }
+                (None, "jitter_ratio") => {
+                    policy.jitter_ratio = value.parse()
+                        .map_err(|_| format!("Invalid jitter_ratio: {}", value))?;
+                }
+                (None, "idempotent_only") => {
+                    policy.idempotent_only = value.parse()
+                        .map_err(|_| format!("Invalid idempotent_only: {}", value))?;
                 }
-                if parts.len() >= 2 {
-                    policy.base_delay_ms = Self::parse_duration(parts[1])?;
+                (None, "respect_retry_after") => {
+                    policy.respect_retry_after = value.parse()
+                        .map_err(|_| format!("Invalid respect_retry_after: {}", value))?;
                 }
-                break;
+                (None, "on_status") => {
+                    policy.on_status = Some(Self::parse_status_list(value)?);
+                }
+                (None, "on_error") => {
+                    policy.on_error = Some(Self::parse_error_list(value)?);
+                }
+                (Some(_), _) => return Err(format!("Unexpected positional parameter: {}", value)),
+                (None, key) => return Err(format!("Unknown parameter: {}", key)),
             }
-        }
+            Ok(())
+        })?;
         
         Ok(policy)
     }
@@ -204,34 +258,133 @@ impl RetryConfig {
     fn parse_fixed_config(params: &str) -> Result<RetryPolicy, String> {
         let mut policy = RetryPolicy::default();
         policy.exponential_base = 1.0; // 固定延迟
+        let mut max_delay = None;
+
+        // 简化格式：fixed(5, 200ms)
+        Self::parse_params(params, |position, key, value| {
+            match (position, key) {
+                (Some(0), _) | (None, "max_attempts") => {
+                    policy.max_attempts = value.parse()
+                        .map_err(|_| format!("Invalid max_attempts: {}", value))?;
+                }
+                (Some(1), _) | (None, "delay") => {
+                    policy.base_delay_ms = Self::parse_duration(value)?;
+                }
+                (None, "max_delay") => {
+                    max_delay = Some(Self::parse_duration(value)?);
+                }
+                (None, "respect_retry_after") => {
+                    policy.respect_retry_after = value.parse()
+                        .map_err(|_| format!("Invalid respect_retry_after: {}", value))?;
+                }
+                (None, "on_status") => {
+                    policy.on_status = Some(Self::parse_status_list(value)?);
+                }
+                (None, "on_error") => {
+                    policy.on_error = Some(Self::parse_error_list(value)?);
+                }
+                (Some(_), _) => return Err(format!("Unexpected positional parameter: {}", value)),
+                (None, key) => return Err(format!("Unknown parameter: {}", key)),
+            }
+            Ok(())
+        })?;
 
-        for param in params.split(',') {
+        // 未指定 max_delay 时不低于 delay，避免截断固定延迟；其主要作用是限制 Retry-After
+        policy.max_delay_ms = max_delay.unwrap_or(policy.max_delay_ms.max(policy.base_delay_ms));
+        
+        Ok(policy)
+    }
+
+    /// 依次解析逗号分隔的参数
+    ///
+    /// 位置参数以 `(Some(index), "", value)` 回调，必须位于所有 `key=value` 参数之前；
+    /// 命名参数以 `(None, key, value)` 回调。方括号内的逗号不作为参数分隔符。
+    fn parse_params(
+        params: &str,
+        mut apply: impl FnMut(Option<usize>, &str, &str) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let mut position = 0;
+        let mut named_seen = false;
+
+        for param in Self::split_top_level(params)? {
             let param = param.trim();
             if param.is_empty() { continue; }
-            
+
             if let Some((key, value)) = param.split_once('=') {
-                let key = key.trim();
-                let value = value.trim();
-                
-                match key {
-                    "max_attempts" => {
-                        policy.max_attempts = value.parse()
-                            .map_err(|_| format!("Invalid max_attempts: {}", value))?;
-                    }
-                    "delay" => {
-                        let delay = Self::parse_duration(value)?;
-                        policy.base_delay_ms = delay;
-                        policy.max_delay_ms = delay;
-                    }
-                    _ => return Err(format!("Unknown parameter: {}", key)),
+                named_seen = true;
+                apply(None, key.trim(), value.trim())?;
+            } else if named_seen {
+                return Err(format!("Positional parameter '{}' must come before key=value parameters", param));
+            } else {
+                apply(Some(position), "", param)?;
+                position += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 按顶层逗号拆分参数，保留 `[...]` 列表
+    fn split_top_level(params: &str) -> Result<Vec<&str>, String> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+
+        for (index, ch) in params.char_indices() {
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth = depth.checked_sub(1)
+                        .ok_or_else(|| format!("Unbalanced ']' in retry config: {}", params))?;
+                }
+                ',' if depth == 0 => {
+                    parts.push(&params[start..index]);
+                    start = index + 1;
                 }
+                _ => {}
             }
         }
-        
-        Ok(policy)
+
+        if depth != 0 {
+            return Err(format!("Unclosed '[' in retry config: {}", params));
+        }
+        parts.push(&params[start..]);
+        Ok(parts)
+    }
+
+    /// 解析 `[a, b, c]` 形式的列表
+    fn parse_list<'a>(key: &str, value: &'a str) -> Result<Vec<&'a str>, String> {
+        let items = value.strip_prefix('[').and_then(|v| v.strip_suffix(']'))
+            .ok_or_else(|| format!("{} must be a list like [a, b]: {}", key, value))?;
+        let items: Vec<&str> = items.split(',').map(str::trim).filter(|item| !item.is_empty()).collect();
+
+        if items.is_empty() {
+            return Err(format!("{} must not be empty", key));
+        }
+        Ok(items)
     }
 
-    fn parse_duration(duration_str: &str) -> Result<u64, String> {
+    fn parse_status_list(value: &str) -> Result<Vec<u16>, String> {
+        Self::parse_list("on_status", value)?
+            .into_iter()
+            .map(|item| match item.parse::<u16>() {
+                Ok(status) if (100..=599).contains(&status) => Ok(status),
+                _ => Err(format!("Invalid status code in on_status: {}", item)),
+            })
+            .collect()
+    }
+
+    fn parse_error_list(value: &str) -> Result<Vec<RetryErrorClass>, String> {
+        Self::parse_list("on_error", value)?
+            .into_iter()
+            .map(RetryErrorClass::parse)
+            .collect()
+    }
+
+    /// 解析时长字符串（`100ms`、`2s`，无后缀按毫秒处理），返回毫秒数
+    ///
+    /// 重试配置与 `timeout` / `connect_timeout` 等宏参数共用此语法。
+    pub fn parse_duration(duration_str: &str) -> Result<u64, String> {
         let duration_str = duration_str.trim();
         
         if duration_str.ends_with("ms") {
@@ -286,6 +439,20 @@ mod basic_tests {
         assert!(!policy.should_retry_status(404));
     }
 
+    #[test]
+    fn test_is_idempotent_method() {
+        use crate::types::http::HttpMethod;
+
+        assert!(RetryPolicy::is_idempotent_method(&HttpMethod::Get));
+        assert!(RetryPolicy::is_idempotent_method(&HttpMethod::Head));
+        assert!(RetryPolicy::is_idempotent_method(&HttpMethod::Options));
+        assert!(RetryPolicy::is_idempotent_method(&HttpMethod::Delete));
+        assert!(!RetryPolicy::is_idempotent_method(&HttpMethod::Post));
+        assert!(!RetryPolicy::is_idempotent_method(&HttpMethod::Patch));
+        assert!(RetryPolicy::is_idempotent_method(&HttpMethod::Custom("PROPFIND".into())));
+        assert!(!RetryPolicy::is_idempotent_method(&HttpMethod::Custom("LOCK".into())));
+    }
+
     #[test]
     fn test_parse_exponential_simple() {
         let config: LitStr = parse_quote! { "exponential(3, 100ms)" };
@@ -316,6 +483,72 @@ mod basic_tests {
         assert_eq!(result.policy.exponential_base, 1.0);
     }
 
+    #[test]
+    fn test_parse_respect_retry_after() {
+        let default: LitStr = parse_quote! { "exponential(3, 100ms)" };
+        assert!(RetryConfig::parse(&default).unwrap().policy.respect_retry_after);
+
+        let mixed: LitStr = parse_quote! { "exponential(3, 100ms, respect_retry_after=false)" };
+        let policy = RetryConfig::parse(&mixed).unwrap().policy;
+        assert_eq!(policy.max_attempts, 3);
+        assert_eq!(policy.base_delay_ms, 100);
+        assert!(!policy.respect_retry_after);
+
+        let fixed: LitStr = parse_quote! { "fixed(max_attempts=2, delay=1s, respect_retry_after=false)" };
+        let policy = RetryConfig::parse(&fixed).unwrap().policy;
+        assert_eq!(policy.base_delay_ms, 1000);
+        assert_eq!(policy.max_delay_ms, 30000);
+        assert!(!policy.respect_retry_after);
+
+        let capped: LitStr = parse_quote! { "fixed(2, 100ms, max_delay=5s)" };
+        let policy = RetryConfig::parse(&capped).unwrap().policy;
+        assert_eq!(policy.base_delay_ms, 100);
+        assert_eq!(policy.max_delay_ms, 5000);
+
+        let invalid: LitStr = parse_quote! { "fixed(delay=1s, respect_retry_after=maybe)" };
+        assert!(RetryConfig::parse(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_positional_after_named_is_error() {
+        let config: LitStr = parse_quote! { "exponential(max_attempts=3, 100ms)" };
+        assert!(RetryConfig::parse(&config).is_err());
+    }
+
+    #[test]
+    fn test_parse_retry_conditions() {
+        let config: LitStr = parse_quote! { "exponential(3, 100ms, on_status=[502, 503,504], on_error=[connect,timeout])" };
+        let policy = RetryConfig::parse(&config).unwrap().policy;
+
+        assert_eq!(policy.max_attempts, 3);
+        assert_eq!(policy.on_status, Some(vec![502, 503, 504]));
+        assert_eq!(policy.on_error, Some(vec![RetryErrorClass::Connect, RetryErrorClass::Timeout]));
+        assert!(policy.should_retry_status(503));
+        assert!(!policy.should_retry_status(500));
+        assert!(!policy.should_retry_status(429));
+        assert!(policy.should_retry_error(RetryErrorClass::Timeout));
+        assert!(!policy.should_retry_error(RetryErrorClass::Request));
+
+        let fixed: LitStr = parse_quote! { "fixed(max_attempts=2, delay=1s, on_status=[409])" };
+        let policy = RetryConfig::parse(&fixed).unwrap().policy;
+        assert_eq!(policy.on_status, Some(vec![409]));
+        assert!(policy.should_retry_error(RetryErrorClass::Request));
+    }
+
+    #[test]
+    fn test_parse_invalid_retry_conditions() {
+        for config in [
+            "exponential(3, 100ms, on_status=502)",
+            "exponential(3, 100ms, on_status=[])",
+            "exponential(3, 100ms, on_status=[700])",
+            "exponential(3, 100ms, on_status=[502)",
+            "exponential(3, 100ms, on_error=[dns])",
+        ] {
+            let config = LitStr::new(config, proc_macro2::Span::call_site());
+            assert!(RetryConfig::parse(&config).is_err(), "{}", config.value());
+        }
+    }
+
     #[test]
     fn test_parse_duration() {
         assert_eq!(RetryConfig::parse_duration("100ms").unwrap(), 100);
@@ -0,0 +1,123 @@
+use crate::error::SwanError;
+use percent_encoding::{AsciiSet, CONTROLS, NON_ALPHANUMERIC, utf8_percent_encode};
+use std::fmt::Display;
+
+/// RFC 3986 unreserved 字符之外的全部字符
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// 路径段编码集合
+///
+/// RFC 3986 §3.3：`segment = *pchar`，`pchar = unreserved / pct-encoded / sub-delims / ":" / "@"`。
+/// 这里额外编码 `%`，保证原始值中的 `%` 不会被当作转义序列。
+const PATH_SEGMENT: &AsciiSet = &UNRESERVED
+    .remove(b'!')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b'*')
+    .remove(b'+')
+    .remove(b',')
+    .remove(b';')
+    .remove(b'=')
+    .remove(b':')
+    .remove(b'@');
+
+/// 多段路径编码集合
+///
+/// 保留 `/` 与已有的 `%` 转义，只编码路径中不合法的字符（空格、`?`、`#` 等），
+/// 避免拼接值截断路径或注入查询参数。
+const PATH: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// 按路径段规则编码占位符值（`{name}`）
+///
+/// `/`、`?`、`#` 等字符都会被编码，值只能占据一个路径段。
+pub fn encode_path_segment(value: impl Display) -> String {
+    utf8_percent_encode(&value.to_string(), PATH_SEGMENT).to_string()
+}
+
+/// 编码路径占位符值（`{name}`），拒绝 `.` 与 `..`
+///
+/// 点段在 URL 规范化时会被移除或回退到上一级（`/users/../posts` 变为 `/posts`），
+/// 而 `%2E` 同样被视为点，编码无法避免，因此直接返回 [`SwanError::Request`]。
+/// 生成的方法使用此函数处理路径中的 `{name}`。
+pub fn try_encode_path_segment(value: impl Display) -> Result<String, SwanError> {
+    let value = value.to_string();
+    if value == "." || value == ".." {
+        return Err(SwanError::request(format!(
+            "Path parameter value '{}' is a dot segment and cannot be used as a path segment",
+            value
+        )));
+    }
+    Ok(encode_path_segment(value))
+}
+
+/// 按多段路径规则编码占位符值（`{*name}`）
+///
+/// 保留 `/`，允许一次拼接多个路径段。
+pub fn encode_path(value: impl Display) -> String {
+    utf8_percent_encode(&value.to_string(), PATH).to_string()
+}
+
+/// 按查询参数规则编码占位符值（URL 模板中 `?` 之后的 `{name}`）
+///
+/// 除 unreserved 字符外全部编码，`&`、`=`、`+`、`#` 与空格都不会破坏查询串结构。
+pub fn encode_query_component(value: impl Display) -> String {
+    utf8_percent_encode(&value.to_string(), UNRESERVED).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path_segment() {
+        assert_eq!(encode_path_segment("a/b?c"), "a%2Fb%3Fc");
+        assert_eq!(encode_path_segment("John Doe"), "John%20Doe");
+        assert_eq!(encode_path_segment("100%"), "100%25");
+        assert_eq!(encode_path_segment("user@example.com"), "user@example.com");
+        assert_eq!(encode_path_segment(42), "42");
+        assert_eq!(encode_path_segment("über"), "%C3%BCber");
+    }
+
+    #[test]
+    fn test_try_encode_path_segment_rejects_dot_segments() {
+        assert_eq!(try_encode_path_segment("..").unwrap_err().kind(), "request");
+        assert!(try_encode_path_segment(".").is_err());
+
+        assert_eq!(try_encode_path_segment("...").unwrap(), "...");
+        assert_eq!(try_encode_path_segment("v1.2").unwrap(), "v1.2");
+        assert_eq!(try_encode_path_segment("../admin").unwrap(), "..%2Fadmin");
+    }
+
+    #[test]
+    fn test_encode_path_keeps_slashes() {
+        assert_eq!(encode_path("docs/guide v2/index.html"), "docs/guide%20v2/index.html");
+        assert_eq!(encode_path("a?b#c"), "a%3Fb%23c");
+        assert_eq!(encode_path("already%20encoded"), "already%20encoded");
+    }
+
+    #[test]
+    fn test_encode_query_component() {
+        assert_eq!(encode_query_component("a&b=c"), "a%26b%3Dc");
+        assert_eq!(encode_query_component("1+1 #2"), "1%2B1%20%232");
+        assert_eq!(encode_query_component("safe-._~"), "safe-._~");
+    }
+}
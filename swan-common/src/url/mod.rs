@@ -0,0 +1,5 @@
+pub mod encode;
+pub mod query;
+
+pub use encode::{encode_path_segment, try_encode_path_segment, encode_path, encode_query_component};
+pub use query::set_query_param;
@@ -0,0 +1,26 @@
+use reqwest::Url;
+
+/// 设置查询参数，替换同名的已有参数，其余参数保持原有顺序
+pub fn set_query_param(url: &mut Url, name: &str, value: &str) {
+    let pairs: Vec<(String, String)> = url.query_pairs()
+        .filter(|(key, _)| key != name)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs).append_pair(name, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_query_param_replaces_existing() {
+        let mut url = Url::parse("https://api.example.com/users?cursor=a&limit=10").unwrap();
+        set_query_param(&mut url, "cursor", "b c");
+        assert_eq!(url.as_str(), "https://api.example.com/users?limit=10&cursor=b+c");
+
+        let mut url = Url::parse("https://api.example.com/users").unwrap();
+        set_query_param(&mut url, "page", "2");
+        assert_eq!(url.as_str(), "https://api.example.com/users?page=2");
+    }
+}
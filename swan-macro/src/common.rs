@@ -1,11 +1,11 @@
 use crate::generator::generate_http_method;
 use proc_macro::TokenStream;
-use swan_common::{HttpMethod, parse_handler_args};
+use swan_common::{HttpMethod, parse_handler_args, parse_request_args};
 use syn::{ItemFn, parse_macro_input};
 
 /// 通用 HTTP 方法处理函数
 /// 
-/// 这是所有 HTTP 方法宏（GET、POST、PUT、DELETE）的共同入口点。
+/// 这是所有固定方法的 HTTP 方法宏（GET、POST、PUT、PATCH、DELETE、HEAD、OPTIONS）的共同入口点。
 /// 它解析宏参数和函数定义，然后调用代码生成器。
 /// 
 /// # 参数
@@ -29,3 +29,14 @@ pub fn common_http_method(
 
     generate_http_method(&item.sig, &args)
 }
+
+/// 通用请求宏处理函数
+/// 
+/// `#[request]` 宏的入口点，HTTP 方法由参数中的 `method = "..."` 指定，
+/// 用于 PROPFIND 等没有专用宏的方法。
+pub fn common_request_method(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(args with parse_request_args);
+
+    generate_http_method(&item.sig, &args)
+}
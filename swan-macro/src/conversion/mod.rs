@@ -1,3 +1,7 @@
 pub mod types;
+pub mod response;
+pub mod stream;
 
-pub use types::generate_type_conversion;
\ No newline at end of file
+pub use types::generate_type_conversion;
+pub use response::{extract_response_body_type, generate_response_wrapping};
+pub use stream::{generate_download_conversion, generate_stream_conversion};
@@ -0,0 +1,92 @@
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type};
+
+/// 提取 `Response<T>` 返回类型中的响应体类型
+/// 
+/// 返回类型为 `Response<T>`、`swan_common::Response<T>` 或 `swan_common::response::Response<T>`
+/// 时返回 `T`，其余类型（包括 `api::Response<T>` 等用户自定义的同名类型）返回 `None`，
+/// 此时方法直接返回解码后的响应体。
+pub fn extract_response_body_type(ok_type: &GenericArgument) -> Option<&GenericArgument> {
+    let GenericArgument::Type(Type::Path(type_path)) = ok_type else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+
+    let segments: Vec<_> = type_path.path.segments.iter().collect();
+    let (last_segment, prefix) = segments.split_last()?;
+    if last_segment.ident != "Response" || prefix.iter().any(|segment| !segment.arguments.is_none()) {
+        return None;
+    }
+    let prefix: Vec<String> = prefix.iter().map(|segment| segment.ident.to_string()).collect();
+    let is_swan_response = match prefix.as_slice() {
+        [] => type_path.path.leading_colon.is_none(),
+        [krate] => krate == "swan_common",
+        [krate, module] => krate == "swan_common" && module == "response",
+        _ => false,
+    };
+    if !is_swan_response {
+        return None;
+    }
+
+    match &last_segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => args.args.first(),
+        _ => None,
+    }
+}
+
+/// 生成响应元信息的采集与包装代码
+/// 
+/// 返回 (读取响应体前采集元信息的代码, 将解码结果包装为 `Response<T>` 的代码)。
+pub fn generate_response_wrapping() -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let capture = quote! {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let url = response.url().clone();
+    };
+
+    let wrap = quote! {
+        let result = swan_common::Response {
+            status,
+            headers,
+            url,
+            elapsed: started_at.elapsed(),
+            attempts,
+            body: result,
+        };
+    };
+
+    (capture, wrap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_extract_response_body_type() {
+        let ok_type: GenericArgument = parse_quote! { swan_common::Response<Vec<User>> };
+        let body_type = extract_response_body_type(&ok_type).unwrap();
+        assert_eq!(quote! { #body_type }.to_string(), "Vec < User >");
+
+        let ok_type: GenericArgument = parse_quote! { Response<()> };
+        assert!(extract_response_body_type(&ok_type).is_some());
+    }
+
+    #[test]
+    fn test_non_response_types_are_not_wrapped() {
+        let ok_type: GenericArgument = parse_quote! { User };
+        assert!(extract_response_body_type(&ok_type).is_none());
+
+        let ok_type: GenericArgument = parse_quote! { reqwest::Response };
+        assert!(extract_response_body_type(&ok_type).is_none());
+
+        let ok_type: GenericArgument = parse_quote! { api::Response<User> };
+        assert!(extract_response_body_type(&ok_type).is_none());
+
+        let ok_type: GenericArgument = parse_quote! { swan_common::response::Response<User> };
+        assert!(extract_response_body_type(&ok_type).is_some());
+    }
+}
@@ -0,0 +1,117 @@
+use quote::quote;
+use syn::{GenericArgument, Type, TypeParamBound};
+
+/// 生成流式响应体的构造代码
+/// 
+/// 支持以下返回类型，均不会将响应体整体读入内存：
+/// 
+/// - `swan_common::ByteStream`
+/// - `impl Stream<Item = Result<Bytes, E>>`（`E: From<SwanError>`，包括 `anyhow::Result<Bytes>`）
+/// 
+/// 非流式类型返回 `None`；`impl Stream` 未声明 `Item` 时返回错误。
+pub fn generate_stream_conversion(ok_type: &GenericArgument) -> Option<syn::Result<proc_macro2::TokenStream>> {
+    let GenericArgument::Type(ty) = ok_type else {
+        return None;
+    };
+
+    match ty {
+        Type::Path(type_path) => {
+            let last_segment = type_path.path.segments.last()?;
+            (last_segment.ident == "ByteStream" && last_segment.arguments.is_empty())
+                .then(|| Ok(quote! { swan_common::ByteStream::from_response(response) }))
+        }
+        Type::ImplTrait(impl_trait) => {
+            let stream_bound = impl_trait.bounds.iter().find_map(|bound| match bound {
+                TypeParamBound::Trait(trait_bound) => trait_bound.path.segments.last()
+                    .filter(|segment| segment.ident == "Stream"),
+                _ => None,
+            })?;
+
+            let item_type = match &stream_bound.arguments {
+                syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                    GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+                    _ => None,
+                }),
+                _ => None,
+            };
+
+            Some(match item_type {
+                Some(item_type) => Ok(quote! {
+                    swan_common::ByteStream::from_response(response).items::<#item_type>()
+                }),
+                None => Err(syn::Error::new_spanned(
+                    stream_bound,
+                    "streaming return type must declare its item, e.g. impl Stream<Item = anyhow::Result<Bytes>>",
+                )),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// 生成 `download_to` 的写文件代码，结果为写入的字节数
+///
+/// 方法的成功类型必须是 `u64`（或 `Response<u64>`），否则返回错误。
+pub fn generate_download_conversion(
+    path: &syn::Ident,
+    body_type: &GenericArgument,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let is_u64 = matches!(
+        body_type,
+        GenericArgument::Type(Type::Path(type_path))
+            if type_path.qself.is_none() && type_path.path.is_ident("u64")
+    );
+    if !is_u64 {
+        return Err(syn::Error::new_spanned(
+            body_type,
+            "download_to methods must return the number of bytes written as u64, e.g. anyhow::Result<u64>",
+        ));
+    }
+
+    Ok(quote! {
+        swan_common::response::download_to_file(response, &#path).await?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_download_requires_u64() {
+        let path: syn::Ident = parse_quote! { dest };
+        assert!(generate_download_conversion(&path, &parse_quote! { u64 }).is_ok());
+
+        for body_type in [parse_quote! { String }, parse_quote! { usize }, parse_quote! { () }] {
+            assert!(generate_download_conversion(&path, &body_type).is_err());
+        }
+    }
+
+    #[test]
+    fn test_byte_stream_type() {
+        let ok_type: GenericArgument = parse_quote! { swan_common::ByteStream };
+        let code = generate_stream_conversion(&ok_type).unwrap().unwrap().to_string();
+        assert!(code.contains("ByteStream :: from_response"));
+        assert!(!code.contains("items"));
+    }
+
+    #[test]
+    fn test_impl_stream_type() {
+        let ok_type: GenericArgument = parse_quote! { impl Stream<Item = anyhow::Result<Bytes>> + Send };
+        let code = generate_stream_conversion(&ok_type).unwrap().unwrap().to_string();
+        assert!(code.contains("items :: < anyhow :: Result < Bytes > >"));
+
+        let ok_type: GenericArgument = parse_quote! { impl futures::Stream };
+        assert!(generate_stream_conversion(&ok_type).unwrap().is_err());
+    }
+
+    #[test]
+    fn test_buffered_types() {
+        let ok_type: GenericArgument = parse_quote! { Vec<u8> };
+        assert!(generate_stream_conversion(&ok_type).is_none());
+
+        let ok_type: GenericArgument = parse_quote! { impl Iterator<Item = u8> };
+        assert!(generate_stream_conversion(&ok_type).is_none());
+    }
+}
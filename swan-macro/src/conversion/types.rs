@@ -6,7 +6,8 @@ use syn::{GenericArgument, PathArguments, Type};
 /// 根据返回类型生成相应的反序列化代码。支持以下类型：
 /// - `String`: 直接从字节转换为UTF-8字符串
 /// - `Vec<u8>`: 直接返回字节向量
-/// - 其他类型: 使用 serde_json 进行JSON反序列化
+/// - `()`: 忽略响应体（适用于 HEAD 等无响应体的请求）
+/// - 其他类型: 使用 serde_json 进行JSON反序列化，失败时返回 `SwanError::Decode`
 /// 
 /// # 参数
 /// 
@@ -17,10 +18,13 @@ use syn::{GenericArgument, PathArguments, Type};
 /// 生成的类型转换代码
 pub fn generate_type_conversion(ok_type: &GenericArgument) -> proc_macro2::TokenStream {
     let default_conversion = quote! {
-        serde_json::from_slice::<#ok_type>(&bytes)?
+        serde_json::from_slice::<#ok_type>(&bytes).map_err(swan_common::SwanError::from)?
     };
 
     match ok_type {
+        GenericArgument::Type(Type::Tuple(tuple)) if tuple.elems.is_empty() => {
+            quote! { drop(bytes) }
+        }
         GenericArgument::Type(Type::Path(type_path)) => {
             let last_segment = match type_path.path.segments.last() {
                 Some(segment) => segment,
@@ -94,12 +98,19 @@ mod tests {
         assert_eq!(result.to_string(), expected.to_string());
     }
 
+    #[test]
+    fn test_unit_type_conversion() {
+        let ok_type: GenericArgument = parse_quote! { () };
+        let result = generate_type_conversion(&ok_type);
+        assert_eq!(result.to_string(), quote! { drop(bytes) }.to_string());
+    }
+
     #[test]
     fn test_custom_type_conversion() {
         let ok_type: GenericArgument = parse_quote! { MyCustomType };
         let result = generate_type_conversion(&ok_type);
         let expected = quote! {
-            serde_json::from_slice::<MyCustomType>(&bytes)?
+            serde_json::from_slice::<MyCustomType>(&bytes).map_err(swan_common::SwanError::from)?
         };
         assert_eq!(result.to_string(), expected.to_string());
     }
@@ -1,5 +1,6 @@
 use quote::quote;
-use syn::{GenericArgument, PathArguments, ReturnType, Type};
+use syn::punctuated::Punctuated;
+use syn::{GenericArgument, PathArguments, ReturnType, Token, Type};
 
 /// 错误处理器
 /// 
@@ -9,7 +10,11 @@ pub struct ErrorHandler;
 impl ErrorHandler {
     /// 验证并解析返回类型
     /// 
-    /// 确保函数返回类型为 `anyhow::Result<T>` 格式，并提取 Ok 和 Err 类型。
+    /// 支持以下返回类型，并提取 Ok 和 Err 类型：
+    /// - `anyhow::Result<T>` 与 `anyhow::Result<T, E>`
+    /// - `Result<T, E>`（包括 `Result<T, SwanError>`），其中 `E: From<SwanError>`
+    /// 
+    /// 错误类型约束由生成代码中的 `?` 转换在编译期检查。
     /// 
     /// # 参数
     /// 
@@ -26,67 +31,73 @@ impl ErrorHandler {
                 let type_path = match &**ty {
                     Type::Path(type_path) => type_path,
                     _ => {
-                        return Err(syn::Error::new_spanned(ty, "Return type must be anyhow::Result<T>"));
+                        return Err(syn::Error::new_spanned(ty, RETURN_TYPE_HINT));
                     }
                 };
 
-                Self::validate_anyhow_result_path(type_path)?;
-                let ok_type = Self::extract_ok_type(type_path, ty)?;
-                let err_type = quote! { anyhow::Error };
+                Self::validate_result_path(type_path)?;
+                let args = Self::extract_generic_args(type_path, ty)?;
+                let ok_type = &args[0];
+                let err_type = match args.get(1) {
+                    Some(err_type) => quote! { #err_type },
+                    None => quote! { anyhow::Error },
+                };
 
                 Ok((ok_type, err_type))
             }
-            _ => Err(syn::Error::new_spanned(output, "Function must return anyhow::Result<T>")),
+            _ => Err(syn::Error::new_spanned(output, RETURN_TYPE_HINT)),
         }
     }
 
-    /// 验证路径是否为 anyhow::Result
-    fn validate_anyhow_result_path(type_path: &syn::TypePath) -> Result<(), syn::Error> {
-        let first_segment = type_path.path.segments.first()
-            .ok_or_else(|| syn::Error::new_spanned(type_path, "Return type path must not be empty"))?;
-
-        if first_segment.ident != "anyhow" {
-            return Err(syn::Error::new_spanned(type_path, "Return type must be anyhow::Result<T>"));
-        }
-
+    /// 验证路径最后一段是否为 Result
+    fn validate_result_path(type_path: &syn::TypePath) -> Result<(), syn::Error> {
         let last_segment = type_path.path.segments.last()
             .ok_or_else(|| syn::Error::new_spanned(type_path, "Return type path must not be empty"))?;
 
         if last_segment.ident != "Result" {
-            return Err(syn::Error::new_spanned(type_path, "Return type must be anyhow::Result<T>"));
+            return Err(syn::Error::new_spanned(type_path, RETURN_TYPE_HINT));
         }
 
         Ok(())
     }
 
-    /// 提取 Ok 类型
-    fn extract_ok_type<'a>(
+    /// 判断路径是否为 anyhow::Result
+    fn is_anyhow_path(type_path: &syn::TypePath) -> bool {
+        type_path.path.segments.first().is_some_and(|segment| segment.ident == "anyhow")
+    }
+
+    /// 提取 Result 的泛型参数
+    /// 
+    /// `anyhow::Result` 的错误类型可省略（默认 `anyhow::Error`），其余 `Result` 必须显式给出错误类型。
+    fn extract_generic_args<'a>(
         type_path: &'a syn::TypePath,
         ty: &'a Type,
-    ) -> Result<&'a GenericArgument, syn::Error> {
+    ) -> Result<&'a Punctuated<GenericArgument, Token![,]>, syn::Error> {
         let last_segment = type_path.path.segments.last().unwrap();
         let args = match &last_segment.arguments {
             PathArguments::AngleBracketed(args) => args,
             _ => {
                 return Err(syn::Error::new_spanned(
                     ty,
-                    "anyhow::Result<T> must have generic arguments",
+                    "Result<T, E> must have generic arguments",
                 ));
             }
         };
 
-        if args.args.len() != 1 {
-            return Err(syn::Error::new_spanned(
-                ty,
-                "anyhow::Result<T> must have exactly 1 type parameter",
-            ));
+        let min_args = if Self::is_anyhow_path(type_path) { 1 } else { 2 };
+        if !(min_args..=2).contains(&args.args.len()) {
+            return Err(syn::Error::new_spanned(ty, RETURN_TYPE_HINT));
         }
 
-        Ok(&args.args[0])
+        Ok(&args.args)
     }
 
 }
 
+/// 返回类型不合法时的提示信息
+const RETURN_TYPE_HINT: &str =
+    "Return type must be anyhow::Result<T>, Result<T, SwanError>, or Result<T, E> where E: From<SwanError>";
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,11 +112,40 @@ mod tests {
 
     #[test]
     fn test_validate_incorrect_return_type() {
-        let return_type: ReturnType = parse_quote! { -> Result<String, Error> };
+        let return_type: ReturnType = parse_quote! { -> Option<String> };
+        let result = ErrorHandler::validate_and_extract_return_types(&return_type);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_typed_error_return_type() {
+        let return_type: ReturnType = parse_quote! { -> Result<String, SwanError> };
+        let (ok_type, err_type) = ErrorHandler::validate_and_extract_return_types(&return_type).unwrap();
+        assert_eq!(quote! { #ok_type }.to_string(), "String");
+        assert_eq!(err_type.to_string(), "SwanError");
+
+        let return_type: ReturnType = parse_quote! { -> std::result::Result<u32, MyError> };
+        let (_, err_type) = ErrorHandler::validate_and_extract_return_types(&return_type).unwrap();
+        assert_eq!(err_type.to_string(), "MyError");
+    }
+
+    #[test]
+    fn test_validate_result_without_error_type() {
+        let return_type: ReturnType = parse_quote! { -> Result<String> };
         let result = ErrorHandler::validate_and_extract_return_types(&return_type);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_anyhow_result_with_two_params() {
+        let return_type: ReturnType = parse_quote! { -> anyhow::Result<String, MyError> };
+        let (_, err_type) = ErrorHandler::validate_and_extract_return_types(&return_type).unwrap();
+        assert_eq!(err_type.to_string(), "MyError");
+
+        let return_type: ReturnType = parse_quote! { -> anyhow::Result<String, MyError, Extra> };
+        assert!(ErrorHandler::validate_and_extract_return_types(&return_type).is_err());
+    }
+
     #[test]
     fn test_validate_no_return_type() {
         let return_type = ReturnType::Default;
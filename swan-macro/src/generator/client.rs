@@ -1,6 +1,9 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use swan_common::{HttpClientArgs, ProxyConfig, ProxyType};
+use swan_common::cookies::CookieConfig;
+use swan_common::tls::TlsVersion;
+use crate::request::{AuthProcessor, CompressionProcessor, RateLimitProcessor, SignerProcessor};
 use syn::ItemStruct;
 
 /// 生成 HTTP 客户端的实现代码
@@ -37,30 +40,39 @@ pub fn generate_http_client_impl(
     let interceptor = &args.interceptor;
 
     // 生成字段（根据是否有状态来决定拦截器类型）
-    let fields = if let Some(state_type) = &args.state {
-        syn::parse_quote! {{
-            client: reqwest::Client,
-            base_url: String,
-            global_interceptor: Option<std::sync::Arc<dyn swan_common::SwanStatefulInterceptor<#state_type> + Send + Sync>>,
-            interceptor_cache: std::sync::Mutex<swan_common::InterceptorCache>,
-            state: Option<#state_type>,
-        }}
+    let (interceptor_field, state_field_type) = if let Some(state_type) = &args.state {
+        (
+            quote! { global_interceptor: Option<std::sync::Arc<dyn swan_common::SwanStatefulInterceptor<#state_type> + Send + Sync>>, },
+            quote! { #state_type },
+        )
     } else if args.interceptor.is_some() {
-        syn::parse_quote! {{
-            client: reqwest::Client,
-            base_url: String,
-            global_interceptor: Option<std::sync::Arc<dyn swan_common::SwanInterceptor + Send + Sync>>,
-            interceptor_cache: std::sync::Mutex<swan_common::InterceptorCache>,
-            state: Option<()>,
-        }}
+        (
+            quote! { global_interceptor: Option<std::sync::Arc<dyn swan_common::SwanInterceptor + Send + Sync>>, },
+            quote! { () },
+        )
     } else {
-        syn::parse_quote! {{
-            client: reqwest::Client,
-            base_url: String,
-            interceptor_cache: std::sync::Mutex<swan_common::InterceptorCache>,
-            state: Option<()>,
-        }}
+        (quote! {}, quote! { () })
     };
+
+    let fields: syn::FieldsNamed = syn::parse_quote! {{
+        client: reqwest::Client,
+        base_url: String,
+        #interceptor_field
+        interceptor_cache: std::sync::Mutex<swan_common::InterceptorCache>,
+        state: Option<#state_field_type>,
+        transport: Option<swan_common::testing::MockTransport>,
+        circuit_breaker: Option<swan_common::CircuitBreaker>,
+        rate_limiter: Option<swan_common::RateLimiter>,
+        method_rate_limiters: swan_common::rate_limit::MethodRateLimiters,
+        metrics: Option<std::sync::Arc<dyn swan_common::SwanMetrics>>,
+        http_cache: swan_common::cache::HttpCache,
+        coalescer: swan_common::coalesce::Coalescer,
+        auth: Option<std::sync::Arc<dyn swan_common::SwanAuth>>,
+        signer: Option<std::sync::Arc<dyn swan_common::SwanSigner>>,
+        default_headers: reqwest::header::HeaderMap,
+        cookies: Option<swan_common::CookieJar>,
+        method_clients: swan_common::client::MethodClients,
+    }};
     
     input.fields = syn::Fields::Named(fields);
 
@@ -131,8 +143,48 @@ pub fn generate_http_client_impl(
         quote! {}
     };
 
-    // 生成客户端创建代码（根据代理配置）
-    let client_creation = generate_client_creation(&args.proxy)?;
+    // 宏参数中的超时、熔断、限流与指标配置作为构建器默认值
+    let timeout_defaults = generate_timeout_defaults(args);
+    let circuit_breaker_default = generate_circuit_breaker_default(args);
+    let rate_limit_default = args.rate_limit.as_ref().map(|config| {
+        let config = RateLimitProcessor::generate_config(config);
+        quote! { options.rate_limit = Some(#config); }
+    });
+    let metrics_default = args.metrics.as_ref().map(|metrics| quote! {
+        options.metrics = Some(std::sync::Arc::new(<#metrics as Default>::default()));
+    });
+    let auth_default = args.auth.as_ref().map(|config| {
+        let auth = AuthProcessor::generate_provider(config);
+        quote! { options.auth = Some(std::sync::Arc::new(#auth)); }
+    });
+    let signer_default = args.signer.as_ref().map(|config| {
+        let signer = SignerProcessor::generate_signer(config);
+        quote! { options.signer = Some(std::sync::Arc::new(#signer)); }
+    });
+    let cookies_default = args.cookies.as_ref().map(|config| match config {
+        CookieConfig::Memory => quote! { options.cookies = Some(swan_common::CookieJar::new()); },
+        CookieConfig::File(path) => quote! { options.cookies = Some(swan_common::CookieJar::persistent(#path)); },
+    });
+    let tls_default = generate_tls_default(args);
+    let decompress_default = (!args.decompress.is_empty()).then(|| {
+        let encodings = args.decompress.iter().map(|encoding| CompressionProcessor::generate_encoding(*encoding));
+        quote! { options.decompress = vec![#(#encodings),*]; }
+    });
+
+    // 生成运行时构建器
+    let builder_name = syn::Ident::new(&format!("{}Builder", struct_name), struct_name.span());
+    let client_builder = generate_client_builder(&args.proxy)?;
+    let builder_impl = generate_builder_impl(
+        &input.vis,
+        struct_name,
+        &builder_name,
+        &client_builder,
+        &interceptor_init,
+        &state_field_init,
+    );
+
+    // 生成拦截器链调用辅助方法
+    let interceptor_helpers = generate_interceptor_helpers(args);
 
     let expanded = quote! {
         #conditional_trait_export
@@ -140,19 +192,59 @@ pub fn generate_http_client_impl(
         #input
 
         impl #struct_name {
-            /// 创建新的 HTTP 客户端实例
+            /// 使用宏参数提供的配置创建新的 HTTP 客户端实例
+            /// 
+            /// 配置无效时 panic，需要处理错误或在运行时覆盖配置时请使用 `builder()`。
             pub fn new() -> Self {
-                #struct_name {
-                    client: #client_creation,
-                    base_url: #base_url.to_string(),
-                    #interceptor_init
-                    interceptor_cache: std::sync::Mutex::new(swan_common::InterceptorCache::new()),
-                    #state_field_init
-                }
+                Self::builder()
+                    .build()
+                    .unwrap_or_else(|e| panic!("Failed to create HTTP client: {}", e))
+            }
+
+            /// 创建客户端构建器，以宏参数作为默认配置
+            pub fn builder() -> #builder_name {
+                let mut options = swan_common::ClientOptions::new(#base_url);
+                #timeout_defaults
+                #circuit_breaker_default
+                #rate_limit_default
+                #metrics_default
+                #auth_default
+                #signer_default
+                #cookies_default
+                #tls_default
+                #decompress_default
+                #builder_name { options }
+            }
+
+            /// 创建使用模拟传输层的客户端（用于测试）
+            /// 
+            /// 请求交给 `transport` 处理，不会打开任何套接字。
+            /// 未配置 `base_url` 时使用 `http://mock.local`。
+            /// 不依赖 `cfg(test)`，因此库中定义的客户端也可在 `tests/*.rs` 或下游 crate 中模拟。
+            pub fn with_mock_transport(transport: swan_common::testing::MockTransport) -> Self {
+                Self::builder()
+                    .mock_transport(transport)
+                    .build()
+                    .unwrap_or_else(|e| panic!("Failed to create HTTP client: {}", e))
             }
 
             #with_state_method
 
+            /// 查询主机的熔断状态
+            /// 
+            /// `host` 为 `host` 或 `host:port`（URL 中显式写出端口时）。
+            /// 未配置熔断器或主机尚无请求时为 `CircuitState::Closed`。
+            pub fn circuit_state(&self, host: &str) -> swan_common::CircuitState {
+                self.circuit_breaker
+                    .as_ref()
+                    .map_or(swan_common::CircuitState::Closed, |breaker| breaker.state(host))
+            }
+
+            /// 客户端使用的 Cookie 存储，用于查看或预置 Cookie；未启用 Cookie 时为 `None`
+            pub fn cookies(&self) -> Option<&swan_common::CookieJar> {
+                self.cookies.as_ref()
+            }
+
             /// 预热拦截器缓存
             /// 
             /// 在客户端创建后调用，可以预先创建常用的拦截器实例，
@@ -165,8 +257,12 @@ pub fn generate_http_client_impl(
                     cache.warmup::<T>();
                 }
             }
+
+            #interceptor_helpers
         }
 
+        #builder_impl
+
         // 为客户端实现状态标识 trait
         impl swan_common::ClientStateMarker for #struct_name {
             type State = #state_type_for_trait;
@@ -177,157 +273,520 @@ pub fn generate_http_client_impl(
     Ok(TokenStream::from(expanded))
 }
 
-/// 生成客户端创建代码（根据代理配置）
-fn generate_client_creation(proxy_config: &Option<ProxyConfig>) -> Result<proc_macro2::TokenStream, syn::Error> {
-    match proxy_config {
-        None => {
-            // 无代理配置，使用默认客户端
-            Ok(quote! { reqwest::Client::new() })
+/// 生成超时默认值设置代码
+fn generate_timeout_defaults(args: &HttpClientArgs) -> proc_macro2::TokenStream {
+    let timeout = args.timeout_ms.map(|timeout_ms| quote! {
+        options.timeout = Some(std::time::Duration::from_millis(#timeout_ms));
+    });
+    let connect_timeout = args.connect_timeout_ms.map(|timeout_ms| quote! {
+        options.connect_timeout = Some(std::time::Duration::from_millis(#timeout_ms));
+    });
+
+    quote! {
+        #timeout
+        #connect_timeout
+    }
+}
+
+/// 生成熔断器默认配置设置代码
+fn generate_circuit_breaker_default(args: &HttpClientArgs) -> proc_macro2::TokenStream {
+    match &args.circuit_breaker {
+        Some(config) => {
+            let failures = config.failures;
+            let window_ms = config.window.as_millis() as u64;
+            let cooldown_ms = config.cooldown.as_millis() as u64;
+            quote! {
+                options.circuit_breaker = Some(swan_common::CircuitBreakerConfig::new(
+                    #failures,
+                    std::time::Duration::from_millis(#window_ms),
+                    std::time::Duration::from_millis(#cooldown_ms),
+                ));
+            }
         }
-        Some(ProxyConfig::Disabled(_)) => {
-            // 明确禁用代理
-            Ok(quote! {
-                reqwest::Client::builder()
-                    .no_proxy()
-                    .build()
-                    .unwrap_or_else(|e| panic!("Failed to create HTTP client with no proxy: {}", e))
-            })
+        None => quote! {},
+    }
+}
+
+/// 生成宏参数中的 TLS 配置
+fn generate_tls_default(args: &HttpClientArgs) -> proc_macro2::TokenStream {
+    let Some(config) = &args.tls else {
+        return quote! {};
+    };
+    let ca = config.ca.iter().map(|path| path.to_string_lossy().into_owned());
+    let identity = config.identity.as_ref().map(|path| {
+        let path = path.to_string_lossy().into_owned();
+        quote! { .identity(#path) }
+    });
+    let identity_password = config.identity_password.as_ref().map(|password| {
+        let password = AuthProcessor::generate_secret(password);
+        quote! { .identity_password(#password) }
+    });
+    let min_version = config.min_version.map(|version| match version {
+        TlsVersion::Tls12 => quote! { .min_version(swan_common::TlsVersion::Tls12) },
+        TlsVersion::Tls13 => quote! { .min_version(swan_common::TlsVersion::Tls13) },
+    });
+    let danger_accept_invalid_certs = config.danger_accept_invalid_certs;
+    let pins = &config.pins;
+    quote! {
+        options.tls = Some(
+            swan_common::TlsConfig::new()
+                #(.ca(#ca))*
+                #identity
+                #identity_password
+                #min_version
+                .danger_accept_invalid_certs(#danger_accept_invalid_certs)
+                #(.pin(#pins))*
+        );
+    }
+}
+
+/// 生成拦截器链调用辅助方法
+///
+/// 方法宏在展开时无法得知客户端是否带有状态，因此由客户端宏生成这些辅助方法：
+/// 方法级拦截器通过泛型方法调用，其 trait 约束（`SwanInterceptor` 或
+/// `SwanStatefulInterceptor<State>`）由客户端的状态配置决定。
+fn generate_interceptor_helpers(args: &HttpClientArgs) -> proc_macro2::TokenStream {
+    let method_helpers = if let Some(state_type) = &args.state {
+        quote! {
+            #[doc(hidden)]
+            pub async fn __swan_before_request<I>(
+                &self,
+                interceptor: &I,
+                request: reqwest::Request,
+            ) -> Result<reqwest::Request, swan_common::SwanError>
+            where
+                I: swan_common::SwanStatefulInterceptor<#state_type> + ?Sized,
+            {
+                swan_common::interceptor::apply_stateful_before_request(
+                    &self.client,
+                    interceptor,
+                    request,
+                    self.state.as_ref(),
+                ).await
+            }
+
+            #[doc(hidden)]
+            pub async fn __swan_after_response<I>(
+                &self,
+                interceptor: &I,
+                response: reqwest::Response,
+            ) -> Result<reqwest::Response, swan_common::SwanError>
+            where
+                I: swan_common::SwanStatefulInterceptor<#state_type> + ?Sized,
+            {
+                swan_common::SwanStatefulInterceptor::after_response(interceptor, response, self.state.as_ref())
+                    .await
+                    .map_err(swan_common::SwanError::Interceptor)
+            }
         }
-        Some(proxy_config @ ProxyConfig::Simple(_)) => {
-            let url = proxy_config.url().unwrap();
-            let url_value = &url.value();
-            
-            match proxy_config.infer_proxy_type() {
-                Some(ProxyType::Http) => {
-                    Ok(quote! {
-                        {
-                            let proxy_url = #url_value;
-                            let proxy = reqwest::Proxy::all(proxy_url)
-                                .unwrap_or_else(|e| panic!("Invalid HTTP proxy URL '{}': {}", proxy_url, e));
-                            
-                            reqwest::Client::builder()
-                                .proxy(proxy)
-                                .build()
-                                .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                        }
-                    })
+    } else {
+        quote! {
+            #[doc(hidden)]
+            pub async fn __swan_before_request<I>(
+                &self,
+                interceptor: &I,
+                request: reqwest::Request,
+            ) -> Result<reqwest::Request, swan_common::SwanError>
+            where
+                I: swan_common::SwanInterceptor + ?Sized,
+            {
+                swan_common::interceptor::apply_before_request(&self.client, interceptor, request).await
+            }
+
+            #[doc(hidden)]
+            pub async fn __swan_after_response<I>(
+                &self,
+                interceptor: &I,
+                response: reqwest::Response,
+            ) -> Result<reqwest::Response, swan_common::SwanError>
+            where
+                I: swan_common::SwanInterceptor + ?Sized,
+            {
+                swan_common::SwanInterceptor::after_response(interceptor, response)
+                    .await
+                    .map_err(swan_common::SwanError::Interceptor)
+            }
+        }
+    };
+
+    let (global_before, global_after) = if args.interceptor.is_some() {
+        (
+            quote! {
+                match &self.global_interceptor {
+                    Some(interceptor) => self.__swan_before_request(interceptor.as_ref(), request).await,
+                    None => Ok(request),
                 }
-                Some(ProxyType::Socks5) => {
-                    Ok(quote! {
-                        {
-                            let proxy_url = #url_value;
-                            let proxy = reqwest::Proxy::all(proxy_url)
-                                .unwrap_or_else(|e| panic!("Invalid SOCKS5 proxy URL '{}': {}", proxy_url, e));
-                            
-                            reqwest::Client::builder()
-                                .proxy(proxy)
-                                .build()
-                                .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                        }
-                    })
+            },
+            quote! {
+                match &self.global_interceptor {
+                    Some(interceptor) => self.__swan_after_response(interceptor.as_ref(), response).await,
+                    None => Ok(response),
                 }
-                None => {
-                    Err(syn::Error::new_spanned(
-                        url,
-                        "Cannot infer proxy type from URL. Use proxy(type = http/socks5, url = \"...\") format or ensure URL starts with http://, https://, or socks5://"
-                    ))
+            },
+        )
+    } else {
+        // 无全局拦截器：直接透传
+        (quote! { Ok(request) }, quote! { Ok(response) })
+    };
+
+    quote! {
+        #method_helpers
+
+        /// 发送一次尝试：配置了认证时先添加凭据，响应为 401 且凭据已刷新时重新授权并重发一次
+        #[doc(hidden)]
+        pub async fn __swan_execute(
+            &self,
+            client: &reqwest::Client,
+            mut request: reqwest::Request,
+            method_rate_limiter: Option<&swan_common::RateLimiter>,
+            call_span: &swan_common::telemetry::CallSpan,
+            call_metrics: &swan_common::metrics::CallMetrics<'_>,
+        ) -> Result<reqwest::Response, swan_common::SwanError> {
+            let auth = match &self.auth {
+                Some(auth) => auth,
+                None => return self.__swan_send(client, request, method_rate_limiter, call_span, call_metrics).await,
+            };
+
+            let auth_client = swan_common::auth::AuthClient::new(client, self.transport.as_ref());
+            auth.authorize(&auth_client, &mut request).await?;
+            let rejected = request.try_clone();
+            let response = self.__swan_send(client, request, method_rate_limiter, call_span, call_metrics).await?;
+
+            let mut replay = match rejected {
+                Some(replay) if response.status() == reqwest::StatusCode::UNAUTHORIZED => replay,
+                _ => return Ok(response),
+            };
+            if !auth.refresh(&auth_client, &replay).await? {
+                return Ok(response);
+            }
+            auth.authorize(&auth_client, &mut replay).await?;
+            self.__swan_send(client, replay, method_rate_limiter, call_span, call_metrics).await
+        }
+
+        /// 按实际发送的样子复制请求，供 HTTP 缓存计算缓存键
+        ///
+        /// 补齐客户端默认请求头，加上认证凭据与 Cookie 存储中的 Cookie，
+        /// 使不同身份的调用不会共享缓存的响应。
+        #[doc(hidden)]
+        pub async fn __swan_cache_identity(
+            &self,
+            client: &reqwest::Client,
+            request: &reqwest::Request,
+        ) -> Result<reqwest::Request, swan_common::SwanError> {
+            let mut identity = request.try_clone()
+                .ok_or_else(|| swan_common::SwanError::request("Cached request body cannot be cloned"))?;
+            for (name, value) in &self.default_headers {
+                if let reqwest::header::Entry::Vacant(entry) = identity.headers_mut().entry(name) {
+                    entry.insert(value.clone());
                 }
             }
+            if let Some(auth) = &self.auth {
+                let auth_client = swan_common::auth::AuthClient::new(client, self.transport.as_ref());
+                auth.authorize(&auth_client, &mut identity).await?;
+            }
+            if let Some(cookies) = &self.cookies
+                && let Some(cookie) = reqwest::cookie::CookieStore::cookies(cookies, identity.url())
+                && let reqwest::header::Entry::Vacant(entry) = identity.headers_mut().entry(reqwest::header::COOKIE)
+            {
+                entry.insert(cookie);
+            }
+            Ok(identity)
         }
-        Some(proxy_config @ ProxyConfig::Full { url, username, password, no_proxy, .. }) => {
-            let url_value = &url.value();
-            let username_value = username.as_ref().map(|u| u.value());
-            let password_value = password.as_ref().map(|p| p.value());
-            let no_proxy_value = no_proxy.as_ref().map(|np| np.value());
-
-            match proxy_config.infer_proxy_type() {
-                Some(ProxyType::Http) => {
-                    // HTTP 代理配置
-                    let auth_code = match (username_value, password_value) {
-                        (Some(username), Some(password)) => {
-                            quote! {
-                                proxy = proxy.basic_auth(#username, #password);
-                            }
-                        }
-                        _ => quote! {}
-                    };
-                    
-                    let no_proxy_code = match no_proxy_value {
-                        Some(no_proxy_domains) => {
-                            quote! {
-                                eprintln!("Warning: no_proxy configuration '{}' is not directly supported by reqwest. Consider using environment variables.", #no_proxy_domains);
-                            }
-                        }
-                        None => quote! {}
-                    };
 
-                    Ok(quote! {
-                        {
-                            let proxy_url = #url_value;
-                            let mut proxy = reqwest::Proxy::all(proxy_url)
-                                .unwrap_or_else(|e| panic!("Invalid HTTP proxy URL '{}': {}", proxy_url, e));
+        /// 发送请求：配置了模拟传输层时交给模拟传输层，否则使用 `client` 发送
+        /// 
+        /// 每次尝试先获取方法级与客户端级限流令牌；配置了熔断器时，
+        /// 再获取目标主机的许可并记录结果。配置了签名器时，在发送前补齐客户端默认请求头
+        /// 并对最终请求签名，限流等待不会使签名时间戳过期，每次发送（包括重试）都重新签名。
+        /// 整个尝试在 `call_span` 的子 span 内执行，结束后向 `call_metrics` 报告状态码、耗时与错误类别。
+        #[doc(hidden)]
+        pub async fn __swan_send(
+            &self,
+            client: &reqwest::Client,
+            mut request: reqwest::Request,
+            method_rate_limiter: Option<&swan_common::RateLimiter>,
+            call_span: &swan_common::telemetry::CallSpan,
+            call_metrics: &swan_common::metrics::CallMetrics<'_>,
+        ) -> Result<reqwest::Response, swan_common::SwanError> {
+            let redacted_query = self.auth.as_deref().map_or(&[][..], |auth| auth.query_params());
+            let attempt = call_span.attempt(&request, redacted_query);
+            attempt.inject(&mut request);
+            let attempt_started_at = call_metrics.attempt_start();
+
+            let result = attempt.run(async {
+                if let Some(limiter) = method_rate_limiter {
+                    limiter.acquire().await?;
+                }
+                if let Some(limiter) = &self.rate_limiter {
+                    limiter.acquire().await?;
+                }
 
-                            #auth_code
+                let permit = match &self.circuit_breaker {
+                    Some(breaker) => Some(breaker.acquire(request.url())?),
+                    None => None,
+                };
 
-                            let client_builder = reqwest::Client::builder().proxy(proxy);
+                if let Some(signer) = &self.signer {
+                    // reqwest 发送时才合并默认请求头（已有的同名请求头优先），签名前先按同样规则补齐
+                    for (name, value) in &self.default_headers {
+                        if let reqwest::header::Entry::Vacant(entry) = request.headers_mut().entry(name) {
+                            entry.insert(value.clone());
+                        }
+                    }
+                    signer.sign(&mut swan_common::signing::SigningRequest::new(&mut request)).await?;
+                }
 
-                            #no_proxy_code
+                let result = match &self.transport {
+                    Some(transport) => transport.execute(request).await,
+                    None => client.execute(request).await.map_err(swan_common::SwanError::from),
+                };
 
-                            client_builder
-                                .build()
-                                .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                        }
-                    })
+                if let Some(permit) = permit {
+                    permit.record(&result);
                 }
-                Some(ProxyType::Socks5) => {
-                    // SOCKS5 代理配置
-                    let auth_code = match (username_value, password_value) {
-                        (Some(username), Some(password)) => {
-                            quote! {
-                                proxy = proxy.basic_auth(#username, #password);
-                            }
-                        }
-                        _ => quote! {}
-                    };
-                    
-                    let no_proxy_code = match no_proxy_value {
-                        Some(no_proxy_domains) => {
-                            quote! {
-                                eprintln!("Warning: no_proxy configuration '{}' is not directly supported by reqwest. Consider using environment variables.", #no_proxy_domains);
-                            }
-                        }
-                        None => quote! {}
-                    };
+                result
+            }).await;
 
-                    Ok(quote! {
-                        {
-                            let proxy_url = #url_value;
-                            let mut proxy = reqwest::Proxy::all(proxy_url)
-                                .unwrap_or_else(|e| panic!("Invalid SOCKS5 proxy URL '{}': {}", proxy_url, e));
+            attempt.record(&result);
+            call_metrics.attempt_end(attempt_started_at, &result);
+            result
+        }
 
-                            #auth_code
+        /// 执行全局拦截器的请求前处理
+        #[doc(hidden)]
+        pub async fn __swan_global_before_request(
+            &self,
+            request: reqwest::Request,
+        ) -> Result<reqwest::Request, swan_common::SwanError> {
+            #global_before
+        }
 
-                            let client_builder = reqwest::Client::builder().proxy(proxy);
+        /// 执行全局拦截器的响应后处理
+        #[doc(hidden)]
+        pub async fn __swan_global_after_response(
+            &self,
+            response: reqwest::Response,
+        ) -> Result<reqwest::Response, swan_common::SwanError> {
+            #global_after
+        }
+    }
+}
 
-                            #no_proxy_code
+/// 生成客户端运行时构建器
+/// 
+/// 构建器持有 `swan_common::ClientOptions`，默认值来自宏参数，
+/// `build()` 时先应用代理配置，再应用运行时配置。
+fn generate_builder_impl(
+    vis: &syn::Visibility,
+    struct_name: &syn::Ident,
+    builder_name: &syn::Ident,
+    client_builder: &proc_macro2::TokenStream,
+    interceptor_init: &proc_macro2::TokenStream,
+    state_field_init: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let doc = format!("[`{}`] 的运行时构建器", struct_name);
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #builder_name {
+            options: swan_common::ClientOptions,
+        }
 
-                            client_builder
-                                .build()
-                                .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                        }
-                    })
-                }
-                None => {
-                    Err(syn::Error::new_spanned(
-                        url,
-                        "Cannot infer proxy type. Use proxy(type = http/socks5, url = \"...\") format or ensure URL starts with http://, https://, or socks5://"
-                    ))
+        impl #builder_name {
+            /// 设置基础 URL
+            pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+                self.options.base_url = base_url.into();
+                self
+            }
+
+            /// 设置整个请求（含读取响应体）的超时时间
+            pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+                self.options.timeout = Some(timeout);
+                self
+            }
+
+            /// 设置建立连接的超时时间
+            pub fn connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+                self.options.connect_timeout = Some(timeout);
+                self
+            }
+
+            /// 设置按主机熔断的配置
+            pub fn circuit_breaker(mut self, config: swan_common::CircuitBreakerConfig) -> Self {
+                self.options.circuit_breaker = Some(config);
+                self
+            }
+
+            /// 设置客户端级令牌桶限流
+            pub fn rate_limit(mut self, config: swan_common::RateLimitConfig) -> Self {
+                self.options.rate_limit = Some(config);
+                self
+            }
+
+            /// 设置请求指标钩子
+            pub fn metrics(mut self, metrics: impl swan_common::SwanMetrics + 'static) -> Self {
+                self.options.metrics = Some(std::sync::Arc::new(metrics));
+                self
+            }
+
+            /// 设置客户端级认证，覆盖宏参数中的 `auth`
+            pub fn auth(mut self, auth: impl swan_common::SwanAuth + 'static) -> Self {
+                self.options.auth = Some(std::sync::Arc::new(auth));
+                self
+            }
+
+            /// 设置 Cookie 存储，覆盖宏参数中的 `cookies`
+            /// 
+            /// 传入同一个 `CookieJar` 的克隆可以让多个客户端共享登录态。
+            pub fn cookie_jar(mut self, cookies: swan_common::CookieJar) -> Self {
+                self.options.cookies = Some(cookies);
+                self
+            }
+
+            /// 设置 TLS 配置（CA 证书、客户端证书、最低版本与公钥固定），覆盖宏参数中的 `tls`
+            pub fn tls(mut self, config: swan_common::TlsConfig) -> Self {
+                self.options.tls = Some(config);
+                self
+            }
+
+            /// 设置自动解压的响应编码，覆盖宏参数中的 `decompress`
+            ///
+            /// 同时决定请求的 `Accept-Encoding`；传入空列表则关闭自动解压。
+            pub fn decompress(mut self, encodings: impl IntoIterator<Item = swan_common::ContentEncoding>) -> Self {
+                self.options.decompress = encodings.into_iter().collect();
+                self
+            }
+
+            /// 设置请求签名器，覆盖宏参数中的 `signer`
+            pub fn signer(mut self, signer: impl swan_common::SwanSigner + 'static) -> Self {
+                self.options.signer = Some(std::sync::Arc::new(signer));
+                self
+            }
+
+            /// 设置 `cache` 方法使用的缓存存储，默认为内存 LRU
+            pub fn cache_store(mut self, store: impl swan_common::SwanCacheStore + 'static) -> Self {
+                self.options.cache_store = Some(std::sync::Arc::new(store));
+                self
+            }
+
+            /// 添加每个请求都会携带的默认请求头
+            /// 
+            /// 名称或值不合法时，在 `build()` 时返回错误。
+            pub fn default_header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+                self.options.insert_default_header(name.as_ref(), value.as_ref());
+                self
+            }
+
+            /// 设置 User-Agent 请求头
+            pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+                self.options.user_agent = Some(user_agent.into());
+                self
+            }
+
+            /// 使用模拟传输层代替网络（用于测试）
+            pub fn mock_transport(mut self, transport: swan_common::testing::MockTransport) -> Self {
+                if self.options.base_url.is_empty() {
+                    self.options.base_url = "http://mock.local".to_string();
                 }
+                self.options.transport = Some(transport);
+                self
+            }
+
+            /// 构建客户端
+            pub fn build(self) -> Result<#struct_name, swan_common::SwanError> {
+                let client = self.options
+                    .apply(#client_builder)?
+                    .build()
+                    .map_err(swan_common::SwanError::from)?;
+                let method_clients = swan_common::client::MethodClients::new(self.options.clone());
+                let default_headers = self.options.request_headers();
+
+                Ok(#struct_name {
+                    client,
+                    method_clients,
+                    transport: self.options.mock_transport(),
+                    circuit_breaker: self.options.circuit_breaker.map(swan_common::CircuitBreaker::new),
+                    rate_limiter: self.options.rate_limit.map(swan_common::RateLimiter::new),
+                    method_rate_limiters: Default::default(),
+                    metrics: self.options.metrics,
+                    http_cache: self.options.cache_store
+                        .map(swan_common::cache::HttpCache::new)
+                        .unwrap_or_default(),
+                    coalescer: Default::default(),
+                    auth: self.options.auth,
+                    signer: self.options.signer,
+                    default_headers,
+                    cookies: self.options.cookies,
+                    base_url: self.options.base_url,
+                    #interceptor_init
+                    interceptor_cache: std::sync::Mutex::new(swan_common::InterceptorCache::new()),
+                    #state_field_init
+                })
             }
         }
     }
 }
 
+/// 生成 `reqwest::ClientBuilder` 创建代码（根据代理配置）
+/// 
+/// 生成的表达式位于构建器的 `build()` 方法中，代理地址无效时通过 `?` 返回 `SwanError`。
+fn generate_client_builder(proxy_config: &Option<ProxyConfig>) -> Result<proc_macro2::TokenStream, syn::Error> {
+    match proxy_config {
+        None => {
+            // 无代理配置，使用默认客户端
+            Ok(quote! { reqwest::Client::builder() })
+        }
+        Some(ProxyConfig::Disabled(_)) => {
+            // 明确禁用代理
+            Ok(quote! { reqwest::Client::builder().no_proxy() })
+        }
+        Some(proxy_config) => {
+            let proxy = generate_proxy(proxy_config)?;
+            Ok(quote! { reqwest::Client::builder().proxy(#proxy) })
+        }
+    }
+}
+
+/// 生成构造 `reqwest::Proxy` 的表达式（代理地址无效时通过 `?` 返回 `SwanError`）
+///
+/// 设置了 `no_proxy` 时，列出的主机绕过代理直连，格式与 `NO_PROXY` 环境变量相同。
+pub(crate) fn generate_proxy(proxy_config: &ProxyConfig) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let url = proxy_config.url().unwrap();
+    let url_value = url.value();
+
+    if !matches!(proxy_config.infer_proxy_type(), Some(ProxyType::Http) | Some(ProxyType::Socks5)) {
+        return Err(syn::Error::new_spanned(
+            url,
+            "Cannot infer proxy type from URL. Use proxy(type = http/socks5, url = \"...\") format or ensure URL starts with http://, https://, or socks5://"
+        ));
+    }
+
+    let (auth_code, no_proxy_code) = match proxy_config {
+        ProxyConfig::Full { username, password, no_proxy, .. } => {
+            let auth_code = match (username, password) {
+                (Some(username), Some(password)) => quote! { let proxy = proxy.basic_auth(#username, #password); },
+                _ => quote! {},
+            };
+            let no_proxy_code = match no_proxy {
+                Some(no_proxy) => quote! { let proxy = proxy.no_proxy(reqwest::NoProxy::from_string(#no_proxy)); },
+                None => quote! {},
+            };
+            (auth_code, no_proxy_code)
+        }
+        _ => (quote! {}, quote! {}),
+    };
+
+    Ok(quote! {
+        {
+            let proxy = reqwest::Proxy::all(#url_value)
+                .map_err(swan_common::SwanError::from)?;
+            #auth_code
+            #no_proxy_code
+            proxy
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,6 +804,16 @@ mod tests {
             interceptor: None,
             state: None,
             proxy: None,
+            timeout_ms: None,
+            connect_timeout_ms: None,
+            circuit_breaker: None,
+            rate_limit: None,
+            metrics: None,
+            auth: None,
+            signer: None,
+            cookies: None,
+            tls: None,
+            decompress: Vec::new(),
         };
 
         // 测试基本验证逻辑，不依赖TokenStream
@@ -366,6 +835,16 @@ mod tests {
             interceptor: None,
             state: None,
             proxy: None,
+            timeout_ms: None,
+            connect_timeout_ms: None,
+            circuit_breaker: None,
+            rate_limit: None,
+            metrics: None,
+            auth: None,
+            signer: None,
+            cookies: None,
+            tls: None,
+            decompress: Vec::new(),
         };
 
         // 测试验证逻辑，应该检测到非空结构体
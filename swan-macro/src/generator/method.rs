@@ -1,10 +1,14 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use swan_common::{HandlerArgs, ProxyConfig, ProxyType};
+use swan_common::{HandlerArgs, ProxyConfig};
 use syn::{FnArg, Signature};
-use crate::conversion::generate_type_conversion;
+use crate::conversion::{
+    extract_response_body_type, generate_download_conversion, generate_response_wrapping,
+    generate_stream_conversion, generate_type_conversion,
+};
 use crate::error::ErrorHandler;
-use crate::request::{RequestBuilder, CachedInterceptorProcessor, RetryProcessor};
+use super::client::generate_proxy;
+use crate::request::{RequestBuilder, CachedInterceptorProcessor, CoalesceProcessor, CompressionProcessor, HttpCacheProcessor, MethodParams, PaginationProcessor, RateLimitProcessor, RetryProcessor};
 use crate::optimization::ConditionalOptimizer;
 
 /// 生成 HTTP 方法的实现代码
@@ -22,13 +26,13 @@ use crate::optimization::ConditionalOptimizer;
 /// 
 /// # 注意
 /// 
-/// 目前暂时使用统一的接口，未来需要改进以支持编译时 trait 检测
+/// 方法宏无法得知客户端是否带状态，拦截器调用统一委托给 `#[http_client]`
+/// 生成的 `__swan_*` 辅助方法，由其决定使用 `SwanInterceptor` 还是 `SwanStatefulInterceptor`
 pub fn generate_http_method(fn_sig: &Signature, handler_args: &HandlerArgs) -> TokenStream {
-    // 检查是否在有状态的客户端上下文中 - 通过查看 self 的预期字段类型
-    generate_http_method_impl(fn_sig, handler_args, None)
+    generate_http_method_impl(fn_sig, handler_args)
 }
 
-pub fn generate_http_method_impl(fn_sig: &Signature, handler_args: &HandlerArgs, client_state_type: Option<&syn::Type>) -> TokenStream {
+pub fn generate_http_method_impl(fn_sig: &Signature, handler_args: &HandlerArgs) -> TokenStream {
     let fn_name = &fn_sig.ident;
     let inputs = &fn_sig.inputs;
     let output = &fn_sig.output;
@@ -38,199 +42,285 @@ pub fn generate_http_method_impl(fn_sig: &Signature, handler_args: &HandlerArgs,
         return error.to_compile_error().into();
     }
 
-    // 验证并提取返回类型
-    let (ok_type, _err_type) = match ErrorHandler::validate_and_extract_return_types(output) {
+    // 只缓存 GET 响应
+    if handler_args.cache.is_some() && handler_args.method != swan_common::HttpMethod::Get {
+        return syn::Error::new_spanned(fn_name, "cache is only supported on GET methods")
+            .to_compile_error()
+            .into();
+    }
+
+    // 只压缩带请求体的方法
+    if handler_args.compress.is_some() && !handler_args.method.allows_body() {
+        return syn::Error::new_spanned(fn_name, "compress is only supported on methods with a request body")
+            .to_compile_error()
+            .into();
+    }
+
+    // 只合并 GET 请求；下载到文件的调用各自写入不同的文件，不能共享结果
+    if handler_args.coalesce.is_some() {
+        let message = if handler_args.method != swan_common::HttpMethod::Get {
+            Some("coalesce is only supported on GET methods")
+        } else if handler_args.download_to.is_some() {
+            Some("coalesce cannot be combined with download_to")
+        } else {
+            None
+        };
+        if let Some(message) = message {
+            return syn::Error::new_spanned(fn_name, message).to_compile_error().into();
+        }
+    }
+
+    // 分页方法返回 `impl Stream`，按 `Item` 校验单个条目的类型
+    let item_output = match &handler_args.paginate {
+        Some(_) => match validate_paginated_method(fn_sig, handler_args) {
+            Ok(item_output) => Some(item_output),
+            Err(error) => return error.to_compile_error().into(),
+        },
+        None => None,
+    };
+
+    // 验证并提取返回类型（错误类型需满足 `From<SwanError>`，由 `?` 完成转换）
+    let (ok_type, err_type) = match ErrorHandler::validate_and_extract_return_types(item_output.as_ref().unwrap_or(output)) {
         Ok(types) => types,
         Err(error) => return error.to_compile_error().into(),
     };
 
-    // 生成函数参数和请求体处理代码
-    let (_body_type, body_param, body_method_call) = generate_body_handling(inputs, handler_args);
+    // 分析参数角色（路径、查询、请求头、请求体）并生成函数参数
+    let params = match MethodParams::analyze(inputs, handler_args) {
+        Ok(params) => params,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let fn_params = params.signature();
 
     // 生成缓存式拦截器处理代码 - 传递状态类型信息
-    let method_interceptor_access = CachedInterceptorProcessor::generate_cached_interceptor_access(&handler_args.interceptor, client_state_type);
+    let method_interceptor_access = CachedInterceptorProcessor::generate_cached_interceptor_access(&handler_args.interceptor);
     
     // 生成客户端选择代码（根据方法级代理配置）
-    let client_selection = match generate_client_selection(&handler_args.proxy) {
+    let client_selection = match generate_client_selection(fn_name, &handler_args.proxy) {
         Ok(code) => code,
         Err(error) => return error.to_compile_error().into(),
     };
+    let (async_client_selection, paginated_client_selection) = match &client_selection {
+        Some(client) => (quote! { &#client? }, quote! { #client }),
+        None => (quote! { &self.client }, quote! { Ok(self.client.clone()) }),
+    };
     
-    let request_builder_code = RequestBuilder::generate_request_builder_code(handler_args, &body_method_call, inputs);
-
-    // 生成类型转换代码
-    let type_conversion = generate_type_conversion(ok_type);
-
-    // 生成延迟序列化代码
+    let request_builder_code = RequestBuilder::generate_request_builder_code(handler_args, &params, inputs);
+
+    // 返回 `Response<T>` 时对 `T` 解码，并包装响应元信息
+    let (body_type, started_at, capture_metadata, wrap_response) = match extract_response_body_type(ok_type) {
+        Some(body_type) => {
+            let (capture, wrap) = generate_response_wrapping();
+            let started_at = quote! { let started_at = std::time::Instant::now(); };
+            (body_type, started_at, capture, wrap)
+        }
+        None => (ok_type, quote! {}, quote! {}, quote! {}),
+    };
 
     // 生成条件编译优化代码
     let conditional_logging = ConditionalOptimizer::generate_conditional_logging();
     let conditional_response_logging = ConditionalOptimizer::generate_conditional_response_logging();
+
+    // 生成响应体读取代码：写入文件、流式返回，或整体读取后进行类型转换
+    let read_body = if let Some(path) = &handler_args.download_to {
+        match generate_download_conversion(path, body_type) {
+            Ok(download_conversion) => download_conversion,
+            Err(error) => return error.to_compile_error().into(),
+        }
+    } else {
+        match generate_stream_conversion(body_type) {
+            Some(Ok(stream_conversion)) => stream_conversion,
+            Some(Err(error)) => return error.to_compile_error().into(),
+            None => {
+                let type_conversion = generate_type_conversion(body_type);
+                quote! {{
+                    let bytes = response.bytes().await.map_err(swan_common::SwanError::from)?;
+                    let result = #type_conversion;
+                    #conditional_response_logging
+                    result
+                }}
+            }
+        }
+    };
     
+    // 调用 span（启用 swan-common 的 `tracing` 特性时生效）与指标记录器使用方法名标识调用
+    let call_name = fn_name.to_string();
+
+    // 生成方法级限流器获取代码（每次尝试都会消耗令牌）
+    let method_rate_limiter = RateLimitProcessor::generate_method_limiter(fn_name, &handler_args.rate_limit);
+
     // 生成重试执行代码
-    let retry_execution = RetryProcessor::generate_complete_retry_block(&handler_args.retry, &handler_args.method);
+    let retry_execution = RetryProcessor::generate_complete_retry_block(
+        &handler_args.retry,
+        &handler_args.retry_predicate,
+        &handler_args.method,
+    );
+    // 标注了 `cache` 时在发送前查询缓存，并在收到响应后更新缓存
+    let execution = HttpCacheProcessor::generate_cached_execution(&handler_args.cache, retry_execution);
+
+    // 生成拦截器链调用代码
+    let before_request_chain = CachedInterceptorProcessor::generate_before_request_chain(&handler_args.interceptor);
+    let after_response_chain = CachedInterceptorProcessor::generate_after_response_chain(&handler_args.interceptor);
+    // 标注了 `compress` 时在拦截器之后压缩请求体
+    let compression = CompressionProcessor::generate_compression(&handler_args.compress);
+
+    // 标注了 `paginate` 时逐页发送请求，每一页都经过拦截器、限流与重试
+    if let Some(pagination) = &handler_args.paginate {
+        let fetch_page = quote! {
+            // 请求前拦截器链：全局 -> 方法级
+            #before_request_chain
+            #compression
+
+            let call_span = swan_common::telemetry::CallSpan::new(#call_name, &request);
+            let call_metrics = swan_common::metrics::CallMetrics::start(self.metrics.as_deref(), #call_name, &request);
+
+            #method_rate_limiter
+            #execution
+
+            // 响应后拦截器链：方法级 -> 全局
+            #after_response_chain
+        };
+        let stream = PaginationProcessor::generate_paginated_stream(
+            pagination,
+            ok_type,
+            &err_type,
+            handler_args.interceptor.is_some(),
+            fetch_page,
+        );
+
+        let expanded = quote! {
+            pub fn #fn_name(&self #fn_params) #output {
+
+                let effective_client: Result<reqwest::Client, swan_common::SwanError> = #paginated_client_selection;
+
+                // 第一页的请求；之后每一页由它复制并修改查询参数或 URL
+                let first_request: Result<reqwest::Request, swan_common::SwanError> = (|| {
+                    let effective_client = effective_client.as_ref().unwrap_or(&self.client);
+
+                    #request_builder_code
+
+                    #conditional_logging
+
+                    request_builder.build().map_err(swan_common::SwanError::from)
+                })();
+                // 方法客户端创建失败时，错误作为第一页的结果返回
+                let (effective_client, first_request) = match effective_client {
+                    Ok(effective_client) => (effective_client, first_request),
+                    Err(error) => (self.client.clone(), Err(error)),
+                };
+
+                #method_interceptor_access
+
+                #stream
+            }
+        };
+        return TokenStream::from(expanded);
+    }
 
-    // 生成拦截器调用代码
-    let interceptor_calls = generate_interceptor_calls(&handler_args, client_state_type);
+    let call = quote! {
+        let call_span = swan_common::telemetry::CallSpan::new(#call_name, &request);
+        let call_metrics = swan_common::metrics::CallMetrics::start(self.metrics.as_deref(), #call_name, &request);
+
+        // 执行请求（包含重试逻辑和响应处理）
+        #method_rate_limiter
+        #started_at
+        let result = {
+            #execution
+
+            // 响应后拦截器链：方法级 -> 全局
+            #after_response_chain
+
+            if response.status().is_success() {
+                #capture_metadata
+                let result = #read_body;
+                #wrap_response
+                Ok(result)
+            } else {
+                // 非 2xx 响应：保留状态码、响应头与响应体，转换为方法声明的错误类型
+                Err(swan_common::SwanError::from_response(response).await.into())
+            }
+        };
+
+        result
+    };
+    // 标注了 `coalesce` 时，键相同的并发调用共享同一次发送与解码的结果
+    let call = CoalesceProcessor::generate_coalesced_call(fn_name, &handler_args.coalesce, ok_type, call);
 
     let expanded = quote! {
-        pub async fn #fn_name(&self #body_param) #output {
+        pub async fn #fn_name(&self #fn_params) #output {
 
-            #client_selection
+            let effective_client = #async_client_selection;
 
             #request_builder_code
 
             #conditional_logging
 
-            let request = match request_builder.build() {
-                Ok(req) => req,
-                Err(e) => return Err(anyhow::anyhow!("Failed to build request: {}", e)),
-            };
-
-            // 执行请求（包含重试逻辑和响应处理）
-            let result = {
-                #retry_execution
-                
-                if response.status().is_success() {
-                    let bytes = match response.bytes().await {
-                        Ok(bytes) => bytes,
-                        Err(e) => return Err(anyhow::anyhow!("Failed to read response bytes: {}", e)),
-                    };
+            let request = request_builder.build().map_err(swan_common::SwanError::from)?;
 
-                    let result = #type_conversion;
-                    #conditional_response_logging
-                    Ok(result)
-                } else {
-                    Err(anyhow::anyhow!("Request failed with status: {}", response.status()))
-                }
-            };
-            
-            result
+            // 请求前拦截器链：全局 -> 方法级
+            #method_interceptor_access
+            #before_request_chain
+            #compression
+
+            #call
         }
     };
 
     TokenStream::from(expanded)
 }
 
-/// 生成客户端选择代码（支持方法级代理覆盖）
-fn generate_client_selection(proxy_config: &Option<ProxyConfig>) -> Result<proc_macro2::TokenStream, syn::Error> {
-    match proxy_config {
-        None => {
-            // 无方法级代理配置，使用实例客户端
-            Ok(quote! {
-                let effective_client = &self.client;
-            })
-        }
-        Some(ProxyConfig::Disabled(_)) => {
-            // 方法级禁用代理，创建临时无代理客户端
-            Ok(quote! {
-                let effective_client = &{
-                    static METHOD_CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
-                    METHOD_CLIENT.get_or_init(|| {
-                        reqwest::Client::builder()
-                            .no_proxy()
-                            .build()
-                            .unwrap_or_else(|e| panic!("Failed to create HTTP client with no proxy: {}", e))
-                    })
-                };
-            })
-        }
-        Some(proxy_config @ ProxyConfig::Simple(_)) => {
-            let url = proxy_config.url().unwrap();
-            let url_value = &url.value();
-            
-            match proxy_config.infer_proxy_type() {
-                Some(ProxyType::Http) | Some(ProxyType::Socks5) => {
-                    Ok(quote! {
-                        let effective_client = &{
-                            static METHOD_CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
-                            METHOD_CLIENT.get_or_init(|| {
-                                let proxy_url = #url_value;
-                                let proxy = reqwest::Proxy::all(proxy_url)
-                                    .unwrap_or_else(|e| panic!("Invalid proxy URL '{}': {}", proxy_url, e));
-                                
-                                reqwest::Client::builder()
-                                    .proxy(proxy)
-                                    .build()
-                                    .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                            })
-                        };
-                    })
-                }
-                None => {
-                    Err(syn::Error::new_spanned(
-                        url,
-                        "Cannot infer proxy type from URL. Use proxy(type = http/socks5, url = \"...\") format or ensure URL starts with http://, https://, or socks5://"
-                    ))
-                }
-            }
-        }
-        Some(proxy_config @ ProxyConfig::Full { url, username, password, no_proxy, .. }) => {
-            let url_value = &url.value();
-            let username_value = username.as_ref().map(|u| u.value());
-            let password_value = password.as_ref().map(|p| p.value());
-            let no_proxy_value = no_proxy.as_ref().map(|np| np.value());
-
-            match proxy_config.infer_proxy_type() {
-                Some(ProxyType::Http) | Some(ProxyType::Socks5) => {
-                    // 方法级完整代理配置，创建临时代理客户端
-                    let auth_code = match (username_value, password_value) {
-                        (Some(username), Some(password)) => {
-                            quote! {
-                                proxy = proxy.basic_auth(#username, #password);
-                            }
-                        }
-                        _ => quote! {}
-                    };
-                    
-                    let no_proxy_code = match no_proxy_value {
-                        Some(no_proxy_domains) => {
-                            quote! {
-                                eprintln!("Warning: no_proxy configuration '{}' is not directly supported by reqwest. Consider using environment variables.", #no_proxy_domains);
-                            }
-                        }
-                        None => quote! {}
-                    };
-
-                    Ok(quote! {
-                        let effective_client = &{
-                            static METHOD_CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
-                            METHOD_CLIENT.get_or_init(|| {
-                                let proxy_url = #url_value;
-                                let mut proxy = reqwest::Proxy::all(proxy_url)
-                                    .unwrap_or_else(|e| panic!("Invalid proxy URL '{}': {}", proxy_url, e));
-
-                                #auth_code
-
-                                let client_builder = reqwest::Client::builder().proxy(proxy);
-
-                                #no_proxy_code
-
-                                client_builder
-                                    .build()
-                                    .unwrap_or_else(|e| panic!("Failed to create HTTP client with proxy '{}': {}", proxy_url, e))
-                            })
-                        };
-                    })
-                }
-                None => {
-                    Err(syn::Error::new_spanned(
-                        url,
-                        "Cannot infer proxy type. Use proxy(type = http/socks5, url = \"...\") format or ensure URL starts with http://, https://, or socks5://"
-                    ))
-                }
+/// 生成方法级代理客户端的获取表达式，类型为 `Result<reqwest::Client, SwanError>`
+///
+/// 方法级代理使用客户端实例缓存的独立 `reqwest::Client`，它以实例配置构建，
+/// 因此超时、Cookie、TLS 与自动解压等客户端配置同样生效。代理地址或 TLS 配置无效时
+/// 返回 `SwanError`，由调用方通过 `?` 交给方法的错误类型。没有方法级代理时返回 `None`，
+/// 使用实例客户端。
+fn generate_client_selection(fn_name: &syn::Ident, proxy_config: &Option<ProxyConfig>) -> Result<Option<proc_macro2::TokenStream>, syn::Error> {
+    let key = fn_name.to_string();
+
+    let builder = match proxy_config {
+        None => return Ok(None),
+        // 方法级禁用代理，使用无代理的方法客户端
+        Some(ProxyConfig::Disabled(_)) => quote! { Ok(reqwest::Client::builder().no_proxy()) },
+        Some(proxy_config @ (ProxyConfig::Simple(_) | ProxyConfig::Full { .. })) => {
+            let proxy = generate_proxy(proxy_config)?;
+            quote! {
+                let proxy = #proxy;
+                Ok(reqwest::Client::builder().proxy(proxy))
             }
         }
-    }
+    };
+
+    Ok(Some(quote! {
+        self.method_clients.get_or_build(#key, || { #builder })
+    }))
 }
 
-/// 生成拦截器调用代码
-/// 暂时简化，移除拦截器调用逻辑
-fn generate_interceptor_calls(_handler_args: &HandlerArgs, _client_state_type: Option<&syn::Type>) -> proc_macro2::TokenStream {
-    // 暂时移除拦截器调用，专注于trait导出功能
-    quote! {
-        // 拦截器调用逻辑待后续完善
+/// 验证分页方法的声明并返回单个条目的返回类型
+///
+/// 分页只支持 GET；方法直接返回 `Stream`，因此声明时不带 `async`。
+fn validate_paginated_method(fn_sig: &Signature, handler_args: &HandlerArgs) -> Result<syn::ReturnType, syn::Error> {
+    let message = if handler_args.method != swan_common::HttpMethod::Get {
+        Some("paginate is only supported on GET methods")
+    } else if handler_args.coalesce.is_some() {
+        Some("paginate cannot be combined with coalesce")
+    } else if handler_args.download_to.is_some() {
+        Some("paginate cannot be combined with download_to")
+    } else {
+        None
+    };
+    if let Some(message) = message {
+        return Err(syn::Error::new_spanned(&fn_sig.ident, message));
+    }
+
+    if let Some(asyncness) = &fn_sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "paginated methods return a Stream; declare them without async",
+        ));
     }
+
+    PaginationProcessor::item_return_type(&fn_sig.output)
 }
 
 /// 验证函数输入参数
@@ -251,61 +341,6 @@ fn validate_function_inputs(inputs: &syn::punctuated::Punctuated<FnArg, syn::Tok
 }
 
 
-/// 生成请求体处理代码
-fn generate_body_handling(
-    inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
-    handler_args: &HandlerArgs,
-) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    // 收集所有非self参数
-    let mut param_tokens = Vec::new();
-    let mut body_type = None;
-    let mut body_method_call = quote! {};
-
-    for (index, input) in inputs.iter().skip(1).enumerate() {
-        if let FnArg::Typed(pat_type) = input {
-            if let syn::Pat::Ident(pat_ident) = pat_type.pat.as_ref() {
-                let param_name = &pat_ident.ident;
-                let param_type = &pat_type.ty;
-                
-                param_tokens.push(quote! { , #param_name: #param_type });
-                
-                // 检查是否是body参数（通常是最后一个参数，且方法需要body）
-                if is_body_parameter(handler_args, index, inputs.len() - 2) {
-                    body_type = Some(param_type);
-                    body_method_call = RequestBuilder::generate_body_method_call(
-                        &handler_args.content_type,
-                        &handler_args.method,
-                    );
-                }
-            }
-        }
-    }
-
-    let params = if param_tokens.is_empty() {
-        quote! {}
-    } else {
-        quote! { #(#param_tokens)* }
-    };
-
-    (
-        body_type.map(|t| quote! { #t }).unwrap_or(quote! {}),
-        params,
-        body_method_call,
-    )
-}
-
-/// 判断是否为body参数
-fn is_body_parameter(handler_args: &HandlerArgs, param_index: usize, total_params: usize) -> bool {
-    // 如果方法需要body且这是最后一个参数
-    match handler_args.method {
-        swan_common::HttpMethod::Post | swan_common::HttpMethod::Put => {
-            // POST/PUT 方法的最后一个参数通常是body
-            total_params > 0 && param_index == total_params - 1 && handler_args.content_type.is_some()
-        }
-        _ => false,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
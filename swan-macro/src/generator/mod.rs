@@ -1,5 +1,7 @@
 pub mod client;
 pub mod method;
+pub mod multipart;
 
 pub use client::generate_http_client_impl;
-pub use method::generate_http_method;
\ No newline at end of file
+pub use method::generate_http_method;
+pub use multipart::generate_multipart_impl;
@@ -0,0 +1,229 @@
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// 字段在表单中的编码方式
+#[derive(Debug, PartialEq)]
+enum PartKind {
+    /// 文本字段，通过 `Display` 转为字符串
+    Text,
+    /// 文件字段（`Vec<u8>`、`PathBuf` 或 `FilePart`）
+    File,
+}
+
+/// `#[multipart(...)]` 字段属性
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<LitStr>,
+    file_name: Option<LitStr>,
+    mime: Option<LitStr>,
+}
+
+/// 生成 `SwanMultipart` 派生实现
+/// 
+/// 逐个字段构建 `reqwest::multipart::Form`：
+/// 
+/// - `Vec<u8>`、`PathBuf`、`swan_common::FilePart` 作为文件部分
+/// - `Option<T>` 为 `None` 时跳过该字段
+/// - 其余类型通过 `Display` 作为文本字段
+/// 
+/// 字段可使用 `#[multipart(rename = "...", file_name = "...", mime = "...")]`
+/// 指定表单字段名、文件名与 MIME 类型（后两者仅用于文件部分）。
+pub fn generate_multipart_impl(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(name, "SwanMultipart requires a struct with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(name, "SwanMultipart can only be derived for structs")),
+    };
+
+    let mut statements = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let options = parse_field_options(field)?;
+        let field_name = options.rename.as_ref()
+            .map(LitStr::value)
+            .unwrap_or_else(|| ident.to_string());
+
+        let (inner_type, optional) = match option_inner_type(&field.ty) {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let kind = part_kind(inner_type);
+
+        if kind == PartKind::Text && (options.file_name.is_some() || options.mime.is_some()) {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "file_name and mime are only supported on Vec<u8>, PathBuf or FilePart fields",
+            ));
+        }
+
+        let add_part = match kind {
+            PartKind::Text => quote! { form.text(#field_name, value.to_string()) },
+            PartKind::File => {
+                let file_name = options.file_name.as_ref().map(|file_name| quote! { .file_name(#file_name) });
+                let mime = options.mime.as_ref().map(|mime| quote! { .mime(#mime) });
+                quote! {
+                    form.part(
+                        #field_name,
+                        swan_common::FilePart::from(value) #file_name #mime .into_part().await?,
+                    )
+                }
+            }
+        };
+
+        statements.push(if optional {
+            quote! {
+                let form = match self.#ident {
+                    Some(value) => #add_part,
+                    None => form,
+                };
+            }
+        } else {
+            quote! {
+                let form = {
+                    let value = self.#ident;
+                    #add_part
+                };
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics swan_common::SwanMultipart for #name #ty_generics #where_clause {
+            fn into_form(self) -> impl std::future::Future<
+                Output = Result<reqwest::multipart::Form, swan_common::SwanError>
+            > + Send {
+                async move {
+                    let form = reqwest::multipart::Form::new();
+                    #(#statements)*
+                    Ok(form)
+                }
+            }
+        }
+    })
+}
+
+/// 解析字段上的 `#[multipart(...)]` 属性
+fn parse_field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("multipart")) {
+        attr.parse_nested_meta(|meta| {
+            let slot = if meta.path.is_ident("rename") {
+                &mut options.rename
+            } else if meta.path.is_ident("file_name") {
+                &mut options.file_name
+            } else if meta.path.is_ident("mime") {
+                &mut options.mime
+            } else {
+                return Err(meta.error("expected 'rename', 'file_name' or 'mime'"));
+            };
+            *slot = Some(meta.value()?.parse()?);
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// 根据字段类型判断编码方式
+fn part_kind(ty: &Type) -> PartKind {
+    let Type::Path(type_path) = ty else {
+        return PartKind::Text;
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return PartKind::Text;
+    };
+
+    match last_segment.ident.to_string().as_str() {
+        "PathBuf" | "FilePart" => PartKind::File,
+        "Vec" => match single_type_argument(&last_segment.arguments) {
+            Some(Type::Path(inner)) if inner.path.is_ident("u8") => PartKind::File,
+            _ => PartKind::Text,
+        },
+        _ => PartKind::Text,
+    }
+}
+
+/// 提取 `Option<T>` 中的 `T`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != "Option" {
+        return None;
+    }
+    single_type_argument(&last_segment.arguments)
+}
+
+fn single_type_argument(arguments: &PathArguments) -> Option<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match args.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_part_kind() {
+        assert_eq!(part_kind(&parse_quote! { String }), PartKind::Text);
+        assert_eq!(part_kind(&parse_quote! { u32 }), PartKind::Text);
+        assert_eq!(part_kind(&parse_quote! { Vec<u8> }), PartKind::File);
+        assert_eq!(part_kind(&parse_quote! { Vec<String> }), PartKind::Text);
+        assert_eq!(part_kind(&parse_quote! { std::path::PathBuf }), PartKind::File);
+        assert_eq!(part_kind(&parse_quote! { swan_common::FilePart }), PartKind::File);
+    }
+
+    #[test]
+    fn test_generate_multipart_impl() {
+        let input: DeriveInput = parse_quote! {
+            struct Upload {
+                #[multipart(rename = "caption")]
+                title: String,
+                note: Option<String>,
+                #[multipart(file_name = "a.png", mime = "image/png")]
+                image: Vec<u8>,
+            }
+        };
+
+        let code = generate_multipart_impl(&input).unwrap().to_string();
+
+        assert!(code.contains("\"caption\""));
+        assert!(code.contains("None => form"));
+        assert!(code.contains(". file_name (\"a.png\") . mime (\"image/png\")"));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let tuple: DeriveInput = parse_quote! { struct Upload(String); };
+        assert!(generate_multipart_impl(&tuple).is_err());
+
+        let text_with_mime: DeriveInput = parse_quote! {
+            struct Upload {
+                #[multipart(mime = "text/plain")]
+                title: String,
+            }
+        };
+        assert!(generate_multipart_impl(&text_with_mime).is_err());
+
+        let unknown: DeriveInput = parse_quote! {
+            struct Upload {
+                #[multipart(filename = "a.txt")]
+                file: Vec<u8>,
+            }
+        };
+        assert!(generate_multipart_impl(&unknown).is_err());
+    }
+}
@@ -10,11 +10,11 @@ mod request;
 mod error;
 mod optimization;
 
-use crate::common::common_http_method;
-use crate::generator::generate_http_client_impl;
+use crate::common::{common_http_method, common_request_method};
+use crate::generator::{generate_http_client_impl, generate_multipart_impl};
 use proc_macro::TokenStream;
 use swan_common::{HttpMethod, parse_http_client_args};
-use syn::{ItemStruct, parse_macro_input};
+use syn::{DeriveInput, ItemStruct, parse_macro_input};
 
 /// HTTP 客户端宏
 /// 
@@ -174,3 +174,90 @@ pub fn put(args: TokenStream, item: TokenStream) -> TokenStream {
 pub fn delete(args: TokenStream, item: TokenStream) -> TokenStream {
     common_http_method(args, item, HttpMethod::Delete)
 }
+
+/// PATCH 方法宏
+/// 
+/// 用于为方法生成 PATCH 请求实现，常用于资源的部分更新。
+/// 
+/// # 示例
+/// 
+/// ```rust,ignore
+/// impl ApiClient {
+///     #[patch(url = "/users/{id}", content_type = json)]
+///     async fn update_user(&self, id: u32, body: UserPatch) -> anyhow::Result<User> {}
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn patch(args: TokenStream, item: TokenStream) -> TokenStream {
+    common_http_method(args, item, HttpMethod::Patch)
+}
+
+/// HEAD 方法宏
+/// 
+/// 用于为方法生成 HEAD 请求实现，常用于资源存在性检查。返回类型可使用 `anyhow::Result<()>`。
+#[proc_macro_attribute]
+pub fn head(args: TokenStream, item: TokenStream) -> TokenStream {
+    common_http_method(args, item, HttpMethod::Head)
+}
+
+/// OPTIONS 方法宏
+/// 
+/// 用于为方法生成 OPTIONS 请求实现，例如探测 CORS 预检响应。
+#[proc_macro_attribute]
+pub fn options(args: TokenStream, item: TokenStream) -> TokenStream {
+    common_http_method(args, item, HttpMethod::Options)
+}
+
+/// 通用请求宏
+/// 
+/// 用于没有专用宏的 HTTP 方法（如 WebDAV 的 PROPFIND）。
+/// 
+/// # 参数
+/// 
+/// * `method` - HTTP 方法名（必填，需为合法的 HTTP token）
+/// * 其余参数与 `#[post]` 相同
+/// 
+/// # 示例
+/// 
+/// ```rust,ignore
+/// impl DavClient {
+///     #[request(method = "PROPFIND", url = "/files", header = "Depth: 1")]
+///     async fn list_files(&self) -> anyhow::Result<String> {}
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn request(args: TokenStream, item: TokenStream) -> TokenStream {
+    common_request_method(args, item)
+}
+
+/// multipart 表单派生宏
+/// 
+/// 为结构体实现 `swan_common::SwanMultipart`，用于 `content_type = form_multipart` 方法的请求体。
+/// 
+/// * 文本字段：任意实现 `Display` 的类型
+/// * 文件字段：`Vec<u8>`、`PathBuf`、`swan_common::FilePart`
+/// * `Option<T>` 字段为 `None` 时不发送
+/// * `#[multipart(rename = "...", file_name = "...", mime = "...")]` 指定字段名、文件名与 MIME 类型
+/// 
+/// # 示例
+/// 
+/// ```rust,ignore
+/// #[derive(SwanMultipart)]
+/// struct Avatar {
+///     user_id: u64,
+///     #[multipart(file_name = "avatar.png", mime = "image/png")]
+///     image: Vec<u8>,
+/// }
+/// 
+/// impl ApiClient {
+///     #[post(url = "/avatars", content_type = form_multipart)]
+///     async fn upload(&self, avatar: Avatar) -> anyhow::Result<()> {}
+/// }
+/// ```
+#[proc_macro_derive(SwanMultipart, attributes(multipart))]
+pub fn swan_multipart(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    generate_multipart_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
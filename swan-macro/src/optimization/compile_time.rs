@@ -273,6 +273,14 @@ mod tests {
             interceptor: None,
             retry: None,
             proxy: None,
+            retry_predicate: None,
+            download_to: None,
+            timeout_ms: None,
+            rate_limit: None,
+            cache: None,
+            coalesce: None,
+            paginate: None,
+            compress: None,
         };
         
         let result = CompileTimeOptimizer::generate_request_execution_strategy(&handler_args);
@@ -309,6 +317,14 @@ mod tests {
             interceptor: None,
             retry: None,
             proxy: None,
+            retry_predicate: None,
+            download_to: None,
+            timeout_ms: None,
+            rate_limit: None,
+            cache: None,
+            coalesce: None,
+            paginate: None,
+            compress: None,
         };
         
         let result = CompileTimeOptimizer::generate_compile_time_checks(&handler_args);
@@ -0,0 +1,65 @@
+use quote::quote;
+use swan_common::auth::{ApiKeyLocation, AuthConfig, Secret};
+
+/// 客户端认证代码生成器
+pub struct AuthProcessor;
+
+impl AuthProcessor {
+    /// 生成构造认证提供者的表达式
+    pub fn generate_provider(config: &AuthConfig) -> proc_macro2::TokenStream {
+        match config {
+            AuthConfig::Bearer { token } => {
+                let token = Self::generate_secret(token);
+                quote! { swan_common::auth::BearerAuth::new(#token) }
+            }
+            AuthConfig::Basic { username, password } => {
+                let username = Self::generate_secret(username);
+                let password = Self::generate_secret(password);
+                quote! { swan_common::auth::BasicAuth::new(#username, #password) }
+            }
+            AuthConfig::ApiKey { location, key } => {
+                let key = Self::generate_secret(key);
+                match location {
+                    ApiKeyLocation::Header(name) => quote! { swan_common::auth::ApiKeyAuth::header(#name, #key) },
+                    ApiKeyLocation::Query(name) => quote! { swan_common::auth::ApiKeyAuth::query(#name, #key) },
+                }
+            }
+            AuthConfig::OAuth2ClientCredentials { token_url, client_id, client_secret, scopes } => {
+                let client_id = Self::generate_secret(client_id);
+                let client_secret = Self::generate_secret(client_secret);
+                let scopes = (!scopes.is_empty()).then(|| quote! { .scopes([#(#scopes),*]) });
+                quote! {
+                    swan_common::auth::OAuth2ClientCredentials::new(#token_url, #client_id, #client_secret)
+                        #scopes
+                }
+            }
+        }
+    }
+
+    pub(crate) fn generate_secret(secret: &Secret) -> proc_macro2::TokenStream {
+        match secret {
+            Secret::Value(value) => quote! { swan_common::auth::Secret::Value(#value.to_string()) },
+            Secret::Env(name) => quote! { swan_common::auth::Secret::env(#name) },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_provider() {
+        let bearer = AuthProcessor::generate_provider(&AuthConfig::Bearer { token: Secret::env("API_TOKEN") }).to_string();
+        assert_eq!(bearer, "swan_common :: auth :: BearerAuth :: new (swan_common :: auth :: Secret :: env (\"API_TOKEN\"))");
+
+        let oauth2 = AuthProcessor::generate_provider(&AuthConfig::OAuth2ClientCredentials {
+            token_url: "https://auth.example.com/token".into(),
+            client_id: "swan".into(),
+            client_secret: Secret::env("CLIENT_SECRET"),
+            scopes: vec!["read".into()],
+        }).to_string();
+        assert!(oauth2.contains("OAuth2ClientCredentials :: new (\"https://auth.example.com/token\" , swan_common :: auth :: Secret :: Value (\"swan\" . to_string ())"));
+        assert!(oauth2.ends_with(". scopes ([\"read\"])"));
+    }
+}
@@ -1,7 +1,7 @@
 use quote::quote;
 use syn::{LitStr, FnArg};
 use swan_common::{ContentType, HandlerArgs, HttpMethod};
-use super::DynamicParamsProcessor;
+use super::{DynamicParamsProcessor, MethodParams};
 
 /// 请求构建器
 /// 
@@ -14,7 +14,7 @@ impl RequestBuilder {
     /// # 参数
     /// 
     /// * `handler_args` - 处理器参数
-    /// * `body_method_call` - 请求体方法调用代码
+    /// * `params` - 方法参数绑定（请求体、查询参数、请求头参数）
     /// * `fn_inputs` - 函数参数列表（用于动态参数替换）
     /// 
     /// # 返回值
@@ -22,14 +22,14 @@ impl RequestBuilder {
     /// 生成的请求构建代码
     pub fn generate_request_builder_code(
         handler_args: &HandlerArgs,
-        body_method_call: &proc_macro2::TokenStream,
+        params: &MethodParams,
         fn_inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
     ) -> proc_macro2::TokenStream {
         let url = handler_args.url.value();
         let method = &handler_args.method;
         let headers = &handler_args.headers;
 
-        let method_ident = method.client_method();
+        let reqwest_method = method.reqwest_method();
         
         // 生成动态URL代码
         let url_code = DynamicParamsProcessor::generate_dynamic_url_code(&url, fn_inputs);
@@ -38,21 +38,22 @@ impl RequestBuilder {
         let header_statements = Self::generate_dynamic_header_statements(headers, fn_inputs);
         let content_type_header = Self::generate_content_type_header(&handler_args.content_type);
 
-        // 如果没有 body_method_call，则不添加它
-        let body_call = if body_method_call.is_empty() {
-            quote! {}
-        } else {
-            body_method_call.clone()
-        };
+        let timeout_call = Self::generate_timeout_call(handler_args.timeout_ms);
+
+        let body_call = params.body_method_call(handler_args);
+        let param_statements = params.builder_statements();
 
         quote! {
             #url_code
 
             let request_builder = effective_client
-                .#method_ident(&full_url)
+                .request(#reqwest_method, &full_url)
+                #timeout_call
                 #content_type_header
                 #(#header_statements)*
                 #body_call;
+
+            #param_statements
         }
     }
 
@@ -68,6 +69,14 @@ impl RequestBuilder {
         }).collect()
     }
 
+    /// 生成方法级超时设置代码（覆盖客户端的 `timeout`）
+    fn generate_timeout_call(timeout_ms: Option<u64>) -> proc_macro2::TokenStream {
+        match timeout_ms {
+            Some(timeout_ms) => quote! { .timeout(std::time::Duration::from_millis(#timeout_ms)) },
+            None => quote! {},
+        }
+    }
+
     /// 生成内容类型头部代码
     fn generate_content_type_header(content_type: &Option<ContentType>) -> proc_macro2::TokenStream {
         match content_type {
@@ -76,7 +85,8 @@ impl RequestBuilder {
                 quote! { .header("Content-Type", "application/x-www-form-urlencoded") }
             }
             Some(ContentType::FormMultipart) => {
-                quote! { .header("Content-Type", "multipart/form-data") }
+                // 由 multipart 编码器生成带 boundary 的 Content-Type
+                quote! {}
             }
             None => quote! { .header("Content-Type", "application/json") },
         }
@@ -88,6 +98,7 @@ impl RequestBuilder {
     /// 
     /// * `content_type` - 内容类型
     /// * `method` - HTTP 方法
+    /// * `body` - 请求体参数
     /// 
     /// # 返回值
     /// 
@@ -95,18 +106,22 @@ impl RequestBuilder {
     pub fn generate_body_method_call(
         content_type: &Option<ContentType>,
         method: &HttpMethod,
+        body: &syn::Ident,
     ) -> proc_macro2::TokenStream {
+        // 未指定内容类型时与 Content-Type 头保持一致，按 JSON 发送
         let method_call = match content_type {
-            Some(ContentType::Json) => quote! { .json(&body) },
-            Some(ContentType::FormUrlEncoded) => quote! { .form(&body) },
-            Some(ContentType::FormMultipart) => quote! { .multipart(&body) },
-            None => quote! { .query(&body) },
+            Some(ContentType::Json) | None => quote! { .json(&#body) },
+            Some(ContentType::FormUrlEncoded) => quote! { .form(&#body) },
+            Some(ContentType::FormMultipart) => quote! {
+                .multipart(swan_common::SwanMultipart::into_form(#body).await?)
+            },
         };
 
-        // 仅对 POST 和 PUT 使用 body，GET 和 DELETE 使用 query 参数
-        match method {
-            HttpMethod::Post | HttpMethod::Put => method_call,
-            HttpMethod::Get | HttpMethod::Delete => quote! { .query(&body) },
+        // 仅对 POST、PUT、PATCH 和自定义方法使用 body，其余方法使用 query 参数
+        if method.allows_body() {
+            method_call
+        } else {
+            quote! { .query(&#body) }
         }
     }
 }
@@ -115,6 +130,30 @@ impl RequestBuilder {
 mod tests {
     use super::*;
 
+    fn body_ident() -> syn::Ident {
+        syn::Ident::new("body", proc_macro2::Span::call_site())
+    }
+
+    #[test]
+    fn test_generate_timeout_call() {
+        assert!(RequestBuilder::generate_timeout_call(None).is_empty());
+
+        let result = RequestBuilder::generate_timeout_call(Some(30_000));
+        assert_eq!(
+            result.to_string(),
+            quote! { .timeout(std::time::Duration::from_millis(30000u64)) }.to_string()
+        );
+    }
+
+    #[test]
+    fn test_multipart_leaves_content_type_to_encoder() {
+        let content_type = Some(ContentType::FormMultipart);
+        assert!(RequestBuilder::generate_content_type_header(&content_type).is_empty());
+
+        let result = RequestBuilder::generate_body_method_call(&content_type, &HttpMethod::Post, &body_ident());
+        assert!(result.to_string().contains("SwanMultipart :: into_form (body) . await ?"));
+    }
+
     #[test]
     fn test_generate_content_type_header_json() {
         let content_type = Some(ContentType::Json);
@@ -127,16 +166,32 @@ mod tests {
     fn test_generate_body_method_call_post_json() {
         let content_type = Some(ContentType::Json);
         let method = HttpMethod::Post;
-        let result = RequestBuilder::generate_body_method_call(&content_type, &method);
+        let result = RequestBuilder::generate_body_method_call(&content_type, &method, &body_ident());
         let expected = quote! { .json(&body) };
         assert_eq!(result.to_string(), expected.to_string());
     }
 
+    #[test]
+    fn test_generate_body_method_call_patch_json() {
+        let content_type = Some(ContentType::Json);
+        let result = RequestBuilder::generate_body_method_call(&content_type, &HttpMethod::Patch, &body_ident());
+        let expected = quote! { .json(&body) };
+        assert_eq!(result.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_generate_body_method_call_head_uses_query() {
+        let content_type = Some(ContentType::Json);
+        let result = RequestBuilder::generate_body_method_call(&content_type, &HttpMethod::Head, &body_ident());
+        let expected = quote! { .query(&body) };
+        assert_eq!(result.to_string(), expected.to_string());
+    }
+
     #[test]
     fn test_generate_body_method_call_get_with_json() {
         let content_type = Some(ContentType::Json);
         let method = HttpMethod::Get;
-        let result = RequestBuilder::generate_body_method_call(&content_type, &method);
+        let result = RequestBuilder::generate_body_method_call(&content_type, &method, &body_ident());
         let expected = quote! { .query(&body) };
         assert_eq!(result.to_string(), expected.to_string());
     }
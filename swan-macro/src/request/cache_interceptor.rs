@@ -9,49 +9,65 @@ pub struct CachedInterceptorProcessor;
 impl CachedInterceptorProcessor {
     /// 生成缓存式拦截器获取代码
     /// 
+    /// 返回拦截器的具体类型 `Arc<T>`，不做 trait 对象转换：
+    /// 方法宏无法得知客户端是否带状态，trait 约束由客户端生成的辅助方法确定。
+    /// 
     /// # 参数
     /// 
     /// * `interceptor_path` - 拦截器类型路径
-    /// * `state_type` - 状态类型（可选）
     /// 
     /// # 返回值
     /// 
-    /// 生成的拦截器获取代码
+    /// 生成的拦截器获取代码；无方法级拦截器时为空
     pub fn generate_cached_interceptor_access(
-        interceptor_path: &Option<Path>, 
-        state_type: Option<&syn::Type>
+        interceptor_path: &Option<Path>,
     ) -> proc_macro2::TokenStream {
         match interceptor_path {
             Some(path) => {
-                if let Some(state_type) = state_type {
-                    // 有状态拦截器
-                    quote! {
-                        let method_interceptor = {
-                            let mut cache = self.interceptor_cache.lock().unwrap();
-                            Some(cache.get_or_create::<#path>() as std::sync::Arc<dyn swan_common::SwanStatefulInterceptor<#state_type> + Send + Sync>)
-                        };
-                    }
-                } else {
-                    // 无状态拦截器
-                    quote! {
-                        let method_interceptor = {
-                            let mut cache = self.interceptor_cache.lock().unwrap();
-                            Some(cache.get_or_create::<#path>() as std::sync::Arc<dyn swan_common::SwanInterceptor + Send + Sync>)
-                        };
-                    }
-                }
-            },
-            None => {
-                if state_type.is_some() {
-                    quote! {
-                        let method_interceptor: Option<std::sync::Arc<dyn swan_common::SwanStatefulInterceptor<_> + Send + Sync>> = None;
-                    }
-                } else {
-                    quote! {
-                        let method_interceptor: Option<std::sync::Arc<dyn swan_common::SwanInterceptor + Send + Sync>> = None;
-                    }
+                quote! {
+                    let method_interceptor = {
+                        let mut cache = self.interceptor_cache.lock().unwrap();
+                        cache.get_or_create::<#path>()
+                    };
                 }
             },
+            None => quote! {},
+        }
+    }
+
+    /// 生成请求前拦截器链调用代码
+    /// 
+    /// 执行顺序：全局拦截器 -> 方法级拦截器
+    pub fn generate_before_request_chain(interceptor_path: &Option<Path>) -> proc_macro2::TokenStream {
+        let method_call = if interceptor_path.is_some() {
+            quote! {
+                let request = self.__swan_before_request(&*method_interceptor, request).await?;
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            let request = self.__swan_global_before_request(request).await?;
+            #method_call
+        }
+    }
+
+    /// 生成响应后拦截器链调用代码
+    /// 
+    /// 执行顺序：方法级拦截器 -> 全局拦截器
+    pub fn generate_after_response_chain(interceptor_path: &Option<Path>) -> proc_macro2::TokenStream {
+        let method_call = if interceptor_path.is_some() {
+            quote! {
+                let response = self.__swan_after_response(&*method_interceptor, response).await?;
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #method_call
+            let response = self.__swan_global_after_response(response).await?;
         }
     }
 
@@ -65,7 +81,7 @@ mod tests {
     #[test]
     fn test_generate_cached_interceptor_access_with_path() {
         let path: Path = parse_quote! { MyInterceptor };
-        let result = CachedInterceptorProcessor::generate_cached_interceptor_access(&Some(path), None);
+        let result = CachedInterceptorProcessor::generate_cached_interceptor_access(&Some(path));
         
         let result_str = result.to_string();
         assert!(result_str.contains("MyInterceptor"));
@@ -75,9 +91,30 @@ mod tests {
 
     #[test]
     fn test_generate_cached_interceptor_access_none() {
-        let result = CachedInterceptorProcessor::generate_cached_interceptor_access(&None, None);
-        let result_str = result.to_string();
-        assert!(result_str.contains("None"));
+        let result = CachedInterceptorProcessor::generate_cached_interceptor_access(&None);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_generate_interceptor_chain_order() {
+        let path: Path = parse_quote! { MyInterceptor };
+
+        let before = CachedInterceptorProcessor::generate_before_request_chain(&Some(path.clone())).to_string();
+        let global = before.find("__swan_global_before_request").unwrap();
+        let method = before.find("__swan_before_request").unwrap();
+        assert!(global < method);
+
+        let after = CachedInterceptorProcessor::generate_after_response_chain(&Some(path)).to_string();
+        let method = after.find("__swan_after_response").unwrap();
+        let global = after.find("__swan_global_after_response").unwrap();
+        assert!(method < global);
+    }
+
+    #[test]
+    fn test_generate_interceptor_chain_without_method_interceptor() {
+        let before = CachedInterceptorProcessor::generate_before_request_chain(&None).to_string();
+        assert!(before.contains("__swan_global_before_request"));
+        assert!(!before.contains("method_interceptor"));
     }
 
 }
\ No newline at end of file
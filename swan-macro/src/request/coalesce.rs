@@ -0,0 +1,51 @@
+use quote::quote;
+use swan_common::coalesce::CoalesceConfig;
+
+/// 请求合并代码生成器
+pub struct CoalesceProcessor;
+
+impl CoalesceProcessor {
+    /// 将发送与解码代码包装为可合并的调用
+    ///
+    /// `call` 是产生 `result` 的代码块；配置了 `coalesce` 时在 `async move` 块中执行，
+    /// 内部的 `?` 统一产生 `SwanError`，最后再转换为方法声明的错误类型。
+    pub fn generate_coalesced_call(
+        method_name: &syn::Ident,
+        config: &Option<CoalesceConfig>,
+        ok_type: &syn::GenericArgument,
+        call: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Some(config) = config else {
+            return call;
+        };
+
+        let key = method_name.to_string();
+        let key_headers = &config.key_headers;
+        quote! {
+            let coalesce_key = swan_common::coalesce::Coalescer::key(#key, &request, &[#(#key_headers),*]);
+            self.coalescer
+                .run::<#ok_type, _>(coalesce_key, async move { #call })
+                .await
+                .map_err(Into::into)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_coalesced_call() {
+        let name: syn::Ident = syn::parse_quote! { get_user };
+        let ok_type: syn::GenericArgument = syn::parse_quote! { User };
+        let config = Some(CoalesceConfig::new().key_header("X-Tenant"));
+
+        let coalesced = CoalesceProcessor::generate_coalesced_call(&name, &config, &ok_type, quote! { result }).to_string();
+        assert!(coalesced.contains("Coalescer :: key (\"get_user\" , & request , & [\"authorization\" , \"cookie\" , \"accept\" , \"x-tenant\"])"));
+        assert!(coalesced.contains("run :: < User , _ > (coalesce_key , async move { result })"));
+
+        let direct = CoalesceProcessor::generate_coalesced_call(&name, &None, &ok_type, quote! { result }).to_string();
+        assert_eq!(direct, "result");
+    }
+}
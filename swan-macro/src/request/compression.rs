@@ -0,0 +1,52 @@
+use quote::quote;
+use swan_common::{CompressConfig, ContentEncoding};
+
+/// 请求体压缩代码生成器
+pub struct CompressionProcessor;
+
+impl CompressionProcessor {
+    /// 生成 `swan_common::ContentEncoding` 取值
+    pub fn generate_encoding(encoding: ContentEncoding) -> proc_macro2::TokenStream {
+        match encoding {
+            ContentEncoding::Gzip => quote! { swan_common::ContentEncoding::Gzip },
+            ContentEncoding::Zstd => quote! { swan_common::ContentEncoding::Zstd },
+            ContentEncoding::Brotli => quote! { swan_common::ContentEncoding::Brotli },
+        }
+    }
+
+    /// 生成请求体压缩代码
+    ///
+    /// 在请求前拦截器链之后执行，拦截器看到的是未压缩的请求体；
+    /// 重试与签名使用压缩后的请求，每次调用只压缩一次。
+    pub fn generate_compression(config: &Option<CompressConfig>) -> proc_macro2::TokenStream {
+        let Some(config) = config else {
+            return quote! {};
+        };
+        let encoding = Self::generate_encoding(config.encoding);
+        let min_size = config.min_size;
+        quote! {
+            let request = {
+                let mut request = request;
+                swan_common::CompressConfig::new(#encoding)
+                    .min_size(#min_size)
+                    .compress_request(&mut request)?;
+                request
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_compression() {
+        assert!(CompressionProcessor::generate_compression(&None).is_empty());
+
+        let config = CompressConfig::new(ContentEncoding::Zstd).min_size(4096);
+        let result = CompressionProcessor::generate_compression(&Some(config)).to_string();
+        assert!(result.contains("CompressConfig :: new (swan_common :: ContentEncoding :: Zstd)"));
+        assert!(result.contains(". min_size (4096usize)"));
+    }
+}
@@ -10,6 +10,12 @@ pub struct DynamicParamsProcessor;
 impl DynamicParamsProcessor {
     /// 生成带动态参数替换的URL代码
     /// 
+    /// 占位符的值会按所在位置进行百分号编码：
+    /// - 路径中的 `{name}`：按 RFC 3986 路径段规则编码（`/`、`?`、`#` 等均被编码），
+    ///   值为 `.` 或 `..` 时返回 `SwanError`
+    /// - 路径中的 `{*name}`：保留 `/`，用于拼接多个路径段
+    /// - `?` 之后的 `{name}`：按查询参数规则编码
+    /// 
     /// # 参数
     /// 
     /// * `url_template` - URL模板字符串，包含 {param} 占位符
@@ -31,32 +37,55 @@ impl DynamicParamsProcessor {
             };
         }
 
-        // 解析URL模板中的占位符
-        let placeholders = Self::extract_placeholders(url_template);
-        let mut format_str = url_template.to_string();
+        let query_start = url_template.find('?');
+        let mut format_str = String::new();
         let mut format_args = Vec::new();
+        let mut rest = url_template;
+        let mut offset = 0;
 
-        for placeholder in placeholders {
-            if let Some(param_ident) = Self::resolve_placeholder(&placeholder, &param_map) {
-                // 替换占位符为 Rust 格式化占位符
-                format_str = format_str.replace(&format!("{{{}}}", placeholder), "{}");
-                format_args.push(param_ident);
+        while let Some(open) = rest.find('{') {
+            let Some(len) = rest[open..].find('}') else { break };
+            let close = open + len;
+            let placeholder = &rest[open + 1..close];
+            let in_query = query_start.is_some_and(|start| offset + open > start);
+
+            format_str.push_str(&rest[..open]);
+
+            if placeholder.is_empty() {
+                format_str.push_str("{{}}");
             } else {
-                // 如果找不到对应参数，编译时报错
-                return quote! {
-                    compile_error!(concat!("Parameter '", #placeholder, "' not found in function parameters"));
+                let name = Self::placeholder_name(placeholder);
+                let Some(param_ident) = Self::resolve_placeholder(name, &param_map) else {
+                    // 如果找不到对应参数，编译时报错
+                    return quote! {
+                        compile_error!(concat!("Parameter '", #name, "' not found in function parameters"));
+                    };
+                };
+
+                let encoded = if placeholder.starts_with('*') {
+                    if in_query {
+                        return quote! {
+                            compile_error!(concat!("'{", #placeholder, "}' is only supported in the URL path"));
+                        };
+                    }
+                    quote! { swan_common::url::encode_path(&#param_ident) }
+                } else if in_query {
+                    quote! { swan_common::url::encode_query_component(&#param_ident) }
+                } else {
+                    quote! { swan_common::url::try_encode_path_segment(&#param_ident)? }
                 };
+
+                format_str.push_str("{}");
+                format_args.push(encoded);
             }
+
+            offset += close + 1;
+            rest = &rest[close + 1..];
         }
+        format_str.push_str(rest);
 
-        if format_args.is_empty() {
-            quote! { 
-                let full_url = format!("{}{}", self.base_url, #format_str);
-            }
-        } else {
-            quote! { 
-                let full_url = format!("{}{}", self.base_url, format!(#format_str, #(#format_args),*));
-            }
+        quote! { 
+            let full_url = format!("{}{}", self.base_url, format!(#format_str, #(#format_args),*));
         }
     }
 
@@ -127,7 +156,7 @@ impl DynamicParamsProcessor {
     }
 
     /// 提取字符串中的所有占位符
-    fn extract_placeholders(text: &str) -> Vec<String> {
+    pub(crate) fn extract_placeholders(text: &str) -> Vec<String> {
         let mut placeholders = Vec::new();
         let mut chars = text.chars().peekable();
         
@@ -150,8 +179,13 @@ impl DynamicParamsProcessor {
         placeholders
     }
 
+    /// 占位符对应的参数名（去除多段路径标记 `*`）
+    pub(crate) fn placeholder_name(placeholder: &str) -> &str {
+        placeholder.strip_prefix('*').unwrap_or(placeholder)
+    }
+
     /// 从函数参数中提取参数映射
-    fn extract_parameters(
+    pub(crate) fn extract_parameters(
         fn_inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
     ) -> HashMap<String, syn::Ident> {
         let mut param_map = HashMap::new();
@@ -209,6 +243,30 @@ mod tests {
         assert!(!DynamicParamsProcessor::has_placeholders("/users/123"));
     }
 
+    #[test]
+    fn test_url_placeholders_are_encoded_by_position() {
+        let inputs: syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]> = parse_quote! {
+            &self, id: String, rest: String, q: String
+        };
+
+        let code = DynamicParamsProcessor::generate_dynamic_url_code("/users/{id}/files/{*rest}?q={q}", &inputs)
+            .to_string();
+
+        assert!(code.contains("\"/users/{}/files/{}?q={}\""));
+        assert!(code.contains("try_encode_path_segment (& id) ?"));
+        assert!(code.contains("encode_path (& rest)"));
+        assert!(code.contains("encode_query_component (& q)"));
+    }
+
+    #[test]
+    fn test_wildcard_placeholder_in_query_is_rejected() {
+        let inputs: syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]> = parse_quote! { &self, rest: String };
+
+        let code = DynamicParamsProcessor::generate_dynamic_url_code("/search?q={*rest}", &inputs).to_string();
+
+        assert!(code.contains("compile_error"));
+    }
+
     #[test]
     fn test_extract_parameters() {
         let inputs: syn::punctuated::Punctuated<syn::FnArg, syn::Token![,]> = parse_quote! {
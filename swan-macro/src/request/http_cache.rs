@@ -0,0 +1,91 @@
+use quote::quote;
+use swan_common::cache::CacheConfig;
+
+/// HTTP 缓存代码生成器
+pub struct HttpCacheProcessor;
+
+impl HttpCacheProcessor {
+    /// 生成构造 `swan_common::cache::CacheConfig` 的常量表达式
+    pub fn generate_config(config: &CacheConfig) -> proc_macro2::TokenStream {
+        let max_age = match config.max_age {
+            Some(max_age) => {
+                let millis = max_age.as_millis() as u64;
+                quote! { Some(std::time::Duration::from_millis(#millis)) }
+            }
+            None => quote! { None },
+        };
+
+        quote! {
+            swan_common::cache::CacheConfig { max_age: #max_age }
+        }
+    }
+
+    /// 生成带缓存的执行代码
+    ///
+    /// 与 `retry_execution` 一样定义 `response` 与 `attempts`；命中新鲜缓存时不发送请求，
+    /// `attempts` 为 0。未配置缓存时原样执行。
+    pub fn generate_cached_execution(
+        config: &Option<CacheConfig>,
+        retry_execution: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Some(config) = config else {
+            return quote! {
+                #retry_execution
+                call_span.record_status(response.status());
+            };
+        };
+
+        let config = Self::generate_config(config);
+        quote! {
+            const CACHE_CONFIG: swan_common::cache::CacheConfig = #config;
+            let mut request = request;
+            let cache_identity = self.__swan_cache_identity(effective_client, &request).await?;
+            let mut cache_lookup = self.http_cache.lookup(&mut request, &cache_identity, &CACHE_CONFIG).await;
+            let (response, attempts) = match cache_lookup.take_fresh() {
+                Some(response) => {
+                    call_span.record_status(response.status());
+                    call_metrics.cached(response.status());
+                    (response, 0u32)
+                }
+                None => {
+                    #retry_execution
+                    call_span.record_status(response.status());
+                    // 304 在这里换成缓存的响应，可缓存的 200 响应在这里存入
+                    let revalidated = response.status() == reqwest::StatusCode::NOT_MODIFIED;
+                    let response = self.http_cache.update(cache_lookup, response).await?;
+                    if revalidated {
+                        call_metrics.cached(response.status());
+                    }
+                    (response, attempts)
+                }
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_generate_config() {
+        let config = CacheConfig::new().max_age(Duration::from_secs(60));
+        let result = HttpCacheProcessor::generate_config(&config).to_string();
+        assert!(result.contains("max_age : Some (std :: time :: Duration :: from_millis (60000u64))"));
+
+        let result = HttpCacheProcessor::generate_config(&CacheConfig::new()).to_string();
+        assert!(result.contains("max_age : None"));
+    }
+
+    #[test]
+    fn test_generate_cached_execution() {
+        let cached = HttpCacheProcessor::generate_cached_execution(&Some(CacheConfig::new()), quote! {}).to_string();
+        assert!(cached.contains("self . __swan_cache_identity (effective_client , & request)"));
+        assert!(cached.contains("self . http_cache . lookup (& mut request , & cache_identity , & CACHE_CONFIG)"));
+        assert!(cached.contains("self . http_cache . update (cache_lookup , response)"));
+
+        let uncached = HttpCacheProcessor::generate_cached_execution(&None, quote! {}).to_string();
+        assert!(!uncached.contains("http_cache"));
+    }
+}
@@ -1,9 +1,25 @@
+pub mod auth;
 pub mod builder;
 pub mod cache_interceptor;
+pub mod coalesce;
+pub mod compression;
 pub mod dynamic_params;
+pub mod http_cache;
+pub mod pagination;
+pub mod params;
+pub mod rate_limit;
 pub mod retry;
+pub mod signer;
 
+pub use auth::AuthProcessor;
 pub use builder::RequestBuilder;
 pub use cache_interceptor::CachedInterceptorProcessor;
+pub use coalesce::CoalesceProcessor;
+pub use compression::CompressionProcessor;
 pub use dynamic_params::DynamicParamsProcessor;
-pub use retry::RetryProcessor;
\ No newline at end of file
+pub use http_cache::HttpCacheProcessor;
+pub use pagination::PaginationProcessor;
+pub use params::MethodParams;
+pub use rate_limit::RateLimitProcessor;
+pub use retry::RetryProcessor;
+pub use signer::SignerProcessor;
\ No newline at end of file
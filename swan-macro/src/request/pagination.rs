@@ -0,0 +1,133 @@
+use quote::quote;
+use swan_common::pagination::{PageMode, Pagination};
+use syn::{GenericArgument, PathArguments, ReturnType, Type, TypeParamBound};
+
+const STREAM_RETURN_HINT: &str =
+    "paginated methods must return impl Stream<Item = anyhow::Result<T>> or impl Stream<Item = Result<T, E>>";
+
+/// 分页方法代码生成器
+pub struct PaginationProcessor;
+
+impl PaginationProcessor {
+    /// 从 `impl Stream<Item = ...>` 返回类型中取出 `Item`，作为单个条目的返回类型交给
+    /// [`ErrorHandler::validate_and_extract_return_types`](crate::error::ErrorHandler) 校验
+    pub fn item_return_type(output: &ReturnType) -> Result<ReturnType, syn::Error> {
+        let ReturnType::Type(arrow, ty) = output else {
+            return Err(syn::Error::new_spanned(output, STREAM_RETURN_HINT));
+        };
+        let Type::ImplTrait(impl_trait) = &**ty else {
+            return Err(syn::Error::new_spanned(ty, STREAM_RETURN_HINT));
+        };
+
+        impl_trait.bounds.iter()
+            .filter_map(|bound| match bound {
+                TypeParamBound::Trait(bound) => bound.path.segments.last(),
+                _ => None,
+            })
+            .filter(|segment| segment.ident == "Stream")
+            .filter_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => Some(args),
+                _ => None,
+            })
+            .flat_map(|args| args.args.iter())
+            .find_map(|arg| match arg {
+                GenericArgument::AssocType(assoc) if assoc.ident == "Item" => {
+                    Some(ReturnType::Type(*arrow, Box::new(assoc.ty.clone())))
+                }
+                _ => None,
+            })
+            .ok_or_else(|| syn::Error::new_spanned(ty, STREAM_RETURN_HINT))
+    }
+
+    /// 生成构造 `swan_common::pagination::Pagination` 的表达式
+    pub fn generate_config(config: &Pagination) -> proc_macro2::TokenStream {
+        let pagination = match &config.mode {
+            PageMode::LinkHeader => quote! { swan_common::pagination::Pagination::link_header() },
+            PageMode::Cursor { param, next } => quote! { swan_common::pagination::Pagination::cursor(#param, #next) },
+            PageMode::Offset { param, start } => quote! { swan_common::pagination::Pagination::offset(#param, #start) },
+        };
+
+        match &config.items {
+            Some(items) => quote! { #pagination.items(#items) },
+            None => pagination,
+        }
+    }
+
+    /// 生成返回分页流的方法体
+    ///
+    /// `fetch_page` 由 `request` 产生 `response`，在每一页的 `async move` 块中执行，
+    /// 内部的 `?` 统一产生 `SwanError`；条目的错误再转换为 `err_type`。
+    pub fn generate_paginated_stream(
+        config: &Pagination,
+        ok_type: &GenericArgument,
+        err_type: &proc_macro2::TokenStream,
+        has_method_interceptor: bool,
+        fetch_page: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let config = Self::generate_config(config);
+        // 方法级拦截器由每一页的请求共享
+        let interceptor_clone = if has_method_interceptor {
+            quote! { let method_interceptor = method_interceptor.clone(); }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            // 流持有客户端的克隆（内部共享连接池）
+            swan_common::pagination::paginate::<#ok_type, #err_type, _, _>(#config, first_request, move |request: reqwest::Request| {
+                #interceptor_clone
+                let effective_client = effective_client.clone();
+                async move {
+                    let effective_client = &effective_client;
+                    #fetch_page
+                    Ok::<reqwest::Response, swan_common::SwanError>(response)
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_item_return_type() {
+        let output: ReturnType = parse_quote! { -> impl futures_util::Stream<Item = anyhow::Result<User>> + '_ };
+        let item = PaginationProcessor::item_return_type(&output).unwrap();
+        assert_eq!(quote! { #item }.to_string(), "-> anyhow :: Result < User >");
+
+        let output: ReturnType = parse_quote! { -> anyhow::Result<Vec<User>> };
+        assert!(PaginationProcessor::item_return_type(&output).is_err());
+
+        let output: ReturnType = parse_quote! { -> impl Iterator<Item = User> };
+        assert!(PaginationProcessor::item_return_type(&output).is_err());
+    }
+
+    #[test]
+    fn test_generate_config() {
+        let config = PaginationProcessor::generate_config(&Pagination::cursor("cursor", "/next").items("/data")).to_string();
+        assert_eq!(config, "swan_common :: pagination :: Pagination :: cursor (\"cursor\" , \"/next\") . items (\"/data\")");
+
+        let config = PaginationProcessor::generate_config(&Pagination::offset("page", 1)).to_string();
+        assert_eq!(config, "swan_common :: pagination :: Pagination :: offset (\"page\" , 1u64)");
+    }
+
+    #[test]
+    fn test_generate_paginated_stream() {
+        let ok_type: GenericArgument = parse_quote! { User };
+        let err_type = quote! { anyhow::Error };
+
+        let stream = PaginationProcessor::generate_paginated_stream(
+            &Pagination::link_header(), &ok_type, &err_type, true, quote! { fetch },
+        ).to_string();
+        assert!(stream.contains("paginate :: < User , anyhow :: Error , _ , _ >"));
+        assert!(stream.contains("let method_interceptor = method_interceptor . clone () ; let effective_client = effective_client . clone () ; async move { let effective_client = & effective_client ; fetch"));
+
+        let stream = PaginationProcessor::generate_paginated_stream(
+            &Pagination::link_header(), &ok_type, &err_type, false, quote! { fetch },
+        ).to_string();
+        assert!(!stream.contains("method_interceptor"));
+    }
+}
@@ -0,0 +1,321 @@
+use quote::quote;
+use std::collections::HashSet;
+use swan_common::{HandlerArgs, ParamRole, parse_param_role};
+use syn::{FnArg, Ident, Type};
+use super::{DynamicParamsProcessor, RequestBuilder};
+
+/// 方法参数绑定
+///
+/// 根据参数上的 `#[path]`、`#[query]`、`#[header]`、`#[body]` 属性，
+/// 确定每个参数在请求中的用途，并生成函数签名与请求构建代码。
+///
+/// 未标注任何角色属性时保持原有行为：参数作为 URL / header 模板占位符使用，
+/// 需要请求体的方法在设置了 `content_type` 时，最后一个未被占位符引用的参数作为请求体。
+pub struct MethodParams {
+    /// 函数签名中的参数（已去除角色属性）
+    signature: Vec<proc_macro2::TokenStream>,
+    /// 请求体参数
+    body: Option<Ident>,
+    /// 查询参数：(参数名, 参数标识符)
+    queries: Vec<(String, Ident)>,
+    /// 请求头参数：(请求头名称, 参数标识符, 是否为 Option)
+    headers: Vec<(String, Ident, bool)>,
+}
+
+impl MethodParams {
+    /// 分析函数参数
+    ///
+    /// 角色冲突、重复的 `#[body]`、未被 URL 使用的 `#[path]` 等情况返回编译错误。
+    /// 使用了角色属性时，既未标注角色、也未被占位符引用的参数不会被发送，同样返回编译错误。
+    pub fn analyze(
+        inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
+        handler_args: &HandlerArgs,
+    ) -> syn::Result<Self> {
+        let url_placeholders = Self::placeholder_idents(&handler_args.url.value(), inputs);
+        let header_placeholders: HashSet<Ident> = handler_args.headers.iter()
+            .flat_map(|header| Self::placeholder_idents(&header.value(), inputs))
+            .collect();
+
+        let mut params = Self {
+            signature: Vec::new(),
+            body: None,
+            queries: Vec::new(),
+            headers: Vec::new(),
+        };
+        let mut has_roles = false;
+        let mut plain_params = Vec::new();
+
+        if let Some(download_to) = &handler_args.download_to {
+            let param_map = DynamicParamsProcessor::extract_parameters(inputs);
+            if !param_map.values().any(|ident| ident == download_to) {
+                return Err(syn::Error::new_spanned(
+                    download_to,
+                    format!("download_to parameter `{}` not found in method parameters", download_to),
+                ));
+            }
+        }
+
+        for input in inputs.iter().skip(1) {
+            let FnArg::Typed(pat_type) = input else { continue };
+            let role = parse_param_role(&pat_type.attrs)?;
+
+            let ident = match pat_type.pat.as_ref() {
+                syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                _ => match role {
+                    Some((_, attr)) => {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "parameter role attributes require a simple identifier pattern",
+                        ));
+                    }
+                    None => continue,
+                },
+            };
+
+            let param_type = &pat_type.ty;
+            let attrs = pat_type.attrs.iter().filter(|attr| !ParamRole::is_role_attribute(attr));
+            params.signature.push(quote! { , #(#attrs)* #ident: #param_type });
+
+            // `download_to` 指定的参数只作为目标文件路径使用
+            if handler_args.download_to.as_ref() == Some(&ident) {
+                if let Some((_, attr)) = role {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!("parameter `{}` is used by download_to and cannot have a role attribute", ident),
+                    ));
+                }
+                continue;
+            }
+
+            let Some((role, attr)) = role else {
+                if !url_placeholders.contains(&ident) && !header_placeholders.contains(&ident) {
+                    plain_params.push(ident);
+                }
+                continue;
+            };
+            has_roles = true;
+
+            if role != ParamRole::Path && url_placeholders.contains(&ident) {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    format!("parameter `{}` is used as a URL placeholder and cannot be marked {}", ident, role.attribute()),
+                ));
+            }
+
+            match role {
+                ParamRole::Path => {
+                    if !url_placeholders.contains(&ident) {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            format!("#[path] parameter `{}` is not used in the URL template", ident),
+                        ));
+                    }
+                }
+                ParamRole::Query(name) => {
+                    let name = name.unwrap_or_else(|| ident.to_string());
+                    params.queries.push((name, ident));
+                }
+                ParamRole::Header(name) => {
+                    params.headers.push((name, ident, Self::is_option(param_type)));
+                }
+                ParamRole::Body => {
+                    if params.body.is_some() {
+                        return Err(syn::Error::new_spanned(attr, "only one #[body] parameter is allowed"));
+                    }
+                    if !handler_args.method.allows_body() {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            format!(
+                                "{} requests cannot have a #[body] parameter; use #[query] instead",
+                                handler_args.method.as_str()
+                            ),
+                        ));
+                    }
+                    params.body = Some(ident);
+                }
+            }
+        }
+
+        if has_roles {
+            if let Some(ident) = plain_params.first() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "parameter `{}` is not used by the request; mark it #[query], #[header] or #[body], or reference it as a placeholder",
+                        ident
+                    ),
+                ));
+            }
+        } else if handler_args.method.allows_body() && handler_args.content_type.is_some() {
+            // 兼容旧写法：未使用角色属性时，最后一个普通参数作为请求体
+            params.body = plain_params.pop();
+        }
+
+        Ok(params)
+    }
+
+    /// 生成函数签名中的参数列表（不含 self）
+    pub fn signature(&self) -> proc_macro2::TokenStream {
+        let signature = &self.signature;
+        quote! { #(#signature)* }
+    }
+
+    /// 生成请求体方法调用代码
+    pub fn body_method_call(&self, handler_args: &HandlerArgs) -> proc_macro2::TokenStream {
+        match &self.body {
+            Some(body) => RequestBuilder::generate_body_method_call(
+                &handler_args.content_type,
+                &handler_args.method,
+                body,
+            ),
+            None => quote! {},
+        }
+    }
+
+    /// 生成查询参数与请求头参数的设置语句
+    pub fn builder_statements(&self) -> proc_macro2::TokenStream {
+        let queries = self.queries.iter().map(|(name, ident)| {
+            // `None` 值由 serde_urlencoded 自动跳过
+            quote! {
+                let request_builder = request_builder.query(&[(#name, &#ident)]);
+            }
+        });
+
+        let headers = self.headers.iter().map(|(name, ident, is_option)| {
+            if *is_option {
+                quote! {
+                    let request_builder = match &#ident {
+                        Some(value) => request_builder.header(#name, value.to_string()),
+                        None => request_builder,
+                    };
+                }
+            } else {
+                quote! {
+                    let request_builder = request_builder.header(#name, #ident.to_string());
+                }
+            }
+        });
+
+        quote! {
+            #(#queries)*
+            #(#headers)*
+        }
+    }
+
+    /// 解析模板中占位符对应的参数标识符
+    fn placeholder_idents(
+        template: &str,
+        inputs: &syn::punctuated::Punctuated<FnArg, syn::Token![,]>,
+    ) -> HashSet<Ident> {
+        let param_map = DynamicParamsProcessor::extract_parameters(inputs);
+        DynamicParamsProcessor::extract_placeholders(template)
+            .iter()
+            .filter_map(|placeholder| {
+                param_map.get(DynamicParamsProcessor::placeholder_name(placeholder)).cloned()
+            })
+            .collect()
+    }
+
+    /// 判断类型是否为 `Option<T>`
+    fn is_option(ty: &Type) -> bool {
+        match ty {
+            Type::Path(type_path) => type_path.path.segments.last()
+                .is_some_and(|segment| segment.ident == "Option"),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swan_common::parse_handler_args;
+    use syn::parse::Parser;
+    use syn::parse_quote;
+
+    fn args(tokens: proc_macro2::TokenStream, method: swan_common::HttpMethod) -> HandlerArgs {
+        let mut args = parse_handler_args.parse2(tokens).unwrap();
+        args.method = method;
+        args
+    }
+
+    #[test]
+    fn test_mixed_roles() {
+        let inputs: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! {
+            &self,
+            #[body] user: User,
+            #[path] id: u32,
+            #[query("pageSize")] size: u32,
+            #[header("X-Tenant")] tenant: Option<String>
+        };
+        let handler_args = args(quote! { url = "/users/{id}", content_type = json }, swan_common::HttpMethod::Post);
+
+        let params = MethodParams::analyze(&inputs, &handler_args).unwrap();
+
+        assert_eq!(params.body.as_ref().unwrap(), "user");
+        assert_eq!(params.queries[0].0, "pageSize");
+        assert_eq!(params.headers[0].0, "X-Tenant");
+        assert!(params.headers[0].2);
+        assert!(!params.signature().to_string().contains("# ["));
+    }
+
+    #[test]
+    fn test_legacy_body_is_last_plain_param() {
+        let inputs: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, body: User };
+        let handler_args = args(quote! { url = "/users", content_type = json }, swan_common::HttpMethod::Post);
+
+        let params = MethodParams::analyze(&inputs, &handler_args).unwrap();
+
+        assert_eq!(params.body.as_ref().unwrap(), "body");
+    }
+
+    #[test]
+    fn test_legacy_placeholder_is_not_body() {
+        let inputs: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, id: u32 };
+        let handler_args = args(quote! { url = "/users/{id}", content_type = json }, swan_common::HttpMethod::Put);
+
+        let params = MethodParams::analyze(&inputs, &handler_args).unwrap();
+
+        assert!(params.body.is_none());
+    }
+
+    #[test]
+    fn test_role_errors() {
+        let get = args(quote! { url = "/users/{id}" }, swan_common::HttpMethod::Get);
+        let post = args(quote! { url = "/users/{id}", content_type = json }, swan_common::HttpMethod::Post);
+
+        let body_on_get: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, #[body] user: User };
+        assert!(MethodParams::analyze(&body_on_get, &get).is_err());
+
+        let two_bodies: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, #[body] a: A, #[body] b: B };
+        assert!(MethodParams::analyze(&two_bodies, &post).is_err());
+
+        let unused_path: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, #[path] name: String };
+        assert!(MethodParams::analyze(&unused_path, &get).is_err());
+
+        let placeholder_query: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, #[query] id: u32 };
+        assert!(MethodParams::analyze(&placeholder_query, &get).is_err());
+
+        let unannotated: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, id: u32, #[query] page: u32, tenant: String };
+        let error = MethodParams::analyze(&unannotated, &get).err().unwrap();
+        assert!(error.to_string().contains("parameter `tenant` is not used by the request"));
+    }
+
+    #[test]
+    fn test_download_to_param_is_not_body() {
+        let inputs: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, report: Report, dest: &str };
+        let handler_args = args(quote! { url = "/exports", content_type = json, download_to = dest }, swan_common::HttpMethod::Post);
+
+        let params = MethodParams::analyze(&inputs, &handler_args).unwrap();
+
+        assert_eq!(params.body.as_ref().unwrap(), "report");
+        assert!(params.signature().to_string().contains("dest"));
+
+        let missing = args(quote! { url = "/exports", download_to = target }, swan_common::HttpMethod::Get);
+        assert!(MethodParams::analyze(&inputs, &missing).is_err());
+
+        let with_role: syn::punctuated::Punctuated<FnArg, syn::Token![,]> = parse_quote! { &self, #[query] dest: &str };
+        let get = args(quote! { url = "/exports", download_to = dest }, swan_common::HttpMethod::Get);
+        assert!(MethodParams::analyze(&with_role, &get).is_err());
+    }
+}
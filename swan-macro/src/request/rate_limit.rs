@@ -0,0 +1,74 @@
+use quote::quote;
+use swan_common::{OnExceed, RateLimitConfig};
+
+/// 限流代码生成器
+pub struct RateLimitProcessor;
+
+impl RateLimitProcessor {
+    /// 生成构造 `swan_common::RateLimitConfig` 的表达式
+    pub fn generate_config(config: &RateLimitConfig) -> proc_macro2::TokenStream {
+        let requests = config.requests;
+        let per_ms = config.per.as_millis() as u64;
+        let burst = config.burst;
+        let on_exceed = match config.on_exceed {
+            OnExceed::Wait => quote! { swan_common::OnExceed::Wait },
+            OnExceed::Error => quote! { swan_common::OnExceed::Error },
+        };
+
+        quote! {
+            swan_common::RateLimitConfig::new(#requests, std::time::Duration::from_millis(#per_ms))
+                .burst(#burst)
+                .on_exceed(#on_exceed)
+        }
+    }
+
+    /// 生成方法级限流器获取代码
+    ///
+    /// 始终定义 `method_rate_limiter`，供重试循环传给 `__swan_execute`；
+    /// 未配置方法级限流时为 `None`。
+    pub fn generate_method_limiter(
+        method_name: &syn::Ident,
+        config: &Option<RateLimitConfig>,
+    ) -> proc_macro2::TokenStream {
+        match config {
+            Some(config) => {
+                let key = method_name.to_string();
+                let config = Self::generate_config(config);
+                quote! {
+                    let method_rate_limiter = Some(self.method_rate_limiters.get_or_create(#key, #config));
+                }
+            }
+            None => quote! {
+                let method_rate_limiter: Option<std::sync::Arc<swan_common::RateLimiter>> = None;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_generate_config() {
+        let config = RateLimitConfig::new(10, Duration::from_secs(1)).burst(20).on_exceed(OnExceed::Error);
+        let result = RateLimitProcessor::generate_config(&config).to_string();
+
+        assert!(result.contains("RateLimitConfig :: new (10u32 , std :: time :: Duration :: from_millis (1000u64))"));
+        assert!(result.contains(". burst (20u32)"));
+        assert!(result.contains("swan_common :: OnExceed :: Error"));
+    }
+
+    #[test]
+    fn test_generate_method_limiter() {
+        let name: syn::Ident = syn::parse_quote! { search };
+        let config = Some(RateLimitConfig::new(2, Duration::from_secs(1)));
+
+        let limited = RateLimitProcessor::generate_method_limiter(&name, &config).to_string();
+        let unlimited = RateLimitProcessor::generate_method_limiter(&name, &None).to_string();
+
+        assert!(limited.contains("get_or_create (\"search\""));
+        assert!(unlimited.contains("= None"));
+    }
+}
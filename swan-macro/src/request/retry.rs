@@ -1,5 +1,6 @@
 use quote::quote;
-use swan_common::{RetryConfig, RetryPolicy};
+use swan_common::{RetryConfig, RetryErrorClass, RetryPolicy};
+use syn::Path;
 
 /// 重试机制处理器
 /// 
@@ -12,18 +13,19 @@ impl RetryProcessor {
     /// 根据重试配置生成完整的重试逻辑，包括指数退避、幂等性检查和条件判断
     pub fn generate_retry_execution_code(
         retry_config: &Option<RetryConfig>,
+        retry_predicate: &Option<Path>,
         method: &swan_common::HttpMethod,
     ) -> proc_macro2::TokenStream {
         match retry_config {
             Some(config) => {
                 let policy = &config.policy;
-                Self::generate_retry_with_policy(policy, method)
+                Self::generate_retry_with_policy(policy, retry_predicate, method)
             }
             None => {
                 // 无重试配置，直接执行
                 quote! {
-                    let response = self.client.execute(request).await
-                        .map_err(|e| anyhow::anyhow!("Request execution failed: {}", e))?;
+                    let attempts: u32 = 1;
+                    let response = self.__swan_execute(effective_client, request, method_rate_limiter.as_deref(), &call_span, &call_metrics).await?;
                 }
             }
         }
@@ -32,6 +34,7 @@ impl RetryProcessor {
     /// 生成带重试策略的执行代码
     fn generate_retry_with_policy(
         policy: &RetryPolicy,
+        retry_predicate: &Option<Path>,
         method: &swan_common::HttpMethod,
     ) -> proc_macro2::TokenStream {
         let max_attempts = policy.max_attempts;
@@ -40,6 +43,7 @@ impl RetryProcessor {
         let exponential_base = policy.exponential_base;
         let jitter_ratio = policy.jitter_ratio;
         let idempotent_only = policy.idempotent_only;
+        let respect_retry_after = policy.respect_retry_after;
 
         // 幂等性检查
         let idempotent_check = if idempotent_only {
@@ -48,6 +52,35 @@ impl RetryProcessor {
             quote! { true }
         };
 
+        // 自定义重试条件：返回 None 时沿用 on_status / on_error 的判断
+        let (predicate_access, status_decision, error_decision) = match retry_predicate {
+            Some(path) => (
+                quote! {
+                    let retry_predicate = {
+                        let mut cache = self.interceptor_cache.lock().unwrap();
+                        cache.get_or_create::<#path>()
+                    };
+                },
+                quote! {
+                    swan_common::SwanRetryPredicate::should_retry(
+                        &*retry_predicate,
+                        &swan_common::RetryOutcome::Response(&resp),
+                    ).unwrap_or_else(|| should_retry_response(status))
+                },
+                quote! {
+                    swan_common::SwanRetryPredicate::should_retry(
+                        &*retry_predicate,
+                        &swan_common::RetryOutcome::Error(&e),
+                    ).unwrap_or_else(|| should_retry_error(&e))
+                },
+            ),
+            None => (
+                quote! {},
+                quote! { should_retry_response(status) },
+                quote! { should_retry_error(&e) },
+            ),
+        };
+
         quote! {
             // 重试策略配置
             const MAX_ATTEMPTS: u32 = #max_attempts;
@@ -56,40 +89,54 @@ impl RetryProcessor {
             const EXPONENTIAL_BASE: f64 = #exponential_base;
             const JITTER_RATIO: f64 = #jitter_ratio;
             const IS_IDEMPOTENT: bool = #idempotent_check;
+            const RESPECT_RETRY_AFTER: bool = #respect_retry_after;
+
+            #predicate_access
 
             let mut last_error = None;
             let mut response = None;
+            let mut attempts: u32 = 0;
             
             for attempt in 0..MAX_ATTEMPTS {
+                attempts += 1;
+
                 // 克隆请求以支持重试
                 let request_clone = match request.try_clone() {
                     Some(req) => req,
                     None => {
-                        return Err(anyhow::anyhow!(
+                        return Err(swan_common::SwanError::request(
                             "Request body cannot be cloned for retry. Use idempotent methods or ensure request body is cloneable."
-                        ));
+                        ).into());
                     }
                 };
 
-                match self.client.execute(request_clone).await {
+                match self.__swan_execute(effective_client, request_clone, method_rate_limiter.as_deref(), &call_span, &call_metrics).await {
                     Ok(resp) => {
                         let status = resp.status().as_u16();
                         
                         // 检查是否需要重试
-                        if should_retry_response(status) && attempt < MAX_ATTEMPTS - 1 && IS_IDEMPOTENT {
+                        if attempt < MAX_ATTEMPTS - 1 && IS_IDEMPOTENT && #status_decision {
                             log::warn!("Request failed with status {}, retrying attempt {}/{}", 
                                       status, attempt + 2, MAX_ATTEMPTS);
                             
-                            // 计算延迟时间
-                            let delay_ms = calculate_retry_delay(
+                            // 计算延迟时间：服务端给出 Retry-After 时优先采用，仍受最大延迟限制
+                            let backoff_ms = calculate_retry_delay(
                                 attempt + 1, 
                                 BASE_DELAY_MS, 
                                 MAX_DELAY_MS, 
                                 EXPONENTIAL_BASE, 
                                 JITTER_RATIO
                             );
+                            let delay_ms = if RESPECT_RETRY_AFTER {
+                                swan_common::retry::retry_after(resp.headers())
+                                    .map(|delay| u64::try_from(delay.as_millis()).unwrap_or(u64::MAX).min(MAX_DELAY_MS))
+                                    .unwrap_or(backoff_ms)
+                            } else {
+                                backoff_ms
+                            };
                             
                             // 异步延迟
+                            call_metrics.retry(attempt + 2, std::time::Duration::from_millis(delay_ms));
                             tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
                             continue;
                         }
@@ -99,10 +146,11 @@ impl RetryProcessor {
                         break;
                     }
                     Err(e) => {
+                        // 网络错误重试判断：默认仅重试超时、连接失败等传输层错误
+                        let should_retry = attempt < MAX_ATTEMPTS - 1 && IS_IDEMPOTENT && #error_decision;
                         last_error = Some(e);
                         
-                        // 网络错误重试判断
-                        if attempt < MAX_ATTEMPTS - 1 && IS_IDEMPOTENT {
+                        if should_retry {
                             log::warn!("Network error on attempt {}/{}, retrying: {}", 
                                       attempt + 1, MAX_ATTEMPTS, last_error.as_ref().unwrap());
                             
@@ -114,36 +162,39 @@ impl RetryProcessor {
                                 JITTER_RATIO
                             );
                             
+                            call_metrics.retry(attempt + 2, std::time::Duration::from_millis(delay_ms));
                             tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
                             continue;
                         }
                         
-                        // 最终失败
-                        return Err(anyhow::anyhow!("Request failed after {} attempts: {}", 
-                                                 attempt + 1, last_error.unwrap()));
+                        // 最终失败：按错误性质分类（超时、连接失败等）
+                        log::warn!("Request failed after {} attempts", attempt + 1);
+                        return Err(last_error.unwrap().into());
                     }
                 }
             }
             
-            let response = response.ok_or_else(|| 
-                anyhow::anyhow!("Retry loop completed without successful response"))?;
+            let response = response.ok_or_else(||
+                swan_common::SwanError::request("Retry loop completed without successful response"))?;
         }
     }
 
     /// 生成幂等性检查代码
     fn generate_idempotent_check(method: &swan_common::HttpMethod) -> proc_macro2::TokenStream {
-        use swan_common::HttpMethod;
-        match method {
-            HttpMethod::Get | HttpMethod::Put | HttpMethod::Delete => quote! { true },
-            HttpMethod::Post => quote! { false },
+        if RetryPolicy::is_idempotent_method(method) {
+            quote! { true }
+        } else {
+            quote! { false }
         }
     }
 
     /// 生成重试条件判断代码
-    pub fn generate_retry_condition_code() -> proc_macro2::TokenStream {
-        quote! {
-            #[inline(always)]
-            fn should_retry_response(status: u16) -> bool {
+    ///
+    /// 未配置 `on_status` / `on_error` 时使用默认条件：5xx、429、408 与全部可重试的传输层错误
+    pub fn generate_retry_condition_code(policy: Option<&RetryPolicy>) -> proc_macro2::TokenStream {
+        let status_check = match policy.and_then(|policy| policy.on_status.as_ref()) {
+            Some(statuses) => quote! { matches!(status, #(#statuses)|*) },
+            None => quote! {
                 match status {
                     // 5xx 服务器错误
                     500..=599 => true,
@@ -154,6 +205,30 @@ impl RetryProcessor {
                     // 其他状态不重试
                     _ => false,
                 }
+            },
+        };
+
+        let error_check = match policy.and_then(|policy| policy.on_error.as_ref()) {
+            Some(classes) => {
+                let classes = classes.iter().map(|class| match class {
+                    RetryErrorClass::Connect => quote! { swan_common::RetryErrorClass::Connect },
+                    RetryErrorClass::Timeout => quote! { swan_common::RetryErrorClass::Timeout },
+                    RetryErrorClass::Request => quote! { swan_common::RetryErrorClass::Request },
+                });
+                quote! { matches!(error.retry_error_class(), Some(#(#classes)|*)) }
+            }
+            None => quote! { error.is_retryable() },
+        };
+
+        quote! {
+            #[inline(always)]
+            fn should_retry_response(status: u16) -> bool {
+                #status_check
+            }
+
+            #[inline(always)]
+            fn should_retry_error(error: &swan_common::SwanError) -> bool {
+                #error_check
             }
         }
     }
@@ -214,10 +289,11 @@ impl RetryProcessor {
     /// 生成完整的重试代码块
     pub fn generate_complete_retry_block(
         retry_config: &Option<RetryConfig>,
+        retry_predicate: &Option<Path>,
         method: &swan_common::HttpMethod,
     ) -> proc_macro2::TokenStream {
-        let retry_execution = Self::generate_retry_execution_code(retry_config, method);
-        let retry_condition = Self::generate_retry_condition_code();
+        let retry_execution = Self::generate_retry_execution_code(retry_config, retry_predicate, method);
+        let retry_condition = Self::generate_retry_condition_code(retry_config.as_ref().map(|config| &config.policy));
         let delay_calculation = Self::generate_delay_calculation_code();
         let retry_monitoring = Self::generate_retry_monitoring_code();
 
@@ -239,12 +315,22 @@ mod tests {
 
     #[test]
     fn test_generate_retry_execution_no_config() {
-        let result = RetryProcessor::generate_retry_execution_code(&None, &swan_common::HttpMethod::Get);
+        let result = RetryProcessor::generate_retry_execution_code(&None, &None, &swan_common::HttpMethod::Get);
         let result_str = result.to_string();
-        assert!(result_str.contains("self . client . execute (request)")); // quote!宏会在token间添加空格
+        assert!(result_str.contains("self . __swan_execute (effective_client , request , method_rate_limiter . as_deref () , & call_span , & call_metrics)")); // quote!宏会在token间添加空格
         assert!(!result_str.contains("MAX_ATTEMPTS"));
     }
 
+    #[test]
+    fn test_generate_retry_respects_retry_after_flag() {
+        let config: syn::LitStr = syn::parse_quote! { "exponential(3, 100ms, respect_retry_after=false)" };
+        let config = RetryConfig::parse(&config).unwrap();
+        let result = RetryProcessor::generate_retry_execution_code(&Some(config), &None, &swan_common::HttpMethod::Get);
+        let result_str = result.to_string();
+        assert!(result_str.contains("const RESPECT_RETRY_AFTER : bool = false"));
+        assert!(result_str.contains("swan_common :: retry :: retry_after"));
+    }
+
     #[test]
     fn test_generate_idempotent_check_get() {
         let result = RetryProcessor::generate_idempotent_check(&swan_common::HttpMethod::Get);
@@ -259,13 +345,51 @@ mod tests {
         assert_eq!(result_str.trim(), "false");
     }
 
+    #[test]
+    fn test_generate_idempotent_check_patch() {
+        let result = RetryProcessor::generate_idempotent_check(&swan_common::HttpMethod::Patch);
+        assert_eq!(result.to_string().trim(), "false");
+    }
+
+    #[test]
+    fn test_generate_idempotent_check_head_and_options() {
+        let head = RetryProcessor::generate_idempotent_check(&swan_common::HttpMethod::Head);
+        let options = RetryProcessor::generate_idempotent_check(&swan_common::HttpMethod::Options);
+        assert_eq!(head.to_string().trim(), "true");
+        assert_eq!(options.to_string().trim(), "true");
+    }
+
     #[test]
     fn test_generate_retry_condition_code() {
-        let result = RetryProcessor::generate_retry_condition_code();
+        let result = RetryProcessor::generate_retry_condition_code(None);
         let result_str = result.to_string();
         assert!(result_str.contains("should_retry_response"));
         assert!(result_str.contains("500 ..= 599")); // quote!宏会在操作符间添加空格
         assert!(result_str.contains("429"));
+        assert!(result_str.contains("error . is_retryable ()"));
+    }
+
+    #[test]
+    fn test_generate_configured_retry_conditions() {
+        let config: syn::LitStr = syn::parse_quote! { "exponential(3, 100ms, on_status=[502,503], on_error=[timeout])" };
+        let config = RetryConfig::parse(&config).unwrap();
+        let result_str = RetryProcessor::generate_retry_condition_code(Some(&config.policy)).to_string();
+
+        assert!(result_str.contains("matches ! (status , 502u16 | 503u16)"));
+        assert!(result_str.contains("Some (swan_common :: RetryErrorClass :: Timeout)"));
+        assert!(!result_str.contains("500 ..= 599"));
+    }
+
+    #[test]
+    fn test_generate_retry_with_predicate() {
+        let config: syn::LitStr = syn::parse_quote! { "exponential(3, 100ms)" };
+        let config = RetryConfig::parse(&config).unwrap();
+        let predicate: Option<Path> = Some(syn::parse_quote! { RetryOnConflict });
+        let result_str = RetryProcessor::generate_retry_execution_code(&Some(config), &predicate, &swan_common::HttpMethod::Get).to_string();
+
+        assert!(result_str.contains("get_or_create :: < RetryOnConflict > ()"));
+        assert!(result_str.contains("swan_common :: RetryOutcome :: Response (& resp)"));
+        assert!(result_str.contains("swan_common :: RetryOutcome :: Error (& e)"));
     }
 
     #[test]
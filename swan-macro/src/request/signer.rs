@@ -0,0 +1,74 @@
+use crate::request::AuthProcessor;
+use quote::quote;
+use swan_common::signing::SignerConfig;
+
+/// 请求签名代码生成器
+pub struct SignerProcessor;
+
+impl SignerProcessor {
+    /// 生成构造签名器的表达式
+    pub fn generate_signer(config: &SignerConfig) -> proc_macro2::TokenStream {
+        match config {
+            SignerConfig::AwsSigV4 { region, service, credentials, session_token } => {
+                let credentials = credentials.as_ref().map(|(access_key_id, secret_access_key)| {
+                    let access_key_id = AuthProcessor::generate_secret(access_key_id);
+                    let secret_access_key = AuthProcessor::generate_secret(secret_access_key);
+                    quote! { .credentials(#access_key_id, #secret_access_key) }
+                });
+                let session_token = session_token.as_ref().map(|token| {
+                    let token = AuthProcessor::generate_secret(token);
+                    quote! { .session_token(#token) }
+                });
+                quote! {
+                    swan_common::signing::AwsSigV4Signer::new(#region, #service)
+                        #credentials
+                        #session_token
+                }
+            }
+            SignerConfig::Hmac { secret, key_id, signature_header, timestamp_header, key_id_header, signed_headers } => {
+                let secret = AuthProcessor::generate_secret(secret);
+                let key_id = key_id.as_ref().map(|key_id| quote! { .key_id(#key_id) });
+                let signature_header = signature_header.as_ref().map(|name| quote! { .signature_header(#name) });
+                let timestamp_header = timestamp_header.as_ref().map(|name| quote! { .timestamp_header(#name) });
+                let key_id_header = key_id_header.as_ref().map(|name| quote! { .key_id_header(#name) });
+                let signed_headers = (!signed_headers.is_empty()).then(|| quote! { .signed_headers([#(#signed_headers),*]) });
+                quote! {
+                    swan_common::signing::HmacSigner::new(#secret)
+                        #key_id
+                        #signature_header
+                        #timestamp_header
+                        #key_id_header
+                        #signed_headers
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swan_common::auth::Secret;
+
+    #[test]
+    fn test_generate_signer() {
+        let aws = SignerProcessor::generate_signer(&SignerConfig::AwsSigV4 {
+            region: "us-east-1".into(),
+            service: "s3".into(),
+            credentials: None,
+            session_token: None,
+        }).to_string();
+        assert_eq!(aws, "swan_common :: signing :: AwsSigV4Signer :: new (\"us-east-1\" , \"s3\")");
+
+        let hmac = SignerProcessor::generate_signer(&SignerConfig::Hmac {
+            secret: Secret::env("SIGNING_SECRET"),
+            key_id: Some("svc-a".into()),
+            signature_header: None,
+            timestamp_header: None,
+            key_id_header: None,
+            signed_headers: vec!["content-type".into()],
+        }).to_string();
+        assert!(hmac.starts_with("swan_common :: signing :: HmacSigner :: new (swan_common :: auth :: Secret :: env (\"SIGNING_SECRET\"))"));
+        assert!(hmac.ends_with(". key_id (\"svc-a\") . signed_headers ([\"content-type\"])"));
+    }
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swan_common::{BasicAuth, SwanError};
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    id: u32,
+}
+
+#[http_client(base_url = "http://unused", auth = bearer(env = "SWAN_AUTH_TEST_TOKEN"))]
+struct BearerClient;
+
+impl BearerClient {
+    #[get(url = "/items/{id}")]
+    async fn get_item(&self, id: u32) -> Result<Item, SwanError> {}
+}
+
+#[http_client(base_url = "http://unused", auth = api_key(query = "api_key", key = "k-123"))]
+struct ApiKeyClient;
+
+impl ApiKeyClient {
+    #[get(url = "/items/{id}")]
+    async fn get_item(&self, id: u32) -> anyhow::Result<Item> {}
+}
+
+#[http_client(
+    base_url = "http://unused",
+    auth = oauth2_client_credentials(
+        token_url = "http://unused/oauth/token",
+        client_id = "swan",
+        client_secret = "s3cret",
+        scopes = ["items.read"],
+    ),
+)]
+struct OAuthClient;
+
+impl OAuthClient {
+    #[get(url = "/items/{id}", retry = "exponential(2, 10ms)")]
+    async fn get_item(&self, id: u32) -> Result<Item, SwanError> {}
+}
+
+fn item(request_path: &str) -> MockResponse {
+    let id = request_path.trim_start_matches("/items/").split('?').next().unwrap_or_default().to_string();
+    MockResponse::json(&format!(r#"{{"id":{}}}"#, id))
+}
+
+#[tokio::test]
+async fn bearer_token_is_read_from_env_for_each_request() {
+    let server = TestServer::start(|request| match request.header("authorization") {
+        Some("Bearer from-env") => item(&request.path),
+        _ => MockResponse::new(401),
+    })
+    .await;
+    let client = BearerClient::builder().base_url(&server.base_url).build().unwrap();
+
+    let error = client.get_item(1).await.unwrap_err();
+    assert_eq!(error.kind(), "auth");
+    assert!(server.requests().is_empty());
+
+    // SAFETY: 只有这个测试读写该环境变量
+    unsafe { std::env::set_var("SWAN_AUTH_TEST_TOKEN", "from-env") };
+    assert_eq!(client.get_item(1).await.unwrap().id, 1);
+
+    // 凭据不会刷新时，401 不会重发
+    unsafe { std::env::set_var("SWAN_AUTH_TEST_TOKEN", "revoked") };
+    let error = client.get_item(2).await.unwrap_err();
+    assert_eq!(error.status().map(|status| status.as_u16()), Some(401));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn api_key_in_query_and_runtime_override() {
+    let server = TestServer::start(|request| {
+        let authorized = request.path.ends_with("api_key=k-123")
+            || request.header("authorization") == Some("Basic dXNlcjpwYXNz");
+        if authorized { item(&request.path) } else { MockResponse::new(403) }
+    })
+    .await;
+
+    let client = ApiKeyClient::builder().base_url(&server.base_url).build().unwrap();
+    assert_eq!(client.get_item(7).await.unwrap().id, 7);
+    assert_eq!(server.requests()[0].path, "/items/7?api_key=k-123");
+
+    let client = ApiKeyClient::builder()
+        .base_url(&server.base_url)
+        .auth(BasicAuth::new("user", "pass"))
+        .build()
+        .unwrap();
+    assert_eq!(client.get_item(8).await.unwrap().id, 8);
+    assert_eq!(server.requests()[1].path, "/items/8");
+}
+
+#[tokio::test]
+async fn oauth2_caches_token_and_refreshes_once_on_401() {
+    static ISSUED: AtomicUsize = AtomicUsize::new(0);
+    // 服务端只接受最近签发的令牌
+    let server = TestServer::start(|request| {
+        if request.path == "/oauth/token" {
+            assert_eq!(request.header("authorization"), Some("Basic c3dhbjpzM2NyZXQ="));
+            assert_eq!(request.body, b"grant_type=client_credentials&scope=items.read");
+            let token = ISSUED.fetch_add(1, Ordering::SeqCst) + 1;
+            return MockResponse::json(&format!(r#"{{"access_token":"t{}","expires_in":3600}}"#, token));
+        }
+        let current = format!("Bearer t{}", ISSUED.load(Ordering::SeqCst));
+        match request.header("authorization") {
+            Some(token) if token == current => item(&request.path),
+            _ => MockResponse::new(401),
+        }
+    })
+    .await;
+    // 宏参数中的令牌端点在运行时替换为测试服务器
+    let client = OAuthClient::builder()
+        .base_url(&server.base_url)
+        .auth(
+            swan_common::OAuth2ClientCredentials::new(format!("{}/oauth/token", server.base_url), "swan", "s3cret")
+                .scopes(["items.read"]),
+        )
+        .build()
+        .unwrap();
+
+    client.get_item(1).await.unwrap();
+    client.get_item(2).await.unwrap();
+    assert_eq!(ISSUED.load(Ordering::SeqCst), 1);
+
+    // 服务端吊销当前令牌：收到 401 后刷新一次并重发
+    ISSUED.fetch_add(1, Ordering::SeqCst);
+    assert_eq!(client.get_item(3).await.unwrap().id, 3);
+
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths, ["/oauth/token", "/items/1", "/items/2", "/items/3", "/oauth/token", "/items/3"]);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use std::time::Duration;
+use swan_common::SwanError;
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "http://default.invalid")]
+struct BuiltClient;
+
+impl BuiltClient {
+    #[get(url = "/ping")]
+    async fn ping(&self) -> Result<String, SwanError> {}
+}
+
+#[test]
+fn builder_keeps_macro_defaults() {
+    let client = BuiltClient::builder().build().unwrap();
+
+    assert_eq!(client.base_url, "http://default.invalid");
+}
+
+#[tokio::test]
+async fn builder_overrides_base_url_and_sets_headers() {
+    let server = TestServer::start(|_| MockResponse::new(200).body("pong")).await;
+    let client = BuiltClient::builder()
+        .base_url(&server.base_url)
+        .default_header("X-Tenant", "acme")
+        .user_agent("swan-test/1.0")
+        .build()
+        .unwrap();
+
+    assert_eq!(client.ping().await.unwrap(), "pong");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("x-tenant"), Some("acme"));
+    assert_eq!(request.header("user-agent"), Some("swan-test/1.0"));
+}
+
+#[tokio::test]
+async fn builder_timeout_produces_timeout_error() {
+    // 接受连接但从不响应
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+
+    let client = BuiltClient::builder()
+        .base_url(base_url)
+        .timeout(Duration::from_millis(100))
+        .connect_timeout(Duration::from_secs(1))
+        .build()
+        .unwrap();
+
+    let error = client.ping().await.unwrap_err();
+
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+}
+
+#[test]
+fn builder_rejects_invalid_default_header() {
+    let result = BuiltClient::builder().default_header("Bad Header", "x").build();
+
+    assert!(matches!(result, Err(SwanError::Request(_))));
+}
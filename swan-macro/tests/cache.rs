@@ -0,0 +1,212 @@
+use serde::Deserialize;
+use swan_common::testing::{MockResponse, MockTransport, RecordingMetrics};
+use swan_common::{BearerAuth, DiskCacheStore, Response};
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Country {
+    code: String,
+}
+
+#[http_client(base_url = "https://api.example.com")]
+struct ReferenceClient;
+
+impl ReferenceClient {
+    #[get(url = "/countries", cache = true)]
+    async fn countries(&self) -> anyhow::Result<Vec<Country>> {}
+
+    #[get(url = "/currencies", cache = "max_age=60s")]
+    async fn currencies(&self) -> anyhow::Result<String> {}
+
+    #[get(url = "/rates", cache = true)]
+    async fn rates(&self) -> anyhow::Result<Response<String>> {}
+
+    #[get(url = "/live")]
+    async fn live(&self) -> anyhow::Result<String> {}
+
+    #[get(url = "/profile", cache = true)]
+    async fn profile(&self, #[header("Authorization")] token: &str) -> anyhow::Result<String> {}
+
+    #[get(url = "/profile", cache = true)]
+    async fn my_profile(&self) -> anyhow::Result<String> {}
+}
+
+/// `/profile` 按 `Authorization` 返回不同内容，且不声明 `Vary`
+fn profile_mocks(transport: &MockTransport) {
+    for name in ["alice", "bob"] {
+        let _ = transport
+            .mock("GET", "/profile")
+            .header("Authorization", format!("Bearer {}", name))
+            .respond(MockResponse::new(200).header("Cache-Control", "max-age=60").body(name));
+    }
+}
+
+fn client(transport: &MockTransport) -> ReferenceClient {
+    ReferenceClient::builder().mock_transport(transport.clone()).build().unwrap()
+}
+
+#[tokio::test]
+async fn fresh_response_is_served_from_cache() {
+    let transport = MockTransport::new();
+    let countries = transport.mock("GET", "/countries").respond(
+        MockResponse::json(r#"[{"code":"NL"}]"#).header("Cache-Control", "max-age=60"),
+    );
+    let client = client(&transport);
+
+    let first = client.countries().await.unwrap();
+    let second = client.countries().await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(second[0].code, "NL");
+    countries.assert_calls(1);
+}
+
+#[tokio::test]
+async fn stale_response_is_revalidated_with_etag() {
+    let transport = MockTransport::new();
+    let revalidated = transport
+        .mock("GET", "/rates")
+        .header("If-None-Match", "\"v1\"")
+        .respond(MockResponse::new(304));
+    let initial = transport
+        .mock("GET", "/rates")
+        .respond(MockResponse::new(200).header("ETag", "\"v1\"").body("1.08"));
+    let client = client(&transport);
+
+    let first = client.rates().await.unwrap();
+    let second = client.rates().await.unwrap();
+
+    assert_eq!(first.attempts, 1);
+    assert_eq!((second.status.as_u16(), second.body.as_str()), (200, "1.08"));
+    assert_eq!(second.attempts, 1);
+    initial.assert_calls(1);
+    revalidated.assert_calls(1);
+}
+
+#[tokio::test]
+async fn last_modified_is_sent_as_if_modified_since() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/rates").respond_sequence([
+        MockResponse::new(200).header("Last-Modified", "Tue, 15 Oct 2024 07:28:00 GMT").body("1.08"),
+        MockResponse::new(200).header("Last-Modified", "Wed, 16 Oct 2024 07:28:00 GMT").body("1.09"),
+    ]);
+    let client = client(&transport);
+
+    assert_eq!(client.rates().await.unwrap().body, "1.08");
+    assert_eq!(client.rates().await.unwrap().body, "1.09");
+
+    let requests = transport.requests();
+    assert!(requests[0].headers.get("if-modified-since").is_none());
+    assert_eq!(requests[1].headers["if-modified-since"], "Tue, 15 Oct 2024 07:28:00 GMT");
+}
+
+#[tokio::test]
+async fn no_store_and_uncached_methods_always_hit_the_network() {
+    let transport = MockTransport::new();
+    let currencies = transport
+        .mock("GET", "/currencies")
+        .respond(MockResponse::new(200).header("Cache-Control", "no-store").body("EUR"));
+    let live = transport.mock("GET", "/live").respond(MockResponse::new(200).header("Cache-Control", "max-age=60").body("up"));
+    let client = client(&transport);
+
+    for _ in 0..2 {
+        assert_eq!(client.currencies().await.unwrap(), "EUR");
+        assert_eq!(client.live().await.unwrap(), "up");
+    }
+
+    currencies.assert_calls(2);
+    live.assert_calls(2);
+}
+
+#[tokio::test]
+async fn configured_max_age_applies_without_cache_control() {
+    let transport = MockTransport::new();
+    let currencies = transport.mock("GET", "/currencies").respond(MockResponse::new(200).body("EUR"));
+    let metrics = RecordingMetrics::new();
+    let client = ReferenceClient::builder()
+        .mock_transport(transport.clone())
+        .metrics(metrics.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(client.currencies().await.unwrap(), "EUR");
+    assert_eq!(client.currencies().await.unwrap(), "EUR");
+
+    currencies.assert_calls(1);
+    let outcomes = metrics.outcomes();
+    assert_eq!(outcomes.len(), 2);
+    assert!(outcomes.iter().all(|outcome| outcome.is_success()));
+    assert_eq!(outcomes[1].attempts, 0);
+}
+
+#[tokio::test]
+async fn error_responses_are_not_cached() {
+    let transport = MockTransport::new();
+    let countries = transport.mock("GET", "/countries").respond_sequence([
+        MockResponse::new(503).header("Cache-Control", "max-age=60"),
+        MockResponse::json(r#"[{"code":"BE"}]"#).header("Cache-Control", "max-age=60"),
+    ]);
+    let client = client(&transport);
+
+    assert!(client.countries().await.is_err());
+    assert_eq!(client.countries().await.unwrap()[0].code, "BE");
+    assert_eq!(client.countries().await.unwrap()[0].code, "BE");
+    countries.assert_calls(2);
+}
+
+#[tokio::test]
+async fn disk_store_survives_a_new_client() {
+    let dir = std::env::temp_dir().join(format!("swan-http-cache-{}", std::process::id()));
+    let transport = MockTransport::new();
+    let countries = transport.mock("GET", "/countries").respond(
+        MockResponse::json(r#"[{"code":"DE"}]"#).header("Cache-Control", "max-age=60"),
+    );
+    let client = || {
+        ReferenceClient::builder()
+            .mock_transport(transport.clone())
+            .cache_store(DiskCacheStore::new(&dir))
+            .build()
+            .unwrap()
+    };
+
+    assert_eq!(client().countries().await.unwrap()[0].code, "DE");
+    assert_eq!(client().countries().await.unwrap()[0].code, "DE");
+
+    countries.assert_calls(1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn cached_responses_are_not_shared_between_credentials() {
+    let transport = MockTransport::new();
+    profile_mocks(&transport);
+    let client = client(&transport);
+
+    assert_eq!(client.profile("Bearer alice").await.unwrap(), "alice");
+    assert_eq!(client.profile("Bearer bob").await.unwrap(), "bob");
+    assert_eq!(client.profile("Bearer alice").await.unwrap(), "alice");
+
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn clients_sharing_a_store_do_not_share_authenticated_responses() {
+    let dir = std::env::temp_dir().join(format!("swan-http-cache-auth-{}", fastrand::u64(..)));
+    let transport = MockTransport::new();
+    profile_mocks(&transport);
+    let client = |token: &str| {
+        ReferenceClient::builder()
+            .mock_transport(transport.clone())
+            .cache_store(DiskCacheStore::new(&dir))
+            .auth(BearerAuth::new(token.to_string()))
+            .build()
+            .unwrap()
+    };
+
+    assert_eq!(client("alice").my_profile().await.unwrap(), "alice");
+    assert_eq!(client("bob").my_profile().await.unwrap(), "bob");
+    assert_eq!(client("alice").my_profile().await.unwrap(), "alice");
+
+    assert_eq!(transport.calls(), 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_common::{CircuitBreakerConfig, CircuitState, SwanError};
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "https://api.example.com", circuit_breaker = "failures=2, window=10s, cooldown=200ms")]
+struct GuardedClient;
+
+impl GuardedClient {
+    #[get(url = "/status")]
+    async fn status(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/status", retry = "exponential(5, 1ms)")]
+    async fn status_with_retry(&self) -> Result<String, SwanError> {}
+}
+
+#[tokio::test]
+async fn circuit_opens_fails_fast_and_recovers() {
+    let transport = MockTransport::new();
+    let status = transport.mock("GET", "/status").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(503),
+        MockResponse::new(200).body("ok"),
+    ]);
+    let client = GuardedClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.circuit_state("api.example.com"), CircuitState::Closed);
+    client.status().await.unwrap_err();
+    client.status().await.unwrap_err();
+    assert_eq!(client.circuit_state("api.example.com"), CircuitState::Open);
+
+    let error = client.status().await.unwrap_err();
+    assert!(error.is_circuit_open(), "unexpected error: {}", error);
+    status.assert_calls(2);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert_eq!(client.circuit_state("api.example.com"), CircuitState::HalfOpen);
+
+    assert_eq!(client.status().await.unwrap(), "ok");
+    assert_eq!(client.circuit_state("api.example.com"), CircuitState::Closed);
+    status.assert_calls(3);
+}
+
+#[tokio::test]
+async fn retries_stop_when_circuit_opens() {
+    let transport = MockTransport::new();
+    let status = transport.mock("GET", "/status").respond(MockResponse::new(503));
+    let client = GuardedClient::with_mock_transport(transport.clone());
+
+    let error = client.status_with_retry().await.unwrap_err();
+
+    assert!(error.is_circuit_open(), "unexpected error: {}", error);
+    status.assert_calls(2);
+}
+
+#[tokio::test]
+async fn client_errors_do_not_trip_the_circuit() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/status").respond(MockResponse::new(404));
+    let client = GuardedClient::with_mock_transport(transport.clone());
+
+    for _ in 0..3 {
+        client.status().await.unwrap_err();
+    }
+
+    assert_eq!(client.circuit_state("api.example.com"), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn builder_overrides_circuit_breaker_config() {
+    let transport = MockTransport::new();
+    let status = transport.mock("GET", "/status").respond(MockResponse::new(500));
+    let client = GuardedClient::builder()
+        .circuit_breaker(CircuitBreakerConfig::new(1, Duration::from_secs(10), Duration::from_secs(60)))
+        .mock_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    client.status().await.unwrap_err();
+    assert!(client.status().await.unwrap_err().is_circuit_open());
+    status.assert_calls(1);
+}
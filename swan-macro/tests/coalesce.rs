@@ -0,0 +1,111 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use futures_util::future::join_all;
+use serde::Deserialize;
+use swan_common::SwanError;
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[http_client(base_url = "http://unused")]
+struct CoalescingClient;
+
+impl CoalescingClient {
+    #[get(url = "/users/{id}", coalesce = true)]
+    async fn get_user(&self, id: u32, #[header("Authorization")] token: &str) -> anyhow::Result<User> {}
+
+    #[get(url = "/users/{id}", coalesce(headers = ["X-Tenant"]))]
+    async fn get_tenant_user(&self, id: u32, #[header("X-Tenant")] tenant: &str) -> Result<User, SwanError> {}
+
+    #[get(url = "/users/{id}")]
+    async fn get_user_uncoalesced(&self, id: u32) -> anyhow::Result<User> {}
+}
+
+async fn server() -> TestServer {
+    TestServer::start(|request| match request.path.as_str() {
+        "/users/404" => MockResponse::new(404).body("no such user"),
+        path => {
+            let id = path.trim_start_matches("/users/");
+            MockResponse::json(&format!(r#"{{"id":{},"name":"user-{}"}}"#, id, id))
+        }
+    })
+    .await
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+fn client(server: &TestServer) -> CoalescingClient {
+    CoalescingClient::builder().base_url(&server.base_url).build().unwrap()
+}
+
+#[tokio::test]
+async fn concurrent_identical_calls_share_one_request() {
+    let server = server().await;
+    let client = client(&server);
+
+    let calls: Vec<_> = (0..5).map(|_| client.get_user(1, "Bearer a")).collect();
+    assert_send(&calls[0]);
+    let users = join_all(calls).await;
+
+    assert_eq!(server.requests().len(), 1);
+    for user in users {
+        assert_eq!(user.unwrap(), User { id: 1, name: "user-1".into() });
+    }
+
+    // 前一次调用完成后重新发送
+    client.get_user(1, "Bearer a").await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn different_urls_and_key_headers_are_not_coalesced() {
+    let server = server().await;
+    let client = client(&server);
+
+    let (a, b, c) = tokio::join!(
+        client.get_user(1, "Bearer a"),
+        client.get_user(2, "Bearer a"),
+        client.get_user(1, "Bearer b"),
+    );
+    assert_eq!((a.unwrap().id, b.unwrap().id, c.unwrap().id), (1, 2, 1));
+    assert_eq!(server.requests().len(), 3);
+
+    let (a, b) = tokio::join!(client.get_tenant_user(3, "acme"), client.get_tenant_user(3, "globex"));
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn errors_reach_every_waiter() {
+    let server = server().await;
+    let client = client(&server);
+
+    let results = join_all((0..3).map(|_| client.get_tenant_user(404, "acme"))).await;
+
+    assert_eq!(server.requests().len(), 1);
+    for result in results {
+        match result {
+            Err(SwanError::Status { status, body, .. }) => {
+                assert_eq!(status.as_u16(), 404);
+                assert_eq!(body, "no such user");
+            }
+            other => panic!("expected status error, got {:?}", other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn methods_without_coalesce_send_every_call() {
+    let server = server().await;
+    let client = client(&server);
+
+    let users = join_all((0..3).map(|_| client.get_user_uncoalesced(1))).await;
+
+    assert!(users.into_iter().all(|user| user.is_ok()));
+    assert_eq!(server.requests().len(), 3);
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::{MockResponse, RecordedRequest, TestServer};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use swan_common::SwanError;
+use swan_macro::{get, http_client, post, put};
+
+#[derive(Debug, Serialize)]
+struct Batch {
+    events: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Report {
+    rows: Vec<u32>,
+}
+
+#[http_client(base_url = "http://unused")]
+struct IngestClient;
+
+impl IngestClient {
+    #[post(url = "/events", content_type = json, compress = gzip)]
+    async fn send_gzip(&self, batch: Batch) -> Result<(), SwanError> {}
+
+    #[put(url = "/events", content_type = json, compress = zstd(min_size = 0), retry = "exponential(2, 10ms)")]
+    async fn send_zstd(&self, batch: Batch) -> Result<(), SwanError> {}
+
+    #[post(url = "/events", content_type = json, compress = br(min_size = 64))]
+    async fn send_brotli(&self, batch: Batch) -> Result<(), SwanError> {}
+}
+
+#[http_client(base_url = "http://unused", decompress = [gzip, br])]
+struct ReportClient;
+
+impl ReportClient {
+    #[get(url = "/report")]
+    async fn report(&self) -> Result<Report, SwanError> {}
+
+    #[get(url = "/report", proxy = false)]
+    async fn report_without_proxy(&self) -> Result<Report, SwanError> {}
+}
+
+fn batch(len: usize) -> Batch {
+    Batch { events: (0..len).map(|i| format!("event-{i}")).collect() }
+}
+
+fn decoded_body(request: &RecordedRequest) -> Vec<u8> {
+    let body = request.body.as_slice();
+    let mut decoded = Vec::new();
+    match request.header("content-encoding") {
+        Some("gzip") => flate2::read::GzDecoder::new(body).read_to_end(&mut decoded).unwrap(),
+        Some("zstd") => zstd::Decoder::new(body).unwrap().read_to_end(&mut decoded).unwrap(),
+        Some("br") => brotli::Decompressor::new(body, 4096).read_to_end(&mut decoded).unwrap(),
+        _ => return body.to_vec(),
+    };
+    decoded
+}
+
+async fn ingest_client(server: &TestServer) -> IngestClient {
+    let mut client = IngestClient::new();
+    client.base_url = server.base_url.clone();
+    client
+}
+
+#[tokio::test]
+async fn compresses_bodies_above_min_size() {
+    let server = TestServer::start(|_| MockResponse::new(204)).await;
+    let client = ingest_client(&server).await;
+    let expected = serde_json::to_vec(&batch(200)).unwrap();
+
+    client.send_gzip(batch(200)).await.unwrap();
+    client.send_brotli(batch(200)).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("content-encoding"), Some("gzip"));
+    assert_eq!(requests[1].header("content-encoding"), Some("br"));
+    for request in &requests {
+        assert!(request.body.len() < expected.len());
+        assert_eq!(request.header("content-length"), Some(request.body.len().to_string().as_str()));
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(decoded_body(request), expected);
+    }
+}
+
+#[tokio::test]
+async fn skips_bodies_below_min_size() {
+    let server = TestServer::start(|_| MockResponse::new(204)).await;
+    let client = ingest_client(&server).await;
+
+    client.send_gzip(batch(1)).await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("content-encoding"), None);
+    assert_eq!(request.body, br#"{"events":["event-0"]}"#);
+}
+
+#[tokio::test]
+async fn retries_resend_compressed_body() {
+    let server = TestServer::start(|_| MockResponse::new(503)).await;
+    let client = ingest_client(&server).await;
+
+    let error = client.send_zstd(batch(1)).await.unwrap_err();
+
+    assert_eq!(error.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+    let requests = server.requests();
+    assert!(requests.len() > 1);
+    for request in &requests {
+        assert_eq!(request.header("content-encoding"), Some("zstd"));
+        assert_eq!(decoded_body(request), br#"{"events":["event-0"]}"#);
+    }
+}
+
+#[tokio::test]
+async fn decompresses_responses() {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(br#"{"rows":[1,2,3]}"#).unwrap();
+    let gzipped = encoder.finish().unwrap();
+    let server = TestServer::start(move |_| {
+        MockResponse::json("").header("Content-Encoding", "gzip").body(gzipped.clone())
+    }).await;
+    let mut client = ReportClient::new();
+    client.base_url = server.base_url.clone();
+
+    let report = client.report().await.unwrap();
+
+    assert_eq!(report, Report { rows: vec![1, 2, 3] });
+    assert_eq!(server.requests()[0].header("accept-encoding"), Some("gzip, br"));
+}
+
+#[tokio::test]
+async fn decompresses_responses_for_method_level_proxy_settings() {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(br#"{"rows":[4]}"#).unwrap();
+    let gzipped = encoder.finish().unwrap();
+    let server = TestServer::start(move |_| {
+        MockResponse::json("").header("Content-Encoding", "gzip").body(gzipped.clone())
+    }).await;
+    let mut client = ReportClient::new();
+    client.base_url = server.base_url.clone();
+
+    let report = client.report_without_proxy().await.unwrap();
+
+    assert_eq!(report, Report { rows: vec![4] });
+    assert_eq!(server.requests()[0].header("accept-encoding"), Some("gzip, br"));
+}
+
+#[tokio::test]
+async fn decompression_is_off_by_default() {
+    let server = TestServer::start(|_| MockResponse::new(204)).await;
+    let client = ingest_client(&server).await;
+
+    client.send_gzip(batch(1)).await.unwrap();
+
+    assert_eq!(server.requests()[0].header("accept-encoding"), None);
+}
+
+#[tokio::test]
+async fn builder_overrides_decompress() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"rows":[]}"#)).await;
+    let mut client = ReportClient::builder().decompress([]).build().unwrap();
+    client.base_url = server.base_url.clone();
+
+    client.report().await.unwrap();
+
+    assert_eq!(server.requests()[0].header("accept-encoding"), None);
+}
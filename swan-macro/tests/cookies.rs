@@ -0,0 +1,105 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use swan_common::CookieJar;
+use swan_macro::{get, http_client, post};
+
+#[http_client(base_url = "http://unused", cookies = true)]
+struct SessionClient;
+
+impl SessionClient {
+    #[post(url = "/login")]
+    async fn login(&self) -> anyhow::Result<()> {}
+
+    #[get(url = "/me")]
+    async fn me(&self) -> anyhow::Result<String> {}
+
+    #[get(url = "/me", proxy = false)]
+    async fn me_without_proxy(&self) -> anyhow::Result<String> {}
+}
+
+#[http_client(base_url = "http://unused", cookies = file("target/swan-cookies-test/session.json"))]
+struct PersistentClient;
+
+impl PersistentClient {
+    #[post(url = "/login")]
+    async fn login(&self) -> anyhow::Result<()> {}
+
+    #[get(url = "/me")]
+    async fn me(&self) -> anyhow::Result<String> {}
+}
+
+#[http_client(base_url = "http://unused")]
+struct PlainClient;
+
+impl PlainClient {
+    #[post(url = "/login")]
+    async fn login(&self) -> anyhow::Result<()> {}
+
+    #[get(url = "/me")]
+    async fn me(&self) -> anyhow::Result<String> {}
+}
+
+/// `/login` 下发会话 Cookie，`/me` 返回请求携带的 Cookie
+async fn session_server() -> TestServer {
+    TestServer::start(|request| match request.path.as_str() {
+        "/login" => MockResponse::new(204).header("Set-Cookie", "session=abc; Path=/; HttpOnly"),
+        _ => MockResponse::new(200).body(request.header("cookie").unwrap_or_default()),
+    })
+    .await
+}
+
+#[tokio::test]
+async fn session_cookie_is_stored_and_sent() {
+    let server = session_server().await;
+    let client = SessionClient::builder().base_url(&server.base_url).build().unwrap();
+
+    assert_eq!(client.me().await.unwrap(), "");
+    client.login().await.unwrap();
+    assert_eq!(client.me().await.unwrap(), "session=abc");
+    // 方法级代理设置使用独立的客户端，共享同一个 Cookie 存储
+    assert_eq!(client.me_without_proxy().await.unwrap(), "session=abc");
+
+    let cookies = client.cookies().unwrap();
+    assert_eq!(cookies.get(&server.base_url, "session").as_deref(), Some("abc"));
+
+    // 预置 Cookie
+    cookies.insert(&server.base_url, "tenant=acme; Path=/").unwrap();
+    let sent = client.me().await.unwrap();
+    let mut sent: Vec<&str> = sent.split("; ").collect();
+    sent.sort();
+    assert_eq!(sent, ["session=abc", "tenant=acme"]);
+}
+
+#[tokio::test]
+async fn jar_is_shared_between_clients() {
+    let server = session_server().await;
+    let jar = CookieJar::new();
+    let first = SessionClient::builder().base_url(&server.base_url).cookie_jar(jar.clone()).build().unwrap();
+    let second = PlainClient::builder().base_url(&server.base_url).cookie_jar(jar.clone()).build().unwrap();
+
+    first.login().await.unwrap();
+    assert_eq!(second.me().await.unwrap(), "session=abc");
+
+    // 未启用 Cookie 的客户端不保存也不发送
+    let plain = PlainClient::builder().base_url(&server.base_url).build().unwrap();
+    assert!(plain.cookies().is_none());
+    plain.login().await.unwrap();
+    assert_eq!(plain.me().await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn file_jar_survives_client_restart() {
+    let _ = std::fs::remove_dir_all("target/swan-cookies-test");
+    let server = session_server().await;
+
+    let client = PersistentClient::builder().base_url(&server.base_url).build().unwrap();
+    client.login().await.unwrap();
+    drop(client);
+
+    let restarted = PersistentClient::builder().base_url(&server.base_url).build().unwrap();
+    assert_eq!(restarted.cookies().unwrap().path().unwrap().file_name().unwrap(), "session.json");
+    assert_eq!(restarted.me().await.unwrap(), "session=abc");
+
+    std::fs::remove_dir_all("target/swan-cookies-test").unwrap();
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use serde::Deserialize;
+use swan_common::SwanError;
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+}
+
+/// 业务侧自定义错误类型
+#[derive(Debug)]
+enum AppError {
+    NotFound,
+    Http(SwanError),
+}
+
+impl From<SwanError> for AppError {
+    fn from(error: SwanError) -> Self {
+        match error.status() {
+            Some(status) if status == 404 => AppError::NotFound,
+            _ => AppError::Http(error),
+        }
+    }
+}
+
+#[http_client(base_url = "http://unused")]
+struct ErrorClient;
+
+impl ErrorClient {
+    #[get(url = "/users/{id}")]
+    async fn typed(&self, id: u32) -> Result<User, SwanError> {}
+
+    #[get(url = "/users/{id}")]
+    async fn custom(&self, id: u32) -> Result<User, AppError> {}
+
+    #[get(url = "/users/{id}")]
+    async fn dynamic(&self, id: u32) -> anyhow::Result<User> {}
+
+    #[get(url = "/users/{id}")]
+    async fn custom_anyhow(&self, id: u32) -> anyhow::Result<User, AppError> {}
+}
+
+async fn client_for(server: &TestServer) -> ErrorClient {
+    let mut client = ErrorClient::new();
+    client.base_url = server.base_url.clone();
+    client
+}
+
+#[tokio::test]
+async fn status_error_keeps_status_headers_and_body() {
+    let server = TestServer::start(|_| {
+        MockResponse::new(422)
+            .header("X-Request-Id", "abc")
+            .body(r#"{"error":"invalid"}"#)
+    })
+    .await;
+    let client = client_for(&server).await;
+
+    match client.typed(1).await {
+        Err(SwanError::Status { status, headers, body }) => {
+            assert_eq!(status, 422);
+            assert_eq!(headers.get("x-request-id").unwrap(), "abc");
+            assert_eq!(body.as_ref(), br#"{"error":"invalid"}"#);
+        }
+        other => panic!("expected status error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn decode_error_is_reported() {
+    let server = TestServer::start(|_| MockResponse::json("not json")).await;
+    let client = client_for(&server).await;
+
+    let error = client.typed(1).await.unwrap_err();
+
+    assert!(matches!(error, SwanError::Decode(_)));
+}
+
+#[tokio::test]
+async fn connect_error_is_reported() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = ErrorClient::new();
+    client.base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let error = client.typed(1).await.unwrap_err();
+
+    assert!(error.is_connect());
+}
+
+#[tokio::test]
+async fn custom_error_type_converts_from_swan_error() {
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/users/1" => MockResponse::json(r#"{"id":1}"#),
+        "/users/2" => MockResponse::new(404),
+        _ => MockResponse::new(500),
+    })
+    .await;
+    let client = client_for(&server).await;
+
+    assert_eq!(client.custom(1).await.unwrap(), User { id: 1 });
+    assert!(matches!(client.custom(2).await, Err(AppError::NotFound)));
+    assert!(matches!(client.custom(3).await, Err(AppError::Http(SwanError::Status { .. }))));
+    assert!(matches!(client.custom_anyhow(2).await, Err(AppError::NotFound)));
+}
+
+#[tokio::test]
+async fn anyhow_error_can_be_downcast() {
+    let server = TestServer::start(|_| MockResponse::new(503)).await;
+    let client = client_for(&server).await;
+
+    let error = client.dynamic(1).await.unwrap_err();
+
+    let swan_error = error.downcast_ref::<SwanError>().unwrap();
+    assert_eq!(swan_error.status().map(|s| s.as_u16()), Some(503));
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{MockResponse, TestServer};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use swan_macro::{get, http_client, post};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Echo {
+    ok: bool,
+}
+
+/// 无状态拦截器的调用记录，按服务器地址区分，避免并行运行的测试互相干扰
+static STATELESS_LOG: Mutex<Vec<(String, &'static str)>> = Mutex::new(Vec::new());
+
+fn record(url: Option<&reqwest::Url>, event: &'static str) {
+    let origin = url.map(|url| url.origin().ascii_serialization()).unwrap_or_default();
+    STATELESS_LOG.lock().unwrap().push((origin, event));
+}
+
+fn request_url(request: &reqwest::RequestBuilder) -> Option<reqwest::Url> {
+    Some(request.try_clone()?.build().ok()?.url().clone())
+}
+
+/// 发往 `base_url` 的请求触发的拦截器调用
+fn stateless_log(base_url: &str) -> Vec<&'static str> {
+    STATELESS_LOG.lock().unwrap()
+        .iter()
+        .filter(|(origin, _)| origin == base_url)
+        .map(|(_, event)| *event)
+        .collect()
+}
+
+#[derive(Default)]
+struct GlobalInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for GlobalInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        record(request_url(&request).as_ref(), "global-before");
+        Ok((request.header("X-Global", "1"), Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(&self, response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+        record(Some(response.url()), "global-after");
+        Ok(response)
+    }
+}
+
+#[derive(Default)]
+struct MethodInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for MethodInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        record(request_url(&request).as_ref(), "method-before");
+        Ok((request.header("X-Method", "1"), Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(&self, response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+        record(Some(response.url()), "method-after");
+        Ok(response)
+    }
+}
+
+/// 追加请求体内容的拦截器，用于验证 `Cow::Owned` 请求体会被发送
+#[derive(Default)]
+struct SigningInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for SigningInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        let mut body = request_body.to_vec();
+        body.extend_from_slice(b"|signed");
+        Ok((request, Cow::Owned(body)))
+    }
+
+    async fn after_response(&self, response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+        Ok(response)
+    }
+}
+
+#[http_client(base_url = "http://unused", interceptor = GlobalInterceptor)]
+struct StatelessClient;
+
+impl StatelessClient {
+    #[get(url = "/global")]
+    async fn global_only(&self) -> anyhow::Result<Echo> {}
+
+    #[get(url = "/chain", interceptor = MethodInterceptor)]
+    async fn full_chain(&self) -> anyhow::Result<Echo> {}
+}
+
+#[http_client(base_url = "http://unused")]
+struct PlainClient;
+
+impl PlainClient {
+    #[post(url = "/sign", interceptor = SigningInterceptor)]
+    async fn signed(&self) -> anyhow::Result<Echo> {}
+}
+
+#[tokio::test]
+async fn runs_global_and_method_interceptors_in_order() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"ok":true}"#)).await;
+    let mut client = StatelessClient::new();
+    client.base_url = server.base_url.clone();
+
+    assert_eq!(client.full_chain().await.unwrap(), Echo { ok: true });
+
+    assert_eq!(
+        stateless_log(&server.base_url),
+        vec!["global-before", "method-before", "method-after", "global-after"]
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/chain");
+    assert_eq!(request.header("x-global"), Some("1"));
+    assert_eq!(request.header("x-method"), Some("1"));
+}
+
+#[tokio::test]
+async fn runs_global_interceptor_without_method_interceptor() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"ok":true}"#)).await;
+    let mut client = StatelessClient::new();
+    client.base_url = server.base_url.clone();
+
+    client.global_only().await.unwrap();
+
+    assert_eq!(stateless_log(&server.base_url), vec!["global-before", "global-after"]);
+    let request = &server.requests()[0];
+    assert_eq!(request.header("x-global"), Some("1"));
+    assert_eq!(request.header("x-method"), None);
+}
+
+#[tokio::test]
+async fn method_interceptor_can_replace_request_body() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"ok":true}"#)).await;
+    let mut client = PlainClient::new();
+    client.base_url = server.base_url.clone();
+
+    client.signed().await.unwrap();
+
+    assert_eq!(server.requests()[0].body, b"|signed");
+}
+
+#[derive(Clone, Default)]
+struct AppState {
+    token: String,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Default)]
+struct StatefulAuth;
+
+#[async_trait]
+impl SwanStatefulInterceptor<AppState> for StatefulAuth {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+        state: Option<&AppState>,
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        let state = state.expect("state should be injected");
+        state.log.lock().unwrap().push("global-before".into());
+        let request = request.header("Authorization", format!("Bearer {}", state.token));
+        Ok((request, Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(
+        &self,
+        response: reqwest::Response,
+        state: Option<&AppState>,
+    ) -> anyhow::Result<reqwest::Response> {
+        state.unwrap().log.lock().unwrap().push(format!("global-after:{}", response.status().as_u16()));
+        Ok(response)
+    }
+}
+
+#[derive(Default)]
+struct StatefulAudit;
+
+#[async_trait]
+impl SwanStatefulInterceptor<AppState> for StatefulAudit {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+        state: Option<&AppState>,
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        state.unwrap().log.lock().unwrap().push("method-before".into());
+        Ok((request, Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(
+        &self,
+        response: reqwest::Response,
+        state: Option<&AppState>,
+    ) -> anyhow::Result<reqwest::Response> {
+        state.unwrap().log.lock().unwrap().push("method-after".into());
+        Ok(response)
+    }
+}
+
+#[http_client(base_url = "http://unused", interceptor = StatefulAuth, state = AppState)]
+struct StatefulClient;
+
+impl StatefulClient {
+    #[get(url = "/me", interceptor = StatefulAudit)]
+    async fn me(&self) -> anyhow::Result<Echo> {}
+}
+
+#[tokio::test]
+async fn stateful_interceptors_receive_client_state() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"ok":true}"#)).await;
+    let state = AppState { token: "secret".into(), ..Default::default() };
+    let mut client = StatefulClient::new().with_state(state.clone());
+    client.base_url = server.base_url.clone();
+
+    client.me().await.unwrap();
+
+    assert_eq!(server.requests()[0].header("authorization"), Some("Bearer secret"));
+    assert_eq!(
+        *state.log.lock().unwrap(),
+        vec!["global-before", "method-before", "method-after", "global-after:200"]
+    );
+}
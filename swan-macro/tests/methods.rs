@@ -0,0 +1,91 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use serde::{Deserialize, Serialize};
+use swan_macro::{head, http_client, options, patch, request};
+
+#[derive(Debug, Serialize)]
+struct UserPatch {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[http_client(base_url = "http://unused")]
+struct MethodsClient;
+
+impl MethodsClient {
+    #[patch(url = "/users/{id}", content_type = json)]
+    async fn update_user(&self, id: u32, body: UserPatch) -> anyhow::Result<User> {}
+
+    #[head(url = "/users/{id}")]
+    async fn user_exists(&self, id: u32) -> anyhow::Result<()> {}
+
+    #[options(url = "/users", header = "Origin: https://app.example.com")]
+    async fn preflight(&self) -> anyhow::Result<()> {}
+
+    #[request(method = "PROPFIND", url = "/dav/files", header = "Depth: 1")]
+    async fn list_files(&self) -> anyhow::Result<String> {}
+}
+
+async fn client_for(server: &TestServer) -> MethodsClient {
+    let mut client = MethodsClient::new();
+    client.base_url = server.base_url.clone();
+    client
+}
+
+#[tokio::test]
+async fn patch_sends_json_body() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"id":7,"name":"new"}"#)).await;
+    let client = client_for(&server).await;
+
+    let user = client.update_user(7, UserPatch { name: "new".into() }).await.unwrap();
+
+    assert_eq!(user, User { id: 7, name: "new".into() });
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PATCH");
+    assert_eq!(request.path, "/users/7");
+    assert_eq!(request.body, br#"{"name":"new"}"#);
+}
+
+#[tokio::test]
+async fn head_returns_unit() {
+    let server = TestServer::start(|_| MockResponse::new(200)).await;
+    let client = client_for(&server).await;
+
+    client.user_exists(1).await.unwrap();
+
+    assert_eq!(server.requests()[0].method, "HEAD");
+}
+
+#[tokio::test]
+async fn options_sends_preflight() {
+    let server = TestServer::start(|_| {
+        MockResponse::new(204).header("Access-Control-Allow-Methods", "GET, PATCH")
+    })
+    .await;
+    let client = client_for(&server).await;
+
+    client.preflight().await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "OPTIONS");
+    assert_eq!(request.header("origin"), Some("https://app.example.com"));
+}
+
+#[tokio::test]
+async fn request_supports_custom_methods() {
+    let server = TestServer::start(|_| MockResponse::new(207).body("<multistatus/>")).await;
+    let client = client_for(&server).await;
+
+    let body = client.list_files().await.unwrap();
+
+    assert_eq!(body, "<multistatus/>");
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PROPFIND");
+    assert_eq!(request.header("depth"), Some("1"));
+}
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swan_common::testing::{MetricsEvent, MockResponse, MockTransport, RecordingMetrics};
+use swan_common::{RequestInfo, RequestOutcome, SwanMetrics};
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "https://api.example.com")]
+struct MeteredClient;
+
+impl MeteredClient {
+    #[get(url = "/users/{id}", retry = "fixed(3, 1ms)")]
+    async fn get_user(&self, id: u32) -> anyhow::Result<String> {}
+}
+
+static FINISHED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct CountingMetrics;
+
+impl SwanMetrics for CountingMetrics {
+    fn on_request_end(&self, request: &RequestInfo<'_>, outcome: &RequestOutcome) {
+        assert_eq!(request.method, "health");
+        assert!(outcome.is_success());
+        FINISHED_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[http_client(base_url = "https://api.example.com", metrics = CountingMetrics)]
+struct CountedClient;
+
+impl CountedClient {
+    #[get(url = "/health")]
+    async fn health(&self) -> anyhow::Result<String> {}
+}
+
+fn client(transport: &MockTransport, metrics: &RecordingMetrics) -> MeteredClient {
+    MeteredClient::builder()
+        .mock_transport(transport.clone())
+        .metrics(metrics.clone())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn records_attempts_retries_and_outcome() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/users/7").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(200).body("alice"),
+    ]);
+    let metrics = RecordingMetrics::new();
+
+    assert_eq!(client(&transport, &metrics).get_user(7).await.unwrap(), "alice");
+
+    let events = metrics.events();
+    assert!(matches!(
+        &events[0],
+        MetricsEvent::RequestStart { method: "get_user", url, .. } if url.as_str() == "https://api.example.com/users/7"
+    ));
+
+    let attempts = metrics.attempts();
+    assert_eq!(attempts.len(), 2);
+    assert_eq!((attempts[0].attempt, attempts[0].status.map(|s| s.as_u16())), (1, Some(503)));
+    assert_eq!((attempts[1].attempt, attempts[1].status.map(|s| s.as_u16())), (2, Some(200)));
+    assert_eq!(metrics.retries(), [(2, std::time::Duration::from_millis(1))]);
+
+    let outcomes = metrics.outcomes();
+    assert_eq!(outcomes.len(), 1);
+    assert!(outcomes[0].is_success());
+    assert_eq!(outcomes[0].attempts, 2);
+    assert!(matches!(events.last(), Some(MetricsEvent::RequestEnd { .. })));
+}
+
+#[tokio::test]
+async fn records_failed_outcomes() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/users/404").respond(MockResponse::new(404));
+    let metrics = RecordingMetrics::new();
+    let client = client(&transport, &metrics);
+
+    assert!(client.get_user(404).await.is_err());
+    // 没有匹配的模拟规则时请求以传输层错误结束
+    assert!(client.get_user(1).await.is_err());
+
+    let outcomes = metrics.outcomes();
+    assert_eq!(outcomes[0].status.map(|s| s.as_u16()), Some(404));
+    assert_eq!(outcomes[0].error, None);
+    assert!(!outcomes[0].is_success());
+    assert_eq!(outcomes[1].status, None);
+    assert_eq!(outcomes[1].error, Some("request"));
+}
+
+#[tokio::test]
+async fn metrics_attribute_registers_default_instance() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/health").respond(MockResponse::new(200).body("ok"));
+    let client = CountedClient::with_mock_transport(transport);
+
+    client.health().await.unwrap();
+    client.health().await.unwrap();
+
+    assert_eq!(FINISHED_CALLS.load(Ordering::SeqCst), 2);
+}
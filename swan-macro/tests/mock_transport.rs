@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_macro::{get, http_client, post};
+
+#[derive(Debug, Serialize)]
+struct NewUser {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[http_client(base_url = "https://api.example.com")]
+struct MockedClient;
+
+impl MockedClient {
+    #[get(url = "/users")]
+    async fn list_users(&self, #[query] page: u32, #[header("X-Tenant")] tenant: &str) -> anyhow::Result<Vec<User>> {}
+
+    #[post(url = "/users", content_type = json)]
+    async fn create_user(&self, user: NewUser) -> anyhow::Result<User> {}
+
+    #[get(url = "/flaky", retry = "exponential(3, 1ms)")]
+    async fn flaky(&self) -> anyhow::Result<String> {}
+}
+
+#[tokio::test]
+async fn requests_are_served_by_matching_rules() {
+    let transport = MockTransport::new();
+    let list = transport.mock("GET", "/users")
+        .query("page", "2")
+        .header("X-Tenant", "acme")
+        .respond(MockResponse::json(r#"[{"id":1,"name":"amy"}]"#));
+    let create = transport.mock("POST", "/users")
+        .json_body(serde_json::json!({ "name": "bo" }))
+        .respond(MockResponse::json(r#"{"id":2,"name":"bo"}"#).header("Location", "/users/2"));
+    let client = MockedClient::with_mock_transport(transport.clone());
+
+    let users = client.list_users(2, "acme").await.unwrap();
+    let user = client.create_user(NewUser { name: "bo".into() }).await.unwrap();
+
+    assert_eq!(users, vec![User { id: 1, name: "amy".into() }]);
+    assert_eq!(user, User { id: 2, name: "bo".into() });
+    list.assert_calls(1);
+    create.assert_calls(1);
+    assert_eq!(transport.requests()[0].url.as_str(), "https://api.example.com/users?page=2");
+}
+
+#[tokio::test]
+async fn unmatched_request_is_an_error() {
+    let transport = MockTransport::new();
+    let list = transport.mock("GET", "/users").query("page", "1").respond(MockResponse::json("[]"));
+    let client = MockedClient::with_mock_transport(transport.clone());
+
+    let error = client.list_users(2, "acme").await.unwrap_err();
+
+    assert!(error.to_string().contains("No mock matches GET"));
+    list.assert_calls(0);
+    assert_eq!(transport.calls(), 1);
+}
+
+#[tokio::test]
+async fn retries_and_status_errors_use_canned_responses() {
+    let transport = MockTransport::new();
+    let flaky = transport.mock("GET", "/flaky")
+        .respond_sequence([MockResponse::new(503), MockResponse::new(200).body("ok")]);
+    let client = MockedClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.flaky().await.unwrap(), "ok");
+    flaky.assert_calls(2);
+
+    let missing = MockTransport::new();
+    missing.mock("GET", "/flaky").respond(MockResponse::new(404).body("gone"));
+    let client = MockedClient::with_mock_transport(missing);
+
+    let error = client.flaky().await.unwrap_err().downcast::<swan_common::SwanError>().unwrap();
+    assert_eq!(error.status().unwrap(), 404);
+}
+
+#[tokio::test]
+async fn builder_defaults_are_visible_to_mock() {
+    let transport = MockTransport::new();
+    transport.mock("GET", "/users").header("User-Agent", "swan-test").respond(MockResponse::json("[]"));
+    let client = MockedClient::builder()
+        .user_agent("swan-test")
+        .default_header("X-Api-Key", "secret")
+        .mock_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    client.list_users(1, "acme").await.unwrap();
+
+    assert_eq!(transport.requests()[0].header("x-api-key"), Some("secret"));
+}
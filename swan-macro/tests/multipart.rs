@@ -0,0 +1,64 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use std::path::PathBuf;
+use swan_common::FilePart;
+use swan_macro::{SwanMultipart, http_client, post};
+
+#[derive(SwanMultipart)]
+struct Upload {
+    title: String,
+    #[multipart(rename = "page-count")]
+    pages: u32,
+    note: Option<String>,
+    #[multipart(file_name = "thumb.png", mime = "image/png")]
+    thumbnail: Vec<u8>,
+    attachment: PathBuf,
+    #[multipart(file_name = "log.txt", mime = "text/plain")]
+    log: FilePart,
+}
+
+#[http_client(base_url = "http://unused")]
+struct UploadClient;
+
+impl UploadClient {
+    #[post(url = "/uploads", content_type = form_multipart)]
+    async fn upload(&self, upload: Upload) -> anyhow::Result<String> {}
+}
+
+#[tokio::test]
+async fn multipart_form_is_encoded_with_boundary() {
+    let server = TestServer::start(|_| MockResponse::new(200).body("ok")).await;
+    let client = UploadClient::builder().base_url(&server.base_url).build().unwrap();
+    let attachment = std::env::temp_dir().join(format!("swan-multipart-{}.json", std::process::id()));
+    tokio::fs::write(&attachment, br#"{"a":1}"#).await.unwrap();
+
+    let upload = Upload {
+        title: "report".into(),
+        pages: 3,
+        note: None,
+        thumbnail: vec![0x89, b'P', b'N', b'G'],
+        attachment: attachment.clone(),
+        log: FilePart::reader(std::io::Cursor::new(b"line 1\nline 2".to_vec())),
+    };
+    client.upload(upload).await.unwrap();
+    tokio::fs::remove_file(&attachment).await.unwrap();
+
+    let request = &server.requests()[0];
+    let content_type = request.header("content-type").unwrap();
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    let boundary = content_type.split("boundary=").nth(1).unwrap();
+
+    let body = String::from_utf8_lossy(&request.body);
+    assert_eq!(body.matches(&format!("--{}\r\n", boundary)).count(), 5);
+    assert!(body.contains("name=\"title\"\r\n\r\nreport\r\n"));
+    assert!(body.contains("name=\"page-count\"\r\n\r\n3\r\n"));
+    assert!(!body.contains("name=\"note\""));
+    assert!(body.contains("name=\"thumbnail\"; filename=\"thumb.png\"\r\nContent-Type: image/png"));
+    let attachment_name = attachment.file_name().unwrap().to_str().unwrap();
+    assert!(body.contains(&format!(
+        "name=\"attachment\"; filename=\"{}\"\r\nContent-Type: application/json\r\n\r\n{{\"a\":1}}",
+        attachment_name
+    )));
+    assert!(body.contains("name=\"log\"; filename=\"log.txt\"\r\nContent-Type: text/plain\r\n\r\nline 1\nline 2"));
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{MockResponse, TestServer};
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swan_common::{SwanError, SwanInterceptor};
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct User {
+    id: u32,
+}
+
+#[derive(Default)]
+struct TokenInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for TokenInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        Ok((request.header("Authorization", "Bearer page"), Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(
+        &self,
+        response: reqwest::Response,
+    ) -> anyhow::Result<reqwest::Response> {
+        Ok(response)
+    }
+}
+
+#[http_client(base_url = "http://unused")]
+struct PagingClient;
+
+impl PagingClient {
+    #[get(url = "/links", paginate = link_header, interceptor = TokenInterceptor)]
+    fn list_linked(&self) -> impl Stream<Item = anyhow::Result<User>> + '_ {}
+
+    #[get(url = "/cursors", paginate = cursor(param = "cursor", next = "/next_cursor", items = "/data"))]
+    fn list_cursor(&self, #[query] limit: u32) -> impl Stream<Item = Result<User, SwanError>> + '_ {}
+
+    #[get(url = "/pages", paginate = offset(param = "page"), retry = "exponential(3, 10ms)", proxy = false)]
+    fn list_pages(&self) -> impl Stream<Item = anyhow::Result<User>> + '_ {}
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+fn client(server: &TestServer) -> PagingClient {
+    PagingClient::builder().base_url(&server.base_url).build().unwrap()
+}
+
+#[tokio::test]
+async fn link_header_follows_next_and_reuses_interceptors() {
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/links" => MockResponse::json(r#"[{"id":1},{"id":2}]"#).header("Link", r#"</links?after=2>; rel="next""#),
+        "/links?after=2" => MockResponse::json(r#"[{"id":3}]"#).header("Link", r#"</links>; rel="first""#),
+        _ => MockResponse::new(404),
+    })
+    .await;
+    let client = client(&server);
+
+    let users: Vec<User> = client.list_linked().try_collect().await.unwrap();
+
+    assert_eq!(users.iter().map(|user| user.id).collect::<Vec<_>>(), [1, 2, 3]);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|request| request.header("authorization") == Some("Bearer page")));
+}
+
+#[tokio::test]
+async fn cursor_keeps_other_query_params() {
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/cursors?limit=2" => MockResponse::json(r#"{"data":[{"id":1},{"id":2}],"next_cursor":"abc"}"#),
+        "/cursors?limit=2&cursor=abc" => MockResponse::json(r#"{"data":[{"id":3}],"next_cursor":null}"#),
+        _ => MockResponse::new(404),
+    })
+    .await;
+    let client = client(&server);
+
+    let users: Vec<User> = client.list_cursor(2).try_collect().await.unwrap();
+
+    assert_eq!(users.len(), 3);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn offset_retries_each_page_and_stops_on_empty_page() {
+    static PAGE_TWO_CALLS: AtomicUsize = AtomicUsize::new(0);
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/pages?page=1" => MockResponse::json(r#"[{"id":1}]"#),
+        // 第二页第一次返回 503，由重试策略重新发送
+        "/pages?page=2" if PAGE_TWO_CALLS.fetch_add(1, Ordering::SeqCst) == 0 => MockResponse::new(503),
+        "/pages?page=2" => MockResponse::json(r#"[{"id":2}]"#),
+        "/pages?page=3" => MockResponse::json("[]"),
+        _ => MockResponse::new(404),
+    })
+    .await;
+    let client = client(&server);
+
+    let users: Vec<User> = client.list_pages().try_collect().await.unwrap();
+
+    assert_eq!(users, [User { id: 1 }, User { id: 2 }]);
+    let paths: Vec<String> = server.requests().into_iter().map(|request| request.path).collect();
+    assert_eq!(paths, ["/pages?page=1", "/pages?page=2", "/pages?page=2", "/pages?page=3"]);
+}
+
+#[tokio::test]
+async fn pages_are_fetched_lazily() {
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/pages?page=1" => MockResponse::json(r#"[{"id":1},{"id":2}]"#),
+        _ => MockResponse::json(r#"[{"id":3}]"#),
+    })
+    .await;
+    let client = client(&server);
+
+    let stream = client.list_pages();
+    assert_send(&stream);
+    assert!(server.requests().is_empty());
+
+    let first_two: Vec<User> = stream.take(2).map(|user| user.unwrap()).collect().await;
+    assert_eq!(first_two.len(), 2);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn error_page_ends_the_stream() {
+    let server = TestServer::start(|request| match request.path.as_str() {
+        "/cursors?limit=1" => MockResponse::json(r#"{"data":[{"id":1}],"next_cursor":"x"}"#),
+        _ => MockResponse::new(500).body("boom"),
+    })
+    .await;
+    let client = client(&server);
+
+    let results: Vec<Result<User, SwanError>> = client.list_cursor(1).collect().await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(&results[1], Err(SwanError::Status { status, .. }) if status.as_u16() == 500));
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use serde::{Deserialize, Serialize};
+use swan_macro::{get, http_client, post};
+
+#[derive(Debug, Serialize)]
+struct NewUser {
+    name: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[http_client(base_url = "http://unused")]
+struct ParamsClient;
+
+impl ParamsClient {
+    #[get(url = "/orgs/{org}/users")]
+    async fn list_users(
+        &self,
+        #[path] org: &str,
+        #[query] page: u32,
+        #[query("pageSize")] page_size: u32,
+        #[query] search: Option<String>,
+        #[header("X-Tenant")] tenant: &str,
+        #[header("X-Trace")] trace: Option<String>,
+    ) -> anyhow::Result<Vec<User>> {}
+
+    #[post(url = "/orgs/{org}/users", content_type = json)]
+    async fn create_user(
+        &self,
+        #[body] user: NewUser,
+        org: &str,
+        #[query] dry_run: bool,
+    ) -> anyhow::Result<User> {}
+
+    #[post(url = "/users", content_type = json)]
+    async fn create_plain(&self, user: NewUser) -> anyhow::Result<User> {}
+}
+
+async fn client_for(server: &TestServer) -> ParamsClient {
+    ParamsClient::builder().base_url(&server.base_url).build().unwrap()
+}
+
+#[tokio::test]
+async fn query_and_header_params_are_sent() {
+    let server = TestServer::start(|_| MockResponse::json("[]")).await;
+    let client = client_for(&server).await;
+
+    client.list_users("acme", 2, 50, None, "t-1", Some("abc".into())).await.unwrap();
+    client.list_users("acme", 1, 10, Some("bob".into()), "t-2", None).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/orgs/acme/users?page=2&pageSize=50");
+    assert_eq!(requests[0].header("x-tenant"), Some("t-1"));
+    assert_eq!(requests[0].header("x-trace"), Some("abc"));
+    assert_eq!(requests[1].path, "/orgs/acme/users?page=1&pageSize=10&search=bob");
+    assert_eq!(requests[1].header("x-trace"), None);
+}
+
+#[tokio::test]
+async fn body_param_can_be_at_any_position() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"id":1,"name":"amy"}"#)).await;
+    let client = client_for(&server).await;
+
+    let user = client.create_user(NewUser { name: "amy".into() }, "acme", true).await.unwrap();
+
+    assert_eq!(user, User { id: 1, name: "amy".into() });
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/orgs/acme/users?dry_run=true");
+    assert_eq!(request.body, br#"{"name":"amy"}"#);
+}
+
+#[tokio::test]
+async fn single_unannotated_param_is_body() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"id":2,"name":"bo"}"#)).await;
+    let client = client_for(&server).await;
+
+    client.create_plain(NewUser { name: "bo".into() }).await.unwrap();
+
+    assert_eq!(server.requests()[0].body, br#"{"name":"bo"}"#);
+}
+
+#[http_client(base_url = "http://unused")]
+struct EncodingClient;
+
+impl EncodingClient {
+    #[get(url = "/users/{id}/files/{*path}?q={q}")]
+    async fn file(&self, id: &str, path: &str, q: &str) -> anyhow::Result<String> {}
+}
+
+#[tokio::test]
+async fn url_placeholders_are_percent_encoded() {
+    let server = TestServer::start(|_| MockResponse::new(200)).await;
+    let client = EncodingClient::builder().base_url(&server.base_url).build().unwrap();
+
+    client.file("a/b?c", "docs/my notes.txt", "x&y=z").await.unwrap();
+
+    assert_eq!(
+        server.requests()[0].path,
+        "/users/a%2Fb%3Fc/files/docs/my%20notes.txt?q=x%26y%3Dz"
+    );
+}
+
+#[tokio::test]
+async fn dot_segment_placeholder_values_are_rejected() {
+    let server = TestServer::start(|_| MockResponse::new(200)).await;
+    let client = EncodingClient::builder().base_url(&server.base_url).build().unwrap();
+
+    for id in ["..", "."] {
+        let error = client.file(id, "index.html", "x").await.unwrap_err();
+        let error = error.downcast_ref::<swan_common::SwanError>().unwrap();
+        assert_eq!(error.kind(), "request");
+    }
+    assert!(server.requests().is_empty());
+
+    client.file("..a", "index.html", "x").await.unwrap();
+    assert_eq!(server.requests()[0].path, "/users/..a/files/index.html?q=x");
+}
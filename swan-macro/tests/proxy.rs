@@ -0,0 +1,41 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use futures_util::{Stream, StreamExt};
+use swan_common::SwanError;
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "http://unused")]
+struct ProxyClient;
+
+impl ProxyClient {
+    /// 端口 9 上没有代理，只有绕过代理的主机可以访问
+    #[get(url = "/ping", proxy(url = "http://127.0.0.1:9", no_proxy = "localhost,127.0.0.1"))]
+    async fn bypassed(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/ping", proxy = "http://exa mple.com:8080")]
+    async fn invalid_proxy(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/items", paginate = offset(param = "page"), proxy = "http://exa mple.com:8080")]
+    fn invalid_proxy_pages(&self) -> impl Stream<Item = Result<String, SwanError>> + '_ {}
+}
+
+#[tokio::test]
+async fn no_proxy_hosts_bypass_the_method_proxy() {
+    let server = TestServer::start(|_| MockResponse::new(200).body("pong")).await;
+    let client = ProxyClient::builder().base_url(&server.base_url).build().unwrap();
+
+    assert_eq!(client.bypassed().await.unwrap(), "pong");
+}
+
+#[tokio::test]
+async fn invalid_method_proxy_is_returned_as_error() {
+    let client = ProxyClient::new();
+
+    let error = client.invalid_proxy().await.unwrap_err();
+    assert_eq!(error.kind(), "request");
+
+    let mut pages = Box::pin(client.invalid_proxy_pages());
+    assert_eq!(pages.next().await.unwrap().unwrap_err().kind(), "request");
+    assert!(pages.next().await.is_none());
+}
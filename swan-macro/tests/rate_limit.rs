@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_common::{RateLimitConfig, SwanError};
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "https://api.example.com", rate_limit = "10/s, burst=2")]
+struct VendorClient;
+
+impl VendorClient {
+    #[get(url = "/items")]
+    async fn items(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/items", retry = "exponential(3, 1ms)")]
+    async fn items_with_retry(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/search", rate_limit = "1/s, on_exceed=error")]
+    async fn search(&self) -> Result<String, SwanError> {}
+}
+
+#[tokio::test]
+async fn calls_wait_for_permits_beyond_burst() {
+    let transport = MockTransport::new();
+    let items = transport.mock("GET", "/items").respond(MockResponse::new(200).body("[]"));
+    let client = VendorClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    for _ in 0..4 {
+        client.items().await.unwrap();
+    }
+
+    // 突发 2 个之后，每 100ms 补充一个令牌
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(180), "elapsed {elapsed:?}");
+    items.assert_calls(4);
+}
+
+#[tokio::test]
+async fn retries_consume_permits() {
+    let transport = MockTransport::new();
+    let items = transport.mock("GET", "/items").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(503),
+        MockResponse::new(503),
+        MockResponse::new(200).body("[]"),
+    ]);
+    let client = VendorClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    client.items_with_retry().await.unwrap_err();
+    client.items().await.unwrap();
+
+    // 4 次尝试，突发 2 个之后还需要等待两个令牌
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(180), "elapsed {elapsed:?}");
+    items.assert_calls(4);
+}
+
+#[tokio::test]
+async fn method_limit_can_fail_fast() {
+    let transport = MockTransport::new();
+    let search = transport.mock("GET", "/search").respond(MockResponse::new(200).body("ok"));
+    let client = VendorClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.search().await.unwrap(), "ok");
+    let error = client.search().await.unwrap_err();
+
+    assert!(error.is_rate_limited(), "unexpected error: {}", error);
+    search.assert_calls(1);
+
+    // 方法级限流器属于客户端实例
+    let other = VendorClient::with_mock_transport(transport.clone());
+    assert_eq!(other.search().await.unwrap(), "ok");
+}
+
+#[tokio::test]
+async fn builder_overrides_client_rate_limit() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/items").respond(MockResponse::new(200).body("[]"));
+    let client = VendorClient::builder()
+        .rate_limit(RateLimitConfig::new(1000, Duration::from_secs(1)))
+        .mock_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    for _ in 0..10 {
+        client.items().await.unwrap();
+    }
+
+    assert!(started.elapsed() < Duration::from_millis(100));
+}
+
+#[test]
+fn builder_rejects_zero_rate_limit() {
+    for config in [
+        RateLimitConfig::new(0, Duration::from_secs(1)),
+        RateLimitConfig::new(10, Duration::from_secs(1)).burst(0),
+    ] {
+        let error = VendorClient::builder().rate_limit(config).build().err().unwrap();
+        assert_eq!(error.kind(), "request");
+    }
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{MockResponse, TestServer};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swan_common::Response;
+use swan_macro::{get, http_client};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u32,
+}
+
+/// 与 `swan_common::Response` 同名的业务信封类型
+mod api {
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    pub struct Response<T> {
+        pub code: u32,
+        pub data: T,
+    }
+}
+
+#[http_client(base_url = "http://unused")]
+struct ResponseClient;
+
+impl ResponseClient {
+    #[get(url = "/users")]
+    async fn list_users(&self) -> anyhow::Result<Response<Vec<User>>> {}
+
+    #[get(url = "/users", retry = "exponential(3, 10ms)")]
+    async fn list_users_with_retry(&self) -> anyhow::Result<swan_common::Response<Vec<User>>> {}
+
+    #[get(url = "/users")]
+    async fn list_users_plain(&self) -> anyhow::Result<Vec<User>> {}
+
+    #[get(url = "/envelope")]
+    async fn enveloped_user(&self) -> anyhow::Result<api::Response<User>> {}
+}
+
+#[tokio::test]
+async fn response_wrapper_carries_metadata() {
+    let server = TestServer::start(|_| {
+        MockResponse::json(r#"[{"id":1},{"id":2}]"#).header("X-Total-Count", "42")
+    })
+    .await;
+    let client = ResponseClient::builder().base_url(&server.base_url).build().unwrap();
+
+    let response = client.list_users().await.unwrap();
+
+    assert_eq!(response.status, reqwest::StatusCode::OK);
+    assert_eq!(response.header("x-total-count"), Some("42"));
+    assert_eq!(response.url.path(), "/users");
+    assert_eq!(response.attempts, 1);
+    assert_eq!(response.body, vec![User { id: 1 }, User { id: 2 }]);
+}
+
+#[tokio::test]
+async fn user_types_named_response_are_decoded_as_is() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"code":0,"data":{"id":3}}"#)).await;
+    let client = ResponseClient::builder().base_url(&server.base_url).build().unwrap();
+
+    let envelope = client.enveloped_user().await.unwrap();
+
+    assert_eq!(envelope, api::Response { code: 0, data: User { id: 3 } });
+}
+
+#[tokio::test]
+async fn response_wrapper_counts_retry_attempts() {
+    let calls = AtomicUsize::new(0);
+    let server = TestServer::start(move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::new(503)
+        } else {
+            MockResponse::json(r#"[{"id":7}]"#)
+        }
+    })
+    .await;
+    let client = ResponseClient::builder().base_url(&server.base_url).build().unwrap();
+
+    let response = client.list_users_with_retry().await.unwrap();
+
+    assert_eq!(response.attempts, 2);
+    assert_eq!(response.into_body(), vec![User { id: 7 }]);
+}
+
+#[tokio::test]
+async fn plain_return_type_is_unchanged() {
+    let server = TestServer::start(|_| MockResponse::json(r#"[{"id":3}]"#)).await;
+    let client = ResponseClient::builder().base_url(&server.base_url).build().unwrap();
+
+    assert_eq!(client.list_users_plain().await.unwrap(), vec![User { id: 3 }]);
+}
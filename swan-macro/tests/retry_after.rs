@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "https://api.example.com")]
+struct RetryAfterClient;
+
+impl RetryAfterClient {
+    #[get(url = "/limited", retry = "exponential(2, 1ms, max_delay=300ms)")]
+    async fn limited(&self) -> anyhow::Result<String> {}
+
+    #[get(url = "/limited", retry = "exponential(2, 1ms, max_delay=300ms, respect_retry_after=false)")]
+    async fn limited_ignoring_header(&self) -> anyhow::Result<String> {}
+
+    #[get(url = "/limited", retry = "fixed(2, 2s)")]
+    async fn limited_fixed(&self) -> anyhow::Result<String> {}
+}
+
+fn throttled_then_ok(transport: &MockTransport, retry_after: &str) {
+    let _ = transport.mock("GET", "/limited").respond_sequence([
+        MockResponse::new(429).header("Retry-After", retry_after),
+        MockResponse::new(200).body("ok"),
+    ]);
+}
+
+#[tokio::test]
+async fn retry_after_seconds_is_used_as_delay() {
+    let transport = MockTransport::new();
+    throttled_then_ok(&transport, "0");
+    let client = RetryAfterClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    assert_eq!(client.limited_fixed().await.unwrap(), "ok");
+
+    assert!(started.elapsed() < Duration::from_millis(200));
+    assert_eq!(transport.calls(), 2);
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_by_max_delay() {
+    let transport = MockTransport::new();
+    throttled_then_ok(&transport, "60");
+    let client = RetryAfterClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    assert_eq!(client.limited().await.unwrap(), "ok");
+
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(300), "elapsed {elapsed:?}");
+    assert!(elapsed < Duration::from_secs(5), "elapsed {elapsed:?}");
+}
+
+#[tokio::test]
+async fn retry_after_http_date_is_honored() {
+    let transport = MockTransport::new();
+    throttled_then_ok(&transport, "Sun, 06 Nov 2095 08:49:37 GMT");
+    let client = RetryAfterClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    assert_eq!(client.limited().await.unwrap(), "ok");
+
+    assert!(started.elapsed() >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn respect_retry_after_false_uses_backoff() {
+    let transport = MockTransport::new();
+    throttled_then_ok(&transport, "60");
+    let client = RetryAfterClient::with_mock_transport(transport.clone());
+
+    let started = Instant::now();
+    assert_eq!(client.limited_ignoring_header().await.unwrap(), "ok");
+
+    assert!(started.elapsed() < Duration::from_millis(200));
+    assert_eq!(transport.calls(), 2);
+}
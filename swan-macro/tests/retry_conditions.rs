@@ -0,0 +1,107 @@
+mod common;
+
+use common::hanging_server;
+use std::sync::atomic::Ordering;
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_common::{RetryOutcome, SwanError, SwanRetryPredicate};
+use swan_macro::{get, http_client, put};
+
+/// 仅在 409 且错误码为 LOCK_CONFLICT 时重试，其余情况沿用默认条件
+#[derive(Default)]
+struct RetryOnLockConflict;
+
+impl SwanRetryPredicate for RetryOnLockConflict {
+    fn should_retry(&self, outcome: &RetryOutcome<'_>) -> Option<bool> {
+        if outcome.status()? == 409 {
+            return Some(outcome.header("X-Error-Code") == Some("LOCK_CONFLICT"));
+        }
+        None
+    }
+}
+
+#[http_client(base_url = "http://unused")]
+struct ConditionalClient;
+
+impl ConditionalClient {
+    #[get(url = "/gateway", retry = "exponential(3, 1ms, on_status=[502,503,504])")]
+    async fn gateway(&self) -> Result<String, SwanError> {}
+
+    #[put(url = "/locks/1", retry = "fixed(3, 1ms)", retry_predicate = RetryOnLockConflict)]
+    async fn acquire(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/slow", timeout = "50ms", retry = "exponential(3, 1ms, on_error=[connect])")]
+    async fn slow_connect_only(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/slow", timeout = "50ms", retry = "exponential(3, 1ms, on_error=[connect, timeout])")]
+    async fn slow(&self) -> Result<String, SwanError> {}
+}
+
+#[tokio::test]
+async fn only_listed_statuses_are_retried() {
+    let transport = MockTransport::new();
+    let gateway = transport.mock("GET", "/gateway").respond(MockResponse::new(500));
+    let client = ConditionalClient::with_mock_transport(transport.clone());
+
+    let error = client.gateway().await.unwrap_err();
+    assert_eq!(error.status().map(|s| s.as_u16()), Some(500));
+    gateway.assert_calls(1);
+
+    let transport = MockTransport::new();
+    let gateway = transport.mock("GET", "/gateway").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(200).body("up"),
+    ]);
+    let client = ConditionalClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.gateway().await.unwrap(), "up");
+    gateway.assert_calls(2);
+}
+
+#[tokio::test]
+async fn predicate_decides_on_status_and_headers() {
+    let transport = MockTransport::new();
+    let locks = transport.mock("PUT", "/locks/1").respond_sequence([
+        MockResponse::new(409).header("X-Error-Code", "LOCK_CONFLICT"),
+        MockResponse::new(200).body("locked"),
+    ]);
+    let client = ConditionalClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.acquire().await.unwrap(), "locked");
+    locks.assert_calls(2);
+
+    let transport = MockTransport::new();
+    let locks = transport.mock("PUT", "/locks/1")
+        .respond(MockResponse::new(409).header("X-Error-Code", "DUPLICATE"));
+    let client = ConditionalClient::with_mock_transport(transport.clone());
+
+    let error = client.acquire().await.unwrap_err();
+    assert_eq!(error.status().map(|s| s.as_u16()), Some(409));
+    locks.assert_calls(1);
+}
+
+#[tokio::test]
+async fn predicate_falls_back_to_default_conditions() {
+    let transport = MockTransport::new();
+    let locks = transport.mock("PUT", "/locks/1").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(200).body("locked"),
+    ]);
+    let client = ConditionalClient::with_mock_transport(transport.clone());
+
+    assert_eq!(client.acquire().await.unwrap(), "locked");
+    locks.assert_calls(2);
+}
+
+#[tokio::test]
+async fn only_listed_error_classes_are_retried() {
+    let (base_url, accepted) = hanging_server().await;
+    let client = ConditionalClient::builder().base_url(base_url).build().unwrap();
+
+    let error = client.slow_connect_only().await.unwrap_err();
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert_eq!(accepted.load(Ordering::SeqCst), 1);
+
+    let error = client.slow().await.unwrap_err();
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert_eq!(accepted.load(Ordering::SeqCst), 4);
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{MockResponse, RecordedRequest, TestServer};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use swan_common::signing::SigningRequest;
+use swan_common::{SwanError, SwanSigner};
+use swan_macro::{http_client, put};
+
+#[derive(Debug, Serialize)]
+struct Event {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ack {
+    ok: bool,
+}
+
+#[derive(Default)]
+struct TenantInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for TenantInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        Ok((request.header("X-Tenant", "acme"), Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(&self, response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+        Ok(response)
+    }
+}
+
+#[http_client(
+    base_url = "http://unused",
+    interceptor = TenantInterceptor,
+    signer = hmac(secret = "s3cret", key_id = "svc-a", signed_headers = ["x-tenant"]),
+)]
+struct HmacClient;
+
+impl HmacClient {
+    #[put(url = "/events/{id}", content_type = json, retry = "exponential(3, 10ms)")]
+    async fn put_event(&self, id: u32, event: Event) -> Result<Ack, SwanError> {}
+}
+
+#[http_client(
+    base_url = "http://unused",
+    signer = hmac(secret = "s3cret", key_id = "svc-a", signed_headers = ["x-tenant"]),
+)]
+struct DefaultHeaderHmacClient;
+
+impl DefaultHeaderHmacClient {
+    #[put(url = "/events/{id}", content_type = json)]
+    async fn put_event(&self, id: u32, event: Event) -> Result<Ack, SwanError> {}
+}
+
+#[http_client(base_url = "http://unused", signer = aws_sigv4(region = "us-east-1", service = "s3", access_key_id = "AKID", secret_access_key = "secret"))]
+struct S3Client;
+
+impl S3Client {
+    #[put(url = "/bucket/{key}", content_type = json)]
+    async fn put_object(&self, key: &str, event: Event) -> anyhow::Result<()> {}
+}
+
+/// 按 `HmacSigner` 的格式在服务端重新计算签名
+fn hmac_is_valid(request: &RecordedRequest) -> bool {
+    let (Some(timestamp), Some(signature)) = (request.header("x-timestamp"), request.header("x-signature")) else {
+        return false;
+    };
+    let string_to_sign = format!(
+        "{}\n{}\n{}\nx-tenant:{}\n{}",
+        request.method,
+        request.path,
+        timestamp,
+        request.header("x-tenant").unwrap_or_default(),
+        hex::encode(Sha256::digest(&request.body)),
+    );
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+    mac.update(string_to_sign.as_bytes());
+    mac.verify_slice(&hex::decode(signature).unwrap_or_default()).is_ok()
+}
+
+#[tokio::test]
+async fn hmac_signature_covers_interceptor_headers_and_body_on_every_attempt() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    let server = TestServer::start(|request| {
+        if !hmac_is_valid(request) || request.header("x-key-id") != Some("svc-a") {
+            return MockResponse::new(401);
+        }
+        match ATTEMPTS.fetch_add(1, Ordering::SeqCst) {
+            0 => MockResponse::new(503),
+            _ => MockResponse::json(r#"{"ok":true}"#),
+        }
+    })
+    .await;
+    let client = HmacClient::builder().base_url(&server.base_url).build().unwrap();
+
+    let ack = client.put_event(7, Event { name: "deploy".into() }).await.unwrap();
+    assert!(ack.ok);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(hmac_is_valid));
+    assert_eq!(requests[0].header("x-tenant"), Some("acme"));
+}
+
+#[tokio::test]
+async fn aws_sigv4_signs_s3_requests() {
+    let server = TestServer::start(|_| MockResponse::new(200)).await;
+    let client = S3Client::builder().base_url(&server.base_url).build().unwrap();
+
+    client.put_object("report.json", Event { name: "deploy".into() }).await.unwrap();
+
+    let request = &server.requests()[0];
+    let body_hash = hex::encode(Sha256::digest(&request.body));
+    assert_eq!(request.header("x-amz-content-sha256"), Some(body_hash.as_str()));
+    let authorization = request.header("authorization").unwrap();
+    assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"), "{}", authorization);
+    assert!(authorization.contains("/us-east-1/s3/aws4_request, SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date,"));
+}
+
+#[tokio::test]
+async fn hmac_signature_covers_client_default_headers() {
+    let server = TestServer::start(|request| match hmac_is_valid(request) {
+        true => MockResponse::json(r#"{"ok":true}"#),
+        false => MockResponse::new(401),
+    })
+    .await;
+    let client = DefaultHeaderHmacClient::builder()
+        .base_url(&server.base_url)
+        .default_header("X-Tenant", "acme")
+        .build()
+        .unwrap();
+
+    assert!(client.put_event(3, Event { name: "deploy".into() }).await.unwrap().ok);
+    assert_eq!(server.requests()[0].header("x-tenant"), Some("acme"));
+}
+
+#[tokio::test]
+async fn aws_sigv4_signs_client_default_headers() {
+    let server = TestServer::start(|_| MockResponse::new(200)).await;
+    let client = S3Client::builder()
+        .base_url(&server.base_url)
+        .default_header("x-amz-meta-team", "ops")
+        .build()
+        .unwrap();
+
+    client.put_object("report.json", Event { name: "deploy".into() }).await.unwrap();
+
+    let authorization = server.requests()[0].header("authorization").unwrap().to_string();
+    assert!(authorization.contains("SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date;x-amz-meta-team,"), "{}", authorization);
+}
+
+/// 记录签名器收到的请求
+#[derive(Clone, Default)]
+struct RecordingSigner {
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl SwanSigner for RecordingSigner {
+    async fn sign(&self, request: &mut SigningRequest<'_>) -> Result<(), SwanError> {
+        let body = String::from_utf8_lossy(request.body().unwrap_or_default()).into_owned();
+        self.seen.lock().unwrap().push(format!("{} {} {}", request.method(), request.url().path(), body));
+        request.headers_mut().insert("x-signed", "1".parse().unwrap());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn runtime_signer_overrides_macro_signer() {
+    let server = TestServer::start(|request| match request.header("x-signed") {
+        Some("1") => MockResponse::json(r#"{"ok":true}"#),
+        _ => MockResponse::new(401),
+    })
+    .await;
+    let signer = RecordingSigner::default();
+    let client = HmacClient::builder().base_url(&server.base_url).signer(signer.clone()).build().unwrap();
+
+    client.put_event(1, Event { name: "a".into() }).await.unwrap();
+
+    assert_eq!(*signer.seen.lock().unwrap(), [r#"PUT /events/1 {"name":"a"}"#]);
+    assert!(server.requests()[0].header("x-signature").is_none());
+}
+
+/// 记录每次签名的时间
+#[derive(Clone, Default)]
+struct TimingSigner {
+    signed_at: Arc<Mutex<Vec<std::time::Instant>>>,
+}
+
+#[async_trait]
+impl SwanSigner for TimingSigner {
+    async fn sign(&self, _request: &mut SigningRequest<'_>) -> Result<(), SwanError> {
+        self.signed_at.lock().unwrap().push(std::time::Instant::now());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn signing_happens_after_rate_limit_wait() {
+    let server = TestServer::start(|_| MockResponse::json(r#"{"ok":true}"#)).await;
+    let signer = TimingSigner::default();
+    let client = HmacClient::builder()
+        .base_url(&server.base_url)
+        .signer(signer.clone())
+        .rate_limit(swan_common::RateLimitConfig::new(10, std::time::Duration::from_secs(1)).burst(1))
+        .build()
+        .unwrap();
+
+    client.put_event(1, Event { name: "a".into() }).await.unwrap();
+    let queued_at = std::time::Instant::now();
+    client.put_event(2, Event { name: "b".into() }).await.unwrap();
+
+    let signed_at = signer.signed_at.lock().unwrap()[1];
+    assert!(signed_at.duration_since(queued_at) >= std::time::Duration::from_millis(80));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use async_trait::async_trait;
+use common::{MockResponse, TestServer};
+use futures_util::{Stream, StreamExt};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use swan_common::{ByteStream, Bytes};
+use swan_macro::{get, http_client};
+
+static AFTER_RESPONSE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct CountingInterceptor;
+
+#[async_trait]
+impl SwanInterceptor for CountingInterceptor {
+    async fn before_request<'a>(
+        &self,
+        request: reqwest::RequestBuilder,
+        request_body: &'a [u8],
+    ) -> anyhow::Result<(reqwest::RequestBuilder, Cow<'a, [u8]>)> {
+        Ok((request, Cow::Borrowed(request_body)))
+    }
+
+    async fn after_response(&self, response: reqwest::Response) -> anyhow::Result<reqwest::Response> {
+        AFTER_RESPONSE_CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(response)
+    }
+}
+
+#[http_client(base_url = "http://unused", interceptor = CountingInterceptor)]
+struct DownloadClient;
+
+impl DownloadClient {
+    #[get(url = "/artifacts/{id}")]
+    async fn artifact(&self, id: u32) -> anyhow::Result<ByteStream> {}
+
+    #[get(url = "/artifacts/{id}")]
+    async fn artifact_chunks(&self, id: u32) -> anyhow::Result<impl Stream<Item = anyhow::Result<Bytes>>> {}
+
+    #[get(url = "/artifacts/{id}", download_to = dest)]
+    async fn download(&self, id: u32, dest: &std::path::Path) -> anyhow::Result<u64> {}
+}
+
+fn payload() -> Vec<u8> {
+    (0..256 * 1024).map(|i| (i % 251) as u8).collect()
+}
+
+async fn client_for(server: &TestServer) -> DownloadClient {
+    DownloadClient::builder().base_url(&server.base_url).build().unwrap()
+}
+
+#[tokio::test]
+async fn byte_stream_yields_whole_body() {
+    let server = TestServer::start(|_| MockResponse::new(200).body(payload())).await;
+    let client = client_for(&server).await;
+    let calls_before = AFTER_RESPONSE_CALLS.load(Ordering::SeqCst);
+
+    let mut stream = client.artifact(1).await.unwrap();
+    let mut received = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        received.extend_from_slice(&chunk.unwrap());
+    }
+
+    assert_eq!(received, payload());
+    assert!(AFTER_RESPONSE_CALLS.load(Ordering::SeqCst) > calls_before);
+}
+
+#[tokio::test]
+async fn impl_stream_return_type() {
+    let server = TestServer::start(|_| MockResponse::new(200).body(payload())).await;
+    let client = client_for(&server).await;
+
+    let chunks: Vec<Bytes> = client.artifact_chunks(2).await.unwrap()
+        .map(|chunk| chunk.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(chunks.concat(), payload());
+    assert_eq!(server.requests()[0].path, "/artifacts/2");
+}
+
+#[tokio::test]
+async fn download_to_writes_file_and_returns_byte_count() {
+    let server = TestServer::start(|_| MockResponse::new(200).body(payload())).await;
+    let client = client_for(&server).await;
+    let dest = std::env::temp_dir().join(format!("swan-download-{}.bin", std::process::id()));
+
+    let written = client.download(3, &dest).await.unwrap();
+
+    assert_eq!(written, payload().len() as u64);
+    assert_eq!(tokio::fs::read(&dest).await.unwrap(), payload());
+    tokio::fs::remove_file(&dest).await.unwrap();
+}
+
+#[tokio::test]
+async fn streaming_keeps_status_errors() {
+    let server = TestServer::start(|_| MockResponse::new(404).body("missing")).await;
+    let client = client_for(&server).await;
+    let dest = std::env::temp_dir().join(format!("swan-download-missing-{}.bin", std::process::id()));
+
+    let error = client.download(4, &dest).await.unwrap_err();
+
+    let error = error.downcast::<swan_common::SwanError>().unwrap();
+    assert_eq!(error.status().unwrap(), 404);
+    assert!(!dest.exists());
+}
+
+/// 声明的长度大于实际发送的字节数，发送一部分后关闭连接
+async fn truncating_server() -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).await;
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\npartial").await;
+        }
+    });
+    base_url
+}
+
+#[tokio::test]
+async fn failed_download_keeps_existing_file() {
+    let client = DownloadClient::builder().base_url(truncating_server().await).build().unwrap();
+    let dir = std::env::temp_dir().join(format!("swan-download-partial-{}", fastrand::u64(..)));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dest = dir.join("artifact.bin");
+    std::fs::write(&dest, "previous").unwrap();
+
+    assert!(client.download(5, &dest).await.is_err());
+
+    assert_eq!(std::fs::read(&dest).unwrap(), b"previous");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::hanging_server;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use swan_common::SwanError;
+use swan_macro::{get, http_client};
+
+#[http_client(base_url = "http://unused", timeout = "150ms", connect_timeout = "1s")]
+struct SlowClient;
+
+impl SlowClient {
+    #[get(url = "/report")]
+    async fn report(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/report", timeout = "50ms")]
+    async fn report_fast(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/report", timeout = "50ms", retry = "exponential(3, 1ms)")]
+    async fn report_with_retry(&self) -> Result<String, SwanError> {}
+
+    #[get(url = "/report", proxy = false)]
+    async fn report_without_proxy(&self) -> Result<String, SwanError> {}
+}
+
+#[tokio::test]
+async fn client_timeout_from_macro_attribute() {
+    let (base_url, _) = hanging_server().await;
+    let client = SlowClient::builder().base_url(base_url).build().unwrap();
+
+    let started = Instant::now();
+    let error = client.report().await.unwrap_err();
+
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn client_timeout_applies_to_method_level_proxy_settings() {
+    let (base_url, _) = hanging_server().await;
+    let client = SlowClient::builder().base_url(base_url).build().unwrap();
+
+    let started = Instant::now();
+    let error = client.report_without_proxy().await.unwrap_err();
+
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn method_timeout_overrides_client_timeout() {
+    let (base_url, _) = hanging_server().await;
+    let client = SlowClient::builder()
+        .base_url(base_url)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let error = client.report_fast().await.unwrap_err();
+
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn timeouts_are_retried() {
+    let (base_url, accepted) = hanging_server().await;
+    let client = SlowClient::builder().base_url(base_url).build().unwrap();
+
+    let error = client.report_with_retry().await.unwrap_err();
+
+    assert!(error.is_timeout(), "unexpected error: {}", error);
+    assert_eq!(accepted.load(Ordering::SeqCst), 3);
+}
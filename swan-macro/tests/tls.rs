@@ -0,0 +1,274 @@
+//! 使用 rcgen 生成的私有 CA 与证书，对本地 rustls 服务器测试 TLS 配置
+
+use p12_keystore::{Certificate, KeyStore, KeyStoreEntry, PrivateKeyChain};
+use rcgen::{BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
+use swan_common::tls::spki_pin;
+use swan_common::{SwanError, TlsConfig, TlsVersion};
+use swan_macro::{get, http_client};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig, crypto, version};
+
+const CERT_DIR: &str = "target/swan-tls-test";
+
+#[derive(Debug, Deserialize)]
+struct Peer {
+    client_cert: bool,
+}
+
+#[http_client(
+    base_url = "https://127.0.0.1",
+    tls(
+        ca = "target/swan-tls-test/ca.pem",
+        identity = "target/swan-tls-test/client.p12",
+        identity_password_env = "SWAN_TLS_TEST_P12_PASSWORD",
+        min_version = "1.2",
+        danger_accept_invalid_certs = false,
+    ),
+)]
+struct InternalClient;
+
+impl InternalClient {
+    #[get(url = "/whoami")]
+    async fn whoami(&self) -> Result<Peer, SwanError> {}
+
+    #[get(url = "/whoami", proxy = false)]
+    async fn whoami_without_proxy(&self) -> Result<Peer, SwanError> {}
+}
+
+/// 测试用 PKI：CA、服务端证书（127.0.0.1）与客户端证书，证书文件写入 `CERT_DIR`
+///
+/// 另有一个无关 CA 及其签发的服务端证书，用于验证服务端附带的证书不参与公钥固定。
+struct Pki {
+    ca: CertificateDer<'static>,
+    server: CertificateDer<'static>,
+    server_key: Vec<u8>,
+    client: CertificateDer<'static>,
+    other_ca: CertificateDer<'static>,
+    other_server: CertificateDer<'static>,
+    other_server_key: Vec<u8>,
+}
+
+fn pki() -> &'static Pki {
+    static PKI: OnceLock<Pki> = OnceLock::new();
+    PKI.get_or_init(|| {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let server = CertificateParams::new(vec!["127.0.0.1".to_string()]).unwrap()
+            .signed_by(&server_key, &ca, &ca_key)
+            .unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(Vec::new()).unwrap();
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client = client_params.signed_by(&client_key, &ca, &ca_key).unwrap();
+
+        let other_ca_key = KeyPair::generate().unwrap();
+        let mut other_ca_params = CertificateParams::new(Vec::new()).unwrap();
+        other_ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let other_ca = other_ca_params.self_signed(&other_ca_key).unwrap();
+        let other_server_key = KeyPair::generate().unwrap();
+        let other_server = CertificateParams::new(vec!["127.0.0.1".to_string()]).unwrap()
+            .signed_by(&other_server_key, &other_ca, &other_ca_key)
+            .unwrap();
+
+        let mut store = KeyStore::new();
+        let chain = [client.der(), ca.der()].map(|cert| Certificate::from_der(cert).unwrap());
+        store.add_entry("client", KeyStoreEntry::PrivateKeyChain(PrivateKeyChain::new(client_key.serialize_der(), [1u8; 20], chain)));
+        let p12 = store.writer("p12-secret").write().unwrap();
+
+        std::fs::create_dir_all(CERT_DIR).unwrap();
+        std::fs::write(format!("{}/ca.pem", CERT_DIR), ca.pem()).unwrap();
+        std::fs::write(format!("{}/other-ca.pem", CERT_DIR), other_ca.pem()).unwrap();
+        std::fs::write(format!("{}/client.p12", CERT_DIR), p12).unwrap();
+        std::fs::write(format!("{}/client.pem", CERT_DIR), client.pem() + &client_key.serialize_pem()).unwrap();
+        // SAFETY: 只有这个测试文件读写该环境变量，且在生成证书时一次性设置
+        unsafe { std::env::set_var("SWAN_TLS_TEST_P12_PASSWORD", "p12-secret") };
+
+        Pki {
+            ca: ca.der().clone(),
+            server: server.der().clone(),
+            server_key: server_key.serialize_der(),
+            client: client.der().clone(),
+            other_ca: other_ca.der().clone(),
+            other_server: other_server.der().clone(),
+            other_server_key: other_server_key.serialize_der(),
+        }
+    })
+}
+
+/// 启动 HTTPS 服务器，返回 base_url；响应体说明客户端是否出示了证书
+async fn start_server(require_client_cert: bool, tls12_only: bool) -> String {
+    let pki = pki();
+    let provider = Arc::new(crypto::ring::default_provider());
+    let versions = if tls12_only { &[&version::TLS12][..] } else { &[&version::TLS13, &version::TLS12][..] };
+    let builder = ServerConfig::builder_with_provider(provider.clone()).with_protocol_versions(versions).unwrap();
+    let builder = if require_client_cert {
+        let mut roots = RootCertStore::empty();
+        roots.add(pki.ca.clone()).unwrap();
+        builder.with_client_cert_verifier(WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build().unwrap())
+    } else {
+        builder.with_no_client_auth()
+    };
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(pki.server_key.clone()));
+    serve(builder.with_single_cert(vec![pki.server.clone(), pki.ca.clone()], key).unwrap()).await
+}
+
+/// 启动出示无关 CA 签发的服务端证书、并在链尾附带测试 CA 证书的 HTTPS 服务器
+async fn start_server_with_appended_ca() -> String {
+    let pki = pki();
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(pki.other_server_key.clone()));
+    let config = ServerConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(vec![pki.other_server.clone(), pki.other_ca.clone(), pki.ca.clone()], key)
+        .unwrap();
+    serve(config).await
+}
+
+async fn serve(config: ServerConfig) -> String {
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("https://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(stream).await else { return };
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut chunk).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&chunk[..n]),
+                    }
+                }
+                let body = format!(r#"{{"client_cert":{}}}"#, stream.get_ref().1.peer_certificates().is_some());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body,
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    base_url
+}
+
+async fn whoami(base_url: &str, tls: TlsConfig) -> Result<Peer, SwanError> {
+    InternalClient::builder().base_url(base_url).tls(tls).build()?.whoami().await
+}
+
+/// 错误及其全部来源的描述
+fn error_chain(error: &SwanError) -> String {
+    let mut chain = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        chain.push_str(": ");
+        chain.push_str(&error.to_string());
+        source = error.source();
+    }
+    chain
+}
+
+#[tokio::test]
+async fn mtls_with_private_ca_and_client_identity() {
+    let base_url = start_server(true, false).await;
+    let ca = format!("{}/ca.pem", CERT_DIR);
+
+    // 宏参数：私有 CA + PKCS#12 客户端证书，密码从环境变量读取
+    let client = InternalClient::builder().base_url(&base_url).build().unwrap();
+    assert!(client.whoami().await.unwrap().client_cert);
+    // 方法级代理设置使用独立的客户端，同样应用 TLS 配置
+    assert!(client.whoami_without_proxy().await.unwrap().client_cert);
+
+    // 运行时覆盖为 PEM 客户端证书
+    let pem_identity = TlsConfig::new().ca(&ca).identity(format!("{}/client.pem", CERT_DIR));
+    assert!(whoami(&base_url, pem_identity).await.unwrap().client_cert);
+
+    // 不出示客户端证书时服务端拒绝握手
+    assert!(whoami(&base_url, TlsConfig::new().ca(&ca)).await.is_err());
+}
+
+#[tokio::test]
+async fn private_ca_is_required_unless_certificates_are_not_verified() {
+    let base_url = start_server(false, false).await;
+
+    let error = whoami(&base_url, TlsConfig::new()).await.unwrap_err();
+    assert!(error.is_connect(), "{}", error_chain(&error));
+    assert!(error_chain(&error).contains("UnknownIssuer"));
+
+    let peer = whoami(&base_url, TlsConfig::new().danger_accept_invalid_certs(true)).await.unwrap();
+    assert!(!peer.client_cert);
+
+    // 缺少 PKCS#12 密码在构建时报错
+    let error = InternalClient::builder()
+        .tls(TlsConfig::new().identity(format!("{}/client.p12", CERT_DIR)).identity_password(swan_common::Secret::env("SWAN_TLS_TEST_UNSET")))
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), "auth");
+}
+
+#[tokio::test]
+async fn spki_pins_are_checked_against_the_server_chain() {
+    let base_url = start_server(false, false).await;
+    let pki = pki();
+    let ca = format!("{}/ca.pem", CERT_DIR);
+    let server_pin = spki_pin(&pki.server).unwrap();
+    let ca_pin = spki_pin(&pki.ca).unwrap();
+    let other_pin = spki_pin(&pki.client).unwrap();
+
+    // 固定服务端或中间证书的公钥均可
+    whoami(&base_url, TlsConfig::new().ca(&ca).pin(&server_pin)).await.unwrap();
+    whoami(&base_url, TlsConfig::new().ca(&ca).pin(&other_pin).pin(&ca_pin)).await.unwrap();
+
+    let error = whoami(&base_url, TlsConfig::new().ca(&ca).pin(&other_pin)).await.unwrap_err();
+    assert!(error.is_connect());
+    assert!(error_chain(&error).contains("pinned public keys"), "{}", error_chain(&error));
+
+    // 跳过证书链校验时仍校验公钥固定
+    let insecure = TlsConfig::new().danger_accept_invalid_certs(true);
+    whoami(&base_url, insecure.clone().pin(&server_pin)).await.unwrap();
+    assert!(whoami(&base_url, insecure.pin(&other_pin)).await.is_err());
+}
+
+#[tokio::test]
+async fn spki_pins_ignore_certificates_appended_outside_the_verified_chain() {
+    let base_url = start_server_with_appended_ca().await;
+    let pki = pki();
+    let ca_pin = spki_pin(&pki.ca).unwrap();
+    let other_ca_pin = spki_pin(&pki.other_ca).unwrap();
+    let both_cas = TlsConfig::new().ca(format!("{}/ca.pem", CERT_DIR)).ca(format!("{}/other-ca.pem", CERT_DIR));
+
+    // 证书链经无关 CA 验证通过，附带的固定 CA 证书不在验证路径上
+    whoami(&base_url, both_cas.clone().pin(&other_ca_pin)).await.unwrap();
+    let error = whoami(&base_url, both_cas.pin(&ca_pin)).await.unwrap_err();
+    assert!(error_chain(&error).contains("pinned public keys"), "{}", error_chain(&error));
+
+    // 跳过证书链校验时只比对服务端证书
+    let error = whoami(&base_url, TlsConfig::new().danger_accept_invalid_certs(true).pin(&ca_pin)).await.unwrap_err();
+    assert!(error_chain(&error).contains("pinned public keys"), "{}", error_chain(&error));
+}
+
+#[tokio::test]
+async fn min_version_rejects_older_servers() {
+    let base_url = start_server(false, true).await;
+    let ca = format!("{}/ca.pem", CERT_DIR);
+
+    whoami(&base_url, TlsConfig::new().ca(&ca).min_version(TlsVersion::Tls12)).await.unwrap();
+    let error = whoami(&base_url, TlsConfig::new().ca(&ca).min_version(TlsVersion::Tls13)).await.unwrap_err();
+    assert!(error.is_connect(), "{}", error_chain(&error));
+}
@@ -0,0 +1,159 @@
+use opentelemetry::trace::{TraceContextExt, TracerProvider};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use swan_common::testing::{MockResponse, MockTransport};
+use swan_macro::{get, http_client};
+use tracing::Instrument;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+
+#[http_client(base_url = "https://api.example.com")]
+struct TracedClient;
+
+impl TracedClient {
+    #[get(url = "/users/{id}", retry = "exponential(3, 1ms)")]
+    async fn get_user(&self, id: u32) -> anyhow::Result<String> {}
+}
+
+type SpanFields = (&'static str, HashMap<String, String>);
+
+/// 按创建顺序记录 span 名称与字段
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<(u64, SpanFields)>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for Recorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.spans.lock().unwrap().push((id.into_u64(), (attrs.metadata().name(), fields)));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((_, (_, fields))) = spans.iter_mut().rev().find(|(span_id, _)| *span_id == id.into_u64()) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+}
+
+impl Recorder {
+    fn spans(&self, name: &str) -> Vec<HashMap<String, String>> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, (span_name, _))| *span_name == name)
+            .map(|(_, (_, fields))| fields.clone())
+            .collect()
+    }
+}
+
+fn unavailable_then_ok(transport: &MockTransport) {
+    let _ = transport.mock("GET", "/users/7").respond_sequence([
+        MockResponse::new(503),
+        MockResponse::new(200).body("alice"),
+    ]);
+}
+
+#[tokio::test]
+async fn attempt_span_redacts_api_key_query_param() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/users/7").respond(MockResponse::new(200).body("alice"));
+    let client = TracedClient::builder()
+        .auth(swan_common::ApiKeyAuth::query("api_key", "s3cret"))
+        .mock_transport(transport.clone())
+        .build()
+        .unwrap();
+    client.get_user(7).await.unwrap();
+
+    assert_eq!(transport.requests()[0].url.query(), Some("api_key=s3cret"));
+    let attempts = recorder.spans("swan.attempt");
+    assert_eq!(attempts[0]["url.full"], "https://api.example.com/users/7?api_key=REDACTED");
+}
+
+#[tokio::test]
+async fn call_and_attempt_spans_carry_semantic_fields() {
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+    let transport = MockTransport::new();
+    unavailable_then_ok(&transport);
+    let client = TracedClient::with_mock_transport(transport.clone());
+    assert_eq!(client.get_user(7).await.unwrap(), "alice");
+
+    let calls = recorder.spans("swan.call");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0]["swan.method"], "get_user");
+    assert_eq!(calls[0]["http.request.method"], "GET");
+    assert_eq!(calls[0]["url.full"], "https://api.example.com/users/7");
+    assert_eq!(calls[0]["http.response.status_code"], "200");
+
+    let attempts = recorder.spans("swan.attempt");
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0]["swan.attempt"], "1");
+    assert_eq!(attempts[0]["http.response.status_code"], "503");
+    assert_eq!(attempts[0]["error.type"], "503");
+    assert_eq!(attempts[1]["swan.attempt"], "2");
+    assert_eq!(attempts[1]["http.response.status_code"], "200");
+    assert!(!attempts[1].contains_key("error.type"));
+}
+
+#[tokio::test]
+async fn traceparent_is_injected_per_attempt() {
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("swan-test"));
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+
+    let transport = MockTransport::new();
+    unavailable_then_ok(&transport);
+    let client = TracedClient::with_mock_transport(transport.clone());
+
+    let outer = tracing::info_span!("outer");
+    let trace_id = outer.context().span().span_context().trace_id().to_string();
+    client.get_user(7).instrument(outer).await.unwrap();
+
+    let traceparents: Vec<Vec<String>> = transport
+        .requests()
+        .iter()
+        .map(|request| request.header("traceparent").unwrap().split('-').map(str::to_string).collect())
+        .collect();
+    assert_eq!(traceparents.len(), 2);
+    // 两次尝试都属于调用方所在的链路，各自带有独立的 span id
+    for parts in &traceparents {
+        assert_eq!(parts[0], "00");
+        assert_eq!(parts[1], trace_id);
+        assert_eq!(parts[3], "01");
+    }
+    assert_ne!(traceparents[0][2], traceparents[1][2]);
+}
+
+#[tokio::test]
+async fn no_traceparent_without_opentelemetry_layer() {
+    let transport = MockTransport::new();
+    let _ = transport.mock("GET", "/users/7").respond(MockResponse::new(200).body("alice"));
+    let client = TracedClient::with_mock_transport(transport.clone());
+
+    client.get_user(7).await.unwrap();
+
+    assert!(transport.requests()[0].header("traceparent").is_none());
+}